@@ -1,5 +1,9 @@
+#[cfg(feature = "dynamic_plugins")]
+use crate::dynamic_plugin::DynamicPlugins;
 use crate::{app_builder::AppBuilder, DefaultTaskPoolOptions};
 use bevy_ecs::{ParallelExecutor, Resources, Schedule, World};
+use bevy_utils::HashSet;
+use std::any::TypeId;
 
 #[allow(clippy::needless_doctest_main)]
 /// Containers of app logic and data
@@ -31,6 +35,9 @@ pub struct App {
     pub executor: ParallelExecutor,
     pub startup_schedule: Schedule,
     pub startup_executor: ParallelExecutor,
+    pub(crate) plugins: HashSet<TypeId>,
+    #[cfg(feature = "dynamic_plugins")]
+    pub(crate) dynamic_plugins: DynamicPlugins,
 }
 
 impl Default for App {
@@ -43,6 +50,9 @@ impl Default for App {
             startup_schedule: Default::default(),
             startup_executor: ParallelExecutor::without_tracker_clears(),
             runner: Box::new(run_once),
+            plugins: Default::default(),
+            #[cfg(feature = "dynamic_plugins")]
+            dynamic_plugins: Default::default(),
         }
     }
 }
@@ -57,13 +67,18 @@ impl App {
     }
 
     pub fn update(&mut self) {
+        #[cfg(feature = "dynamic_plugins")]
+        DynamicPlugins::reload_changed(self);
+
         self.schedule
             .initialize(&mut self.world, &mut self.resources);
         self.executor
             .run(&mut self.schedule, &mut self.world, &mut self.resources);
     }
 
-    pub fn run(mut self) {
+    /// Creates the default task pools and runs the startup schedule. This is called by [App::run] before the runner
+    /// takes over.
+    pub fn startup(&mut self) {
         // Setup the default bevy task pools
         self.resources
             .get_cloned::<DefaultTaskPoolOptions>()
@@ -77,6 +92,10 @@ impl App {
             &mut self.world,
             &mut self.resources,
         );
+    }
+
+    pub fn run(mut self) {
+        self.startup();
 
         let runner = std::mem::replace(&mut self.runner, Box::new(run_once));
         (runner)(self);
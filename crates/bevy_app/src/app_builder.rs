@@ -1,12 +1,16 @@
 #[cfg(feature = "dynamic_plugins")]
-use crate::plugin::dynamically_load_plugin;
+use crate::dynamic_plugin::{DynamicPluginError, DynamicPlugins, PluginReloadHook};
 use crate::{
     app::{App, AppExit},
     event::Events,
-    plugin::Plugin,
+    plugin::{Plugin, PluginError},
+    plugin_group::{PluginGroup, PluginGroupBuilder},
     stage, startup_stage,
+    task::pending_task_system,
+    test_app::TestApp,
 };
-use bevy_ecs::{FromResources, IntoQuerySystem, Resources, System, World};
+use bevy_ecs::{Component, FromResources, IntoQuerySystem, Resources, System, World};
+use std::any::TypeId;
 
 /// Configure [App]s using the builder pattern
 pub struct AppBuilder {
@@ -15,9 +19,7 @@ pub struct AppBuilder {
 
 impl Default for AppBuilder {
     fn default() -> Self {
-        let mut app_builder = AppBuilder {
-            app: App::default(),
-        };
+        let mut app_builder = AppBuilder::empty();
 
         app_builder.add_default_stages();
         app_builder.add_event::<AppExit>();
@@ -45,6 +47,12 @@ impl AppBuilder {
         app.run();
     }
 
+    /// Builds a [TestApp] that can be updated step by step. This runs the app's startup systems.
+    pub fn test_app(&mut self) -> TestApp {
+        let app = std::mem::take(&mut self.app);
+        TestApp::new(app)
+    }
+
     pub fn set_world(&mut self, world: World) -> &mut Self {
         self.app.world = world;
         self
@@ -221,6 +229,26 @@ impl AppBuilder {
             .add_system_to_stage(stage::EVENT_UPDATE, Events::<T>::update_system.system())
     }
 
+    /// Adds [persistent](Events::persistent) events of type `T`, which are kept until every reader has read them. At most
+    /// `max_events` unread events are kept. Systems read them with a `Local<PersistentEventReader<T>>`, which is registered
+    /// when the system is initialized.
+    pub fn add_persistent_event<T>(&mut self, max_events: usize) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.add_resource(Events::<T>::persistent(max_events))
+            .add_system_to_stage(stage::EVENT_UPDATE, Events::<T>::update_system.system())
+    }
+
+    /// Registers a system that polls [PendingTask](crate::PendingTask)s with output `T` each frame and inserts
+    /// their output into the task's entity once finished
+    pub fn add_task_component<T>(&mut self) -> &mut Self
+    where
+        T: Component,
+    {
+        self.add_system_to_stage(stage::PRE_UPDATE, pending_task_system::<T>.system())
+    }
+
     pub fn add_resource<T>(&mut self, resource: T) -> &mut Self
     where
         T: Send + Sync + 'static,
@@ -244,20 +272,119 @@ impl AppBuilder {
         self
     }
 
+    /// Loads the plugin library at `path` and adds its plugin to the app. The library must export the
+    /// [CreatePlugin](crate::CreatePlugin) function. Panics if the library or plugin fails to load.
     #[cfg(feature = "dynamic_plugins")]
     pub fn load_plugin(&mut self, path: &str) -> &mut Self {
-        let (_lib, plugin) = dynamically_load_plugin(path);
-        log::debug!("loaded plugin: {}", plugin.name());
-        plugin.build(self);
+        if let Err(err) = self.try_load_plugin(path) {
+            panic!("{}", err);
+        }
+        self
+    }
+
+    #[cfg(feature = "dynamic_plugins")]
+    pub fn try_load_plugin(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<&mut Self, DynamicPluginError> {
+        DynamicPlugins::load(&mut self.app, path.as_ref(), false)?;
+        Ok(self)
+    }
+
+    /// Loads the plugin library at `path` like [AppBuilder::load_plugin], then watches it for changes. When the
+    /// library changes, the plugin's systems and resources are removed, the library is unloaded and the new version
+    /// is built. If the new version fails to load, the old version keeps running.
+    ///
+    /// Startup systems of the new version are not run. World data is preserved across a reload by
+    /// [PluginReloadHook]s, e.g. `TypeRegistryPlugin` preserves components registered by the plugin. Any other
+    /// components defined by the plugin must be removed before the reload, because their code is unloaded.
+    #[cfg(feature = "dynamic_plugins")]
+    pub fn load_reloadable_plugin(&mut self, path: impl AsRef<std::path::Path>) -> &mut Self {
+        if let Err(err) = DynamicPlugins::load(&mut self.app, path.as_ref(), true) {
+            panic!("{}", err);
+        }
+        self
+    }
+
+    /// Adds a hook that observes dynamic plugins loaded after this call. See [PluginReloadHook].
+    #[cfg(feature = "dynamic_plugins")]
+    pub fn add_plugin_reload_hook(&mut self, hook: impl PluginReloadHook) -> &mut Self {
+        self.app.dynamic_plugins.add_hook(Box::new(hook));
         self
     }
 
+    /// Adds `plugin` to the app. Panics if the plugin was already added or one of its
+    /// [dependencies](Plugin::dependencies) is missing. See [AppBuilder::try_add_plugin] for a non-panicking version.
     pub fn add_plugin<T>(&mut self, plugin: T) -> &mut Self
     where
         T: Plugin,
     {
+        self.add_boxed_plugin(Box::new(plugin))
+    }
+
+    pub fn add_boxed_plugin(&mut self, plugin: Box<dyn Plugin>) -> &mut Self {
+        if let Err(err) = self.try_add_boxed_plugin(plugin) {
+            panic!("{}", err);
+        }
+        self
+    }
+
+    /// Adds `plugin` to the app, or returns an error if the plugin was already added or one of its
+    /// [dependencies](Plugin::dependencies) is missing
+    pub fn try_add_plugin<T>(&mut self, plugin: T) -> Result<&mut Self, PluginError>
+    where
+        T: Plugin,
+    {
+        self.try_add_boxed_plugin(Box::new(plugin))
+    }
+
+    pub fn try_add_boxed_plugin(
+        &mut self,
+        plugin: Box<dyn Plugin>,
+    ) -> Result<&mut Self, PluginError> {
+        let type_id = plugin.as_ref().type_id();
+        if self.app.plugins.contains(&type_id) {
+            return Err(PluginError::Duplicate(plugin.name().to_string()));
+        }
+
+        for dependency in plugin.dependencies() {
+            if !self.app.plugins.contains(&dependency.type_id) {
+                return Err(PluginError::MissingDependency {
+                    plugin: plugin.name().to_string(),
+                    dependency: dependency.name,
+                });
+            }
+        }
+
         log::debug!("added plugin: {}", plugin.name());
+        self.app.plugins.insert(type_id);
         plugin.build(self);
+        Ok(self)
+    }
+
+    /// Returns true if a plugin of type `T` has been added to the app
+    pub fn has_plugin<T>(&self) -> bool
+    where
+        T: Plugin,
+    {
+        self.app.plugins.contains(&TypeId::of::<T>())
+    }
+
+    /// Adds every enabled plugin in `group`, in order
+    pub fn add_plugins<T: PluginGroup>(&mut self, group: T) -> &mut Self {
+        self.add_plugins_with(group, |group| group)
+    }
+
+    /// Adds the plugins in `group` after `func` has had a chance to disable, replace or reorder them
+    pub fn add_plugins_with<T, F>(&mut self, mut group: T, func: F) -> &mut Self
+    where
+        T: PluginGroup,
+        F: FnOnce(&mut PluginGroupBuilder) -> &mut PluginGroupBuilder,
+    {
+        let mut plugin_group_builder = PluginGroupBuilder::default();
+        group.build(&mut plugin_group_builder);
+        func(&mut plugin_group_builder);
+        plugin_group_builder.finish(self);
         self
     }
 }
@@ -0,0 +1,431 @@
+use crate::{
+    app::App,
+    app_builder::AppBuilder,
+    plugin::{CreatePlugin, Plugin, PluginError},
+};
+use bevy_ecs::{Resources, SystemId, World};
+use bevy_utils::HashSet;
+use libloading::{Library, Symbol};
+use std::{
+    any::TypeId,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant, SystemTime},
+};
+use thiserror::Error;
+
+/// How long a watched plugin library must stay unchanged before it is reloaded. This avoids loading a library that
+/// is still being written by the linker.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// An error that occurs when a dynamic plugin is loaded or reloaded
+#[derive(Error, Debug)]
+pub enum DynamicPluginError {
+    #[error("Failed to access plugin library {path:?}.")]
+    Io {
+        path: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error("Failed to load plugin library {path:?}.")]
+    Load {
+        path: PathBuf,
+        #[source]
+        error: libloading::Error,
+    },
+    #[error(transparent)]
+    Plugin(#[from] PluginError),
+}
+
+/// Observes dynamically loaded plugins as they are built, unloaded and reloaded. Hooks carry app state across a
+/// reload that would otherwise be lost when the plugin's library is unloaded. For example, `TypeRegistryPlugin`
+/// registers a hook that preserves the plugin's registered components.
+///
+/// Hooks are added using [AppBuilder::add_plugin_reload_hook]. They only observe plugins that are loaded after the
+/// hook was added.
+pub trait PluginReloadHook: Send + Sync + 'static {
+    /// Called before a dynamic plugin is built, both when it is first loaded and when it is reloaded
+    fn build_started(&mut self, _world: &mut World, _resources: &mut Resources) {}
+
+    /// Called after the dynamic plugin named `plugin` was built
+    fn build_finished(&mut self, _plugin: &str, _world: &mut World, _resources: &mut Resources) {}
+
+    /// Called before the systems and resources of `plugin` are removed and its library is unloaded
+    fn before_unload(&mut self, _plugin: &str, _world: &mut World, _resources: &mut Resources) {}
+
+    /// Called after the new version of `plugin` was built
+    fn after_reload(&mut self, _plugin: &str, _world: &mut World, _resources: &mut Resources) {}
+}
+
+struct WatchState {
+    modified: SystemTime,
+    changed_at: Option<Instant>,
+}
+
+/// A loaded plugin library. Watched libraries are loaded from a copy, which allows the original to be overwritten
+/// and ensures a rebuilt library is not confused with the one that is already loaded.
+struct PluginLibrary {
+    library: Option<Library>,
+    copy_path: Option<PathBuf>,
+}
+
+impl PluginLibrary {
+    fn load(
+        path: &Path,
+        copy: bool,
+    ) -> Result<(PluginLibrary, Box<dyn Plugin>), DynamicPluginError> {
+        let copy_path = if copy {
+            let copy_path = library_copy_path(path);
+            std::fs::copy(path, &copy_path).map_err(|error| DynamicPluginError::Io {
+                path: path.to_owned(),
+                error,
+            })?;
+            Some(copy_path)
+        } else {
+            None
+        };
+
+        // constructed before the library is loaded, so the copy is removed if loading fails
+        let mut plugin_library = PluginLibrary {
+            library: None,
+            copy_path,
+        };
+        let load_path = plugin_library.copy_path.as_deref().unwrap_or(path);
+        let load_error = |error| DynamicPluginError::Load {
+            path: path.to_owned(),
+            error,
+        };
+        let library = Library::new(load_path).map_err(load_error)?;
+        let plugin = unsafe {
+            let func: Symbol<CreatePlugin> = library.get(b"_create_plugin").map_err(load_error)?;
+            Box::from_raw(func())
+        };
+        plugin_library.library = Some(library);
+        Ok((plugin_library, plugin))
+    }
+}
+
+impl Drop for PluginLibrary {
+    fn drop(&mut self) {
+        // the library must be unloaded before its copy can be removed on some platforms
+        self.library.take();
+        if let Some(copy_path) = self.copy_path.take() {
+            if let Err(err) = std::fs::remove_file(&copy_path) {
+                log::warn!(
+                    "failed to remove plugin library copy {:?}: {}",
+                    copy_path,
+                    err
+                );
+            }
+        }
+    }
+}
+
+fn library_copy_path(path: &Path) -> PathBuf {
+    static COPY_COUNT: AtomicUsize = AtomicUsize::new(0);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut file_name = format!(
+        "{}-{}-{}",
+        stem,
+        std::process::id(),
+        COPY_COUNT.fetch_add(1, Ordering::Relaxed)
+    );
+    if let Some(extension) = path.extension() {
+        file_name.push('.');
+        file_name.push_str(&extension.to_string_lossy());
+    }
+
+    std::env::temp_dir().join(file_name)
+}
+
+/// A plugin that was loaded from a library, along with everything it added to the app
+struct LoadedPlugin {
+    name: String,
+    type_id: TypeId,
+    path: PathBuf,
+    watch: Option<WatchState>,
+    systems: Vec<SystemId>,
+    startup_systems: Vec<SystemId>,
+    resources: Vec<TypeId>,
+    // declared last so the library outlives every other field
+    _library: PluginLibrary,
+}
+
+impl LoadedPlugin {
+    /// Returns true if the watched library changed and has stayed unchanged for [RELOAD_DEBOUNCE]
+    fn should_reload(&mut self) -> bool {
+        let watch = match &mut self.watch {
+            Some(watch) => watch,
+            None => return false,
+        };
+
+        // the library may briefly not exist while it is being rebuilt
+        let modified = match std::fs::metadata(&self.path).and_then(|metadata| metadata.modified())
+        {
+            Ok(modified) => modified,
+            Err(_) => return false,
+        };
+
+        if modified != watch.modified {
+            watch.modified = modified;
+            watch.changed_at = Some(Instant::now());
+            return false;
+        }
+
+        match watch.changed_at {
+            Some(changed_at) if changed_at.elapsed() >= RELOAD_DEBOUNCE => {
+                watch.changed_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The plugins that were loaded from libraries, and the hooks that observe them
+#[derive(Default)]
+pub(crate) struct DynamicPlugins {
+    plugins: Vec<LoadedPlugin>,
+    hooks: Vec<Box<dyn PluginReloadHook>>,
+}
+
+impl DynamicPlugins {
+    pub(crate) fn add_hook(&mut self, hook: Box<dyn PluginReloadHook>) {
+        self.hooks.push(hook);
+    }
+
+    /// Loads the plugin library at `path` and builds the plugin. If `watch` is true, the plugin is reloaded whenever
+    /// the library changes.
+    pub(crate) fn load(app: &mut App, path: &Path, watch: bool) -> Result<(), DynamicPluginError> {
+        let watch = if watch {
+            Some(WatchState {
+                modified: modified_time(path)?,
+                changed_at: None,
+            })
+        } else {
+            None
+        };
+
+        let (library, plugin) = PluginLibrary::load(path, watch.is_some())?;
+        Self::with(app, |dynamic_plugins, app| {
+            let loaded_plugin = dynamic_plugins.build(app, path, watch, library, plugin)?;
+            log::debug!("loaded dynamic plugin: {}", loaded_plugin.name);
+            dynamic_plugins.plugins.push(loaded_plugin);
+            Ok(())
+        })
+    }
+
+    /// Reloads every watched plugin whose library changed
+    pub(crate) fn reload_changed(app: &mut App) {
+        if app
+            .dynamic_plugins
+            .plugins
+            .iter()
+            .all(|plugin| plugin.watch.is_none())
+        {
+            return;
+        }
+
+        Self::with(app, |dynamic_plugins, app| {
+            let mut index = 0;
+            while index < dynamic_plugins.plugins.len() {
+                if dynamic_plugins.plugins[index].should_reload() {
+                    let name = dynamic_plugins.plugins[index].name.clone();
+                    match dynamic_plugins.reload(app, index) {
+                        Ok(()) => log::info!("reloaded dynamic plugin: {}", name),
+                        Err(err) => {
+                            log::error!("failed to reload dynamic plugin {}: {}", name, err)
+                        }
+                    }
+                }
+                index += 1;
+            }
+        });
+    }
+
+    /// Runs `func` with the app's [DynamicPlugins] temporarily taken out of the app, so hooks can be given mutable
+    /// access to the app
+    fn with<T>(app: &mut App, func: impl FnOnce(&mut DynamicPlugins, &mut App) -> T) -> T {
+        let mut dynamic_plugins = std::mem::take(&mut app.dynamic_plugins);
+        let result = func(&mut dynamic_plugins, app);
+        // keep anything that was added to the app while it was taken, e.g. hooks added by a plugin's build
+        let added = std::mem::replace(&mut app.dynamic_plugins, dynamic_plugins);
+        app.dynamic_plugins.plugins.extend(added.plugins);
+        app.dynamic_plugins.hooks.extend(added.hooks);
+        result
+    }
+
+    fn reload(&mut self, app: &mut App, index: usize) -> Result<(), DynamicPluginError> {
+        // load the new version first, so the old version keeps running if it fails to load
+        let path = self.plugins[index].path.clone();
+        let modified = modified_time(&path)?;
+        let (library, plugin) = PluginLibrary::load(&path, true)?;
+        for dependency in plugin.dependencies() {
+            if !app.plugins.contains(&dependency.type_id) {
+                return Err(PluginError::MissingDependency {
+                    plugin: plugin.name().to_string(),
+                    dependency: dependency.name,
+                }
+                .into());
+            }
+        }
+
+        let old_plugin = self.plugins.remove(index);
+        let name = old_plugin.name.clone();
+        self.unload(app, old_plugin);
+
+        let watch = Some(WatchState {
+            modified,
+            changed_at: None,
+        });
+        let loaded_plugin = self.build(app, &path, watch, library, plugin)?;
+        self.plugins.insert(index, loaded_plugin);
+        for hook in self.hooks.iter_mut() {
+            hook.after_reload(&name, &mut app.world, &mut app.resources);
+        }
+
+        Ok(())
+    }
+
+    /// Builds `plugin` and records the systems and resources it adds to the app
+    fn build(
+        &mut self,
+        app: &mut App,
+        path: &Path,
+        watch: Option<WatchState>,
+        library: PluginLibrary,
+        plugin: Box<dyn Plugin>,
+    ) -> Result<LoadedPlugin, PluginError> {
+        for hook in self.hooks.iter_mut() {
+            hook.build_started(&mut app.world, &mut app.resources);
+        }
+
+        let systems = app.schedule.system_ids().collect::<HashSet<_>>();
+        let startup_systems = app.startup_schedule.system_ids().collect::<HashSet<_>>();
+        let resources = app.resources.type_ids().collect::<HashSet<_>>();
+        let name = plugin.name().to_string();
+        let type_id = plugin.as_ref().type_id();
+
+        let mut app_builder = AppBuilder {
+            app: std::mem::take(app),
+        };
+        let result = app_builder.try_add_boxed_plugin(plugin).map(|_| ());
+        *app = app_builder.app;
+        result?;
+
+        for hook in self.hooks.iter_mut() {
+            hook.build_finished(&name, &mut app.world, &mut app.resources);
+        }
+
+        Ok(LoadedPlugin {
+            name,
+            type_id,
+            path: path.to_owned(),
+            watch,
+            systems: app
+                .schedule
+                .system_ids()
+                .filter(|id| !systems.contains(id))
+                .collect(),
+            startup_systems: app
+                .startup_schedule
+                .system_ids()
+                .filter(|id| !startup_systems.contains(id))
+                .collect(),
+            resources: app
+                .resources
+                .type_ids()
+                .filter(|id| !resources.contains(id))
+                .collect(),
+            _library: library,
+        })
+    }
+
+    /// Removes everything `plugin` added to the app, then unloads its library
+    fn unload(&mut self, app: &mut App, plugin: LoadedPlugin) {
+        for hook in self.hooks.iter_mut() {
+            hook.before_unload(&plugin.name, &mut app.world, &mut app.resources);
+        }
+
+        for id in plugin.systems.iter() {
+            app.schedule.remove_system(*id);
+            app.resources.remove_local_resources(*id);
+        }
+
+        for id in plugin.startup_systems.iter() {
+            app.startup_schedule.remove_system(*id);
+            app.resources.remove_local_resources(*id);
+        }
+
+        for type_id in plugin.resources.iter() {
+            app.resources.remove_type(*type_id);
+        }
+
+        app.plugins.remove(&plugin.type_id);
+    }
+}
+
+fn modified_time(path: &Path) -> Result<SystemTime, DynamicPluginError> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|error| DynamicPluginError::Io {
+            path: path.to_owned(),
+            error,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{IntoQuerySystem, Local, ResMut};
+
+    struct Counter(usize);
+
+    fn count_system(mut local: Local<usize>, mut counter: ResMut<Counter>) {
+        *local += 1;
+        counter.0 = *local;
+    }
+
+    struct CounterPlugin;
+
+    impl Plugin for CounterPlugin {
+        fn build(&self, app: &mut AppBuilder) {
+            app.add_resource(Counter(0))
+                .add_system(count_system.system());
+        }
+    }
+
+    #[test]
+    fn unload_removes_systems_and_resources() {
+        let mut app = App::build().app;
+        let mut dynamic_plugins = DynamicPlugins::default();
+        let library = PluginLibrary {
+            library: None,
+            copy_path: None,
+        };
+        let loaded_plugin = dynamic_plugins
+            .build(
+                &mut app,
+                Path::new("counter"),
+                None,
+                library,
+                Box::new(CounterPlugin),
+            )
+            .unwrap();
+        assert_eq!(loaded_plugin.systems.len(), 1);
+        assert_eq!(loaded_plugin.resources, vec![TypeId::of::<Counter>()]);
+
+        app.startup();
+        app.update();
+        app.update();
+        assert_eq!(app.resources.get::<Counter>().unwrap().0, 2);
+
+        dynamic_plugins.unload(&mut app, loaded_plugin);
+        assert!(app.resources.get::<Counter>().is_none());
+        assert!(!app.plugins.contains(&TypeId::of::<CounterPlugin>()));
+        app.update();
+    }
+}
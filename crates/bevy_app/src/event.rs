@@ -1,5 +1,12 @@
-use bevy_ecs::ResMut;
-use std::marker::PhantomData;
+use bevy_ecs::{FromResources, ResMut, Resources};
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
 
 #[derive(Debug)]
 struct EventInstance<T> {
@@ -13,6 +20,20 @@ enum State {
     B,
 }
 
+/// Decides when events are dropped
+#[derive(Debug)]
+enum Retention {
+    /// Events are dropped after two [Events::update] calls
+    DoubleBuffered,
+    /// Events are dropped once every registered [EventReader] has read them, or when there are more than
+    /// `max_events` unread events. Events are only stored in `events_a`.
+    UntilConsumed {
+        max_events: usize,
+        readers: Mutex<Vec<Weak<AtomicUsize>>>,
+        overflowed: bool,
+    },
+}
+
 /// An event collection that represents the events that occurred within the last two [Events::update] calls. Events can be cheaply read using
 /// an [EventReader]. This collection is meant to be paired with a system that calls [Events::update] exactly once per update/frame. [Events::update_system]
 /// is a system that does this. [EventReader]s are expected to read events from this collection at least once per update/frame. If events are not handled
@@ -55,6 +76,12 @@ enum State {
 ///
 /// An alternative call pattern would be to call [Events::update] manually across frames to control when events are cleared. However
 /// this complicates consumption
+///
+/// # Persistent events
+///
+/// Readers that don't run every frame, such as systems in a fixed timestep stage or systems that are skipped while the app is
+/// paused, can miss double buffered events. [Events::persistent] creates a collection that instead keeps each event until every
+/// registered [EventReader] has read it. See [Events::persistent] for details.
 #[derive(Debug)]
 pub struct Events<T> {
     events_a: Vec<EventInstance<T>>,
@@ -63,6 +90,7 @@ pub struct Events<T> {
     b_start_event_count: usize,
     event_count: usize,
     state: State,
+    retention: Retention,
 }
 
 impl<T> Default for Events<T> {
@@ -74,6 +102,7 @@ impl<T> Default for Events<T> {
             events_a: Vec::new(),
             events_b: Vec::new(),
             state: State::A,
+            retention: Retention::DoubleBuffered,
         }
     }
 }
@@ -85,6 +114,8 @@ fn map_instance_event<T>(event_instance: &EventInstance<T>) -> &T {
 /// Reads events of type `T` in order and tracks which events have already been read.
 pub struct EventReader<T> {
     last_event_count: usize,
+    /// Shares `last_event_count` with [persistent](Events::persistent) [Events] this reader is registered with
+    cursor: Option<Arc<AtomicUsize>>,
     _marker: PhantomData<T>,
 }
 
@@ -92,6 +123,7 @@ impl<T> Default for EventReader<T> {
     fn default() -> Self {
         Self {
             last_event_count: 0,
+            cursor: None,
             _marker: PhantomData::default(),
         }
     }
@@ -114,6 +146,13 @@ impl<T> EventReader<T> {
             0
         };
         self.last_event_count = events.event_count;
+        if let Retention::UntilConsumed { readers, .. } = &events.retention {
+            let last_event_count = self.last_event_count;
+            self.cursor
+                .get_or_insert_with(|| register_reader(readers, last_event_count))
+                .store(last_event_count, Ordering::Relaxed);
+        }
+
         match events.state {
             State::A => events
                 .events_b
@@ -169,7 +208,83 @@ impl<T> EventReader<T> {
     }
 }
 
+/// An [EventReader] that is registered with [persistent](Events::persistent) events as soon as it is created from
+/// [Resources], so it keeps every event that is sent after that. Use it as the `Local<PersistentEventReader<T>>` of
+/// systems that may first run after events were sent, for example systems in a fixed timestep stage.
+pub struct PersistentEventReader<T>(pub EventReader<T>);
+
+impl<T: bevy_ecs::Resource> FromResources for PersistentEventReader<T> {
+    fn from_resources(resources: &Resources) -> Self {
+        let mut reader = EventReader::default();
+        if let Some(events) = resources.get::<Events<T>>() {
+            events.register_reader(&mut reader);
+        }
+        PersistentEventReader(reader)
+    }
+}
+
+impl<T> Deref for PersistentEventReader<T> {
+    type Target = EventReader<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for PersistentEventReader<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+fn register_reader(
+    readers: &Mutex<Vec<Weak<AtomicUsize>>>,
+    last_event_count: usize,
+) -> Arc<AtomicUsize> {
+    let cursor = Arc::new(AtomicUsize::new(last_event_count));
+    let mut readers = readers.lock().unwrap();
+    // forget readers that were dropped, e.g. the local state of removed systems
+    readers.retain(|reader| reader.strong_count() > 0);
+    readers.push(Arc::downgrade(&cursor));
+    cursor
+}
+
 impl<T: bevy_ecs::Resource> Events<T> {
+    /// Creates an event collection that keeps each event until every registered [EventReader] has read it, regardless of how
+    /// many times [Events::update] is called. Readers are registered when they are created using [Events::get_reader] or
+    /// [Events::get_reader_current] or [Events::register_reader], and `Local<PersistentEventReader<T>>` readers are registered when
+    /// their system is initialized. Default constructed readers, such as a `Local<EventReader<T>>`, are registered when they read
+    /// for the first time, so they miss events that every other reader read before that.
+    /// Readers are unregistered when they are dropped. While no reader is registered, events are kept until a reader consumes them.
+    ///
+    /// At most `max_events` unread events are kept. When more events are sent, the oldest events are dropped and a warning is
+    /// logged, which usually means a registered reader stopped reading.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_app::Events;
+    ///
+    /// let mut events = Events::<usize>::persistent(64);
+    /// let mut reader = events.get_reader();
+    ///
+    /// events.send(1);
+    /// events.update();
+    /// events.update();
+    ///
+    /// // the event was kept because the reader has not read it yet
+    /// assert_eq!(reader.iter(&events).collect::<Vec<_>>(), vec![&1]);
+    /// ```
+    pub fn persistent(max_events: usize) -> Self {
+        Events {
+            retention: Retention::UntilConsumed {
+                max_events,
+                readers: Mutex::new(Vec::new()),
+                overflowed: false,
+            },
+            ..Default::default()
+        }
+    }
+
     /// "Sends" an `event` by writing it to the current event buffer. [EventReader]s can then read the event.
     pub fn send(&mut self, event: T) {
         let event_instance = EventInstance {
@@ -183,26 +298,95 @@ impl<T: bevy_ecs::Resource> Events<T> {
         }
 
         self.event_count += 1;
+
+        if let Retention::UntilConsumed {
+            max_events,
+            overflowed,
+            ..
+        } = &mut self.retention
+        {
+            if self.events_a.len() > *max_events {
+                if !*overflowed {
+                    log::warn!(
+                        "Events<{}> has more than {} unread events. The oldest events will be dropped before all readers read them.",
+                        std::any::type_name::<T>(),
+                        max_events
+                    );
+                    *overflowed = true;
+                }
+                let dropped = self.events_a.len() - *max_events;
+                self.drop_oldest(dropped);
+            }
+        }
     }
 
     /// Gets a new [EventReader]. This will include all events already in the event buffers.
     pub fn get_reader(&self) -> EventReader<T> {
-        EventReader {
-            last_event_count: 0,
-            _marker: PhantomData,
-        }
+        self.new_reader(0)
     }
 
     /// Gets a new [EventReader]. This will ignore all events already in the event buffers. It will read all future events.
     pub fn get_reader_current(&self) -> EventReader<T> {
+        self.new_reader(self.event_count)
+    }
+
+    /// Registers a default constructed reader, so events it hasn't read yet are kept until it reads them. Does nothing if
+    /// these events aren't [persistent](Events::persistent) or the reader is already registered.
+    pub fn register_reader(&self, reader: &mut EventReader<T>) {
+        if let Retention::UntilConsumed { readers, .. } = &self.retention {
+            if reader.cursor.is_none() {
+                reader.cursor = Some(register_reader(readers, reader.last_event_count));
+            }
+        }
+    }
+
+    fn new_reader(&self, last_event_count: usize) -> EventReader<T> {
+        let cursor = match &self.retention {
+            Retention::DoubleBuffered => None,
+            Retention::UntilConsumed { readers, .. } => {
+                Some(register_reader(readers, last_event_count))
+            }
+        };
+
         EventReader {
-            last_event_count: self.event_count,
+            last_event_count,
+            cursor,
             _marker: PhantomData,
         }
     }
 
+    /// Persistent events only store events in `events_a`, so dropping the oldest events only needs to advance its start
+    fn drop_oldest(&mut self, count: usize) {
+        let count = count.min(self.events_a.len());
+        self.events_a.drain(..count);
+        self.a_start_event_count += count;
+    }
+
     /// Swaps the event buffers and clears the oldest event buffer. In general, this should be called once per frame/update.
+    ///
+    /// For [persistent](Events::persistent) events, this instead drops the events that every registered reader has read.
     pub fn update(&mut self) {
+        if let Retention::UntilConsumed {
+            readers,
+            overflowed,
+            ..
+        } = &mut self.retention
+        {
+            let consumed = readers
+                .get_mut()
+                .unwrap()
+                .iter()
+                .filter_map(|reader| reader.upgrade())
+                .map(|reader| reader.load(Ordering::Relaxed))
+                .min();
+            if let Some(consumed) = consumed {
+                *overflowed = false;
+                let count = consumed.saturating_sub(self.a_start_event_count);
+                self.drop_oldest(count);
+            }
+            return;
+        }
+
         match self.state {
             State::A => {
                 self.events_b = Vec::new();
@@ -226,10 +410,16 @@ impl<T: bevy_ecs::Resource> Events<T> {
     pub fn clear(&mut self) {
         self.events_a.clear();
         self.events_b.clear();
+        if let Retention::UntilConsumed { .. } = self.retention {
+            self.a_start_event_count = self.event_count;
+        }
     }
 
     /// Creates a draining iterator that removes all events.
     pub fn drain<'a>(&'a mut self) -> impl Iterator<Item = T> + 'a {
+        if let Retention::UntilConsumed { .. } = self.retention {
+            self.a_start_event_count = self.event_count;
+        }
         let map = |i: EventInstance<T>| i.event;
         match self.state {
             State::A => self
@@ -259,6 +449,8 @@ impl<T: bevy_ecs::Resource> Events<T> {
     /// this if you know you only need to consume events between the last `update()` call and your call to `iter_current_update_events`.
     /// If events happen outside that window, they will not be handled. For example, any events that happen after this call and before
     /// the next `update()` call will be dropped.
+    ///
+    /// For [persistent](Events::persistent) events, this iterates over all events that have not been read by every reader.
     pub fn iter_current_update_events(&self) -> impl DoubleEndedIterator<Item = &T> {
         match self.state {
             State::A => self.events_a.iter().map(map_instance_event),
@@ -366,6 +558,114 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_persistent_events() {
+        let mut events = Events::<TestEvent>::persistent(8);
+        let event_0 = TestEvent { i: 0 };
+        let event_1 = TestEvent { i: 1 };
+
+        let mut reader_a = events.get_reader();
+        // default readers are registered the first time they read
+        let mut reader_b = EventReader::default();
+        assert_eq!(get_events(&events, &mut reader_b), vec![]);
+
+        events.send(event_0);
+        events.update();
+        events.update();
+        events.update();
+
+        assert_eq!(
+            get_events(&events, &mut reader_a),
+            vec![event_0],
+            "reader_a receives event after several updates"
+        );
+
+        events.send(event_1);
+        events.update();
+        assert_eq!(
+            events.iter_current_update_events().count(),
+            2,
+            "events are kept until reader_b reads them"
+        );
+
+        assert_eq!(get_events(&events, &mut reader_b), vec![event_0, event_1]);
+        events.update();
+        assert_eq!(
+            events.iter_current_update_events().collect::<Vec<_>>(),
+            vec![&event_1],
+            "event_0 is dropped once all readers read it"
+        );
+
+        drop(reader_a);
+        events.update();
+        assert_eq!(
+            events.iter_current_update_events().count(),
+            0,
+            "dropped readers are unregistered"
+        );
+    }
+
+    #[test]
+    fn test_persistent_events_overflow() {
+        let mut events = Events::<TestEvent>::persistent(2);
+        let mut reader = events.get_reader();
+        for i in 0..4 {
+            events.send(TestEvent { i });
+        }
+
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 2 }, TestEvent { i: 3 }],
+            "the oldest events are dropped when there are too many unread events"
+        );
+
+        events.send(TestEvent { i: 4 });
+        assert_eq!(get_events(&events, &mut reader), vec![TestEvent { i: 4 }]);
+    }
+
+    #[test]
+    fn test_persistent_events_local_reader() {
+        use crate::App;
+        use bevy_ecs::{IntoQuerySystem, Local, Res};
+
+        struct ReadLate(bool);
+
+        fn read_early(mut reader: Local<EventReader<TestEvent>>, events: Res<Events<TestEvent>>) {
+            for _ in reader.iter(&events) {}
+        }
+
+        fn read_late(
+            mut reader: Local<PersistentEventReader<TestEvent>>,
+            events: Res<Events<TestEvent>>,
+            read_late: Res<ReadLate>,
+            mut received: ResMut<Vec<TestEvent>>,
+        ) {
+            if read_late.0 {
+                received.extend(reader.iter(&events));
+            }
+        }
+
+        let mut app = App::build()
+            .add_persistent_event::<TestEvent>(8)
+            .add_resource(ReadLate(false))
+            .add_resource(Vec::<TestEvent>::new())
+            .add_system(read_early.system())
+            .add_system(read_late.system())
+            .test_app();
+
+        app.update();
+        app.send_event(TestEvent { i: 0 });
+        app.update_n(2);
+        app.resources_mut().insert(ReadLate(true));
+        app.update();
+
+        assert_eq!(
+            *app.resource::<Vec<TestEvent>>(),
+            vec![TestEvent { i: 0 }],
+            "a persistent reader is registered before it reads for the first time"
+        );
+    }
+
     fn get_events(
         events: &Events<TestEvent>,
         reader: &mut EventReader<TestEvent>,
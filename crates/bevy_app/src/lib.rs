@@ -5,25 +5,36 @@ pub mod startup_stage;
 
 mod app;
 mod app_builder;
+#[cfg(feature = "dynamic_plugins")]
+mod dynamic_plugin;
 mod event;
 mod plugin;
+mod plugin_group;
 mod schedule_runner;
+mod task;
 mod task_pool_options;
+mod test_app;
 
 pub use app::*;
 pub use app_builder::*;
 pub use bevy_derive::DynamicPlugin;
+#[cfg(feature = "dynamic_plugins")]
+pub use dynamic_plugin::{DynamicPluginError, PluginReloadHook};
 pub use event::*;
 pub use plugin::*;
+pub use plugin_group::*;
 pub use schedule_runner::*;
+pub use task::*;
 pub use task_pool_options::*;
+pub use test_app::*;
 
 pub mod prelude {
     pub use crate::{
         app::App,
         app_builder::AppBuilder,
-        event::{EventReader, Events},
-        plugin::Plugin,
+        event::{EventReader, Events, PersistentEventReader},
+        plugin::{Plugin, PluginId},
+        plugin_group::{PluginGroup, PluginGroupBuilder},
         stage, DynamicPlugin,
     };
 }
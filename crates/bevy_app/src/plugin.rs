@@ -1,7 +1,8 @@
 use crate::AppBuilder;
 #[cfg(feature = "dynamic_plugins")]
 use libloading::{Library, Symbol};
-use std::any::Any;
+use std::any::{Any, TypeId};
+use thiserror::Error;
 
 /// A collection of Bevy App logic and configuration
 ///
@@ -11,6 +12,39 @@ pub trait Plugin: Any + Send + Sync {
     fn name(&self) -> &str {
         std::any::type_name::<Self>()
     }
+
+    /// The plugins that must be added to the [App](crate::App) before this plugin
+    fn dependencies(&self) -> Vec<PluginId> {
+        Vec::new()
+    }
+}
+
+/// Identifies a [Plugin] by its type
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PluginId {
+    pub type_id: TypeId,
+    pub name: &'static str,
+}
+
+impl PluginId {
+    pub fn of<T: Plugin>() -> Self {
+        PluginId {
+            type_id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+        }
+    }
+}
+
+/// An error that occurs when a [Plugin] is added to an [App](crate::App)
+#[derive(Error, Debug)]
+pub enum PluginError {
+    #[error("Plugin {0} was already added to the app.")]
+    Duplicate(String),
+    #[error("Plugin {plugin} requires {dependency}, which has not been added to the app. Add it before {plugin}.")]
+    MissingDependency {
+        plugin: String,
+        dependency: &'static str,
+    },
 }
 
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
@@ -26,3 +60,45 @@ pub fn dynamically_load_plugin(path: &str) -> (Library, Box<dyn Plugin>) {
         (lib, plugin)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct PluginA;
+    impl Plugin for PluginA {
+        fn build(&self, _app: &mut AppBuilder) {}
+    }
+
+    struct PluginB;
+    impl Plugin for PluginB {
+        fn build(&self, _app: &mut AppBuilder) {}
+
+        fn dependencies(&self) -> Vec<PluginId> {
+            vec![PluginId::of::<PluginA>()]
+        }
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let mut app = AppBuilder::empty();
+        let result = app.try_add_plugin(PluginB).map(|_| ());
+        assert!(matches!(
+            result,
+            Err(PluginError::MissingDependency { dependency, .. }) if dependency == std::any::type_name::<PluginA>()
+        ));
+
+        app.add_plugin(PluginA).add_plugin(PluginB);
+        assert!(app.has_plugin::<PluginB>());
+    }
+
+    #[test]
+    fn duplicate_plugin() {
+        let mut app = AppBuilder::empty();
+        app.add_plugin(PluginA);
+        assert!(matches!(
+            app.try_add_plugin(PluginA).map(|_| ()),
+            Err(PluginError::Duplicate(_))
+        ));
+    }
+}
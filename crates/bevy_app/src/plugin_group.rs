@@ -0,0 +1,201 @@
+use crate::{AppBuilder, Plugin};
+use bevy_utils::HashMap;
+use std::any::TypeId;
+
+/// Combines multiple [Plugin]s into a single unit. Members of a group can be disabled, replaced or reordered
+/// before the group is added to an [App](crate::App) using [AppBuilder::add_plugins_with].
+pub trait PluginGroup {
+    fn build(&mut self, group: &mut PluginGroupBuilder);
+}
+
+struct PluginEntry {
+    plugin: Box<dyn Plugin>,
+    enabled: bool,
+}
+
+/// An ordered list of [Plugin]s that will be added to an app by [PluginGroupBuilder::finish]
+#[derive(Default)]
+pub struct PluginGroupBuilder {
+    plugins: HashMap<TypeId, PluginEntry>,
+    order: Vec<TypeId>,
+}
+
+impl PluginGroupBuilder {
+    /// Appends `plugin` to the end of the group. If the group already contains a plugin of type `T`, it is replaced
+    /// and keeps its position.
+    pub fn add<T: Plugin>(&mut self, plugin: T) -> &mut Self {
+        if !self.plugins.contains_key(&TypeId::of::<T>()) {
+            self.order.push(TypeId::of::<T>());
+        }
+        self.insert_entry(plugin);
+        self
+    }
+
+    /// Inserts `plugin` directly before the plugin of type `Target`. Panics if `Target` is not in the group.
+    pub fn add_before<Target: Plugin, T: Plugin>(&mut self, plugin: T) -> &mut Self {
+        self.remove_from_order::<T>();
+        let target_index = self.index_of::<Target>();
+        self.order.insert(target_index, TypeId::of::<T>());
+        self.insert_entry(plugin);
+        self
+    }
+
+    /// Inserts `plugin` directly after the plugin of type `Target`. Panics if `Target` is not in the group.
+    pub fn add_after<Target: Plugin, T: Plugin>(&mut self, plugin: T) -> &mut Self {
+        self.remove_from_order::<T>();
+        let target_index = self.index_of::<Target>();
+        self.order.insert(target_index + 1, TypeId::of::<T>());
+        self.insert_entry(plugin);
+        self
+    }
+
+    /// Replaces the plugin of type `Target` with `plugin`, keeping its position. Panics if `Target` is not in the group.
+    pub fn replace<Target: Plugin, T: Plugin>(&mut self, plugin: T) -> &mut Self {
+        let target_index = self.index_of::<Target>();
+        self.plugins.remove(&TypeId::of::<Target>());
+        let ty = TypeId::of::<T>();
+        self.order = self
+            .order
+            .iter()
+            .enumerate()
+            .filter(|(i, existing)| *i == target_index || **existing != ty)
+            .map(|(i, existing)| if i == target_index { ty } else { *existing })
+            .collect();
+        self.insert_entry(plugin);
+        self
+    }
+
+    /// Enables the plugin of type `T`. Plugins are enabled by default. Panics if `T` is not in the group.
+    pub fn enable<T: Plugin>(&mut self) -> &mut Self {
+        self.entry_mut::<T>().enabled = true;
+        self
+    }
+
+    /// Disables the plugin of type `T`, which prevents it from being added to the app. Panics if `T` is not in
+    /// the group.
+    pub fn disable<T: Plugin>(&mut self) -> &mut Self {
+        self.entry_mut::<T>().enabled = false;
+        self
+    }
+
+    /// Returns true if the group contains a plugin of type `T`, regardless of whether it is enabled
+    pub fn contains<T: Plugin>(&self) -> bool {
+        self.plugins.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Adds all enabled plugins to `app`, in order
+    pub fn finish(mut self, app: &mut AppBuilder) {
+        for ty in self.order.iter() {
+            if let Some(entry) = self.plugins.remove(ty) {
+                if entry.enabled {
+                    app.add_boxed_plugin(entry.plugin);
+                } else {
+                    log::debug!("skipped disabled plugin: {}", entry.plugin.name());
+                }
+            }
+        }
+    }
+
+    fn insert_entry<T: Plugin>(&mut self, plugin: T) {
+        self.plugins.insert(
+            TypeId::of::<T>(),
+            PluginEntry {
+                plugin: Box::new(plugin),
+                enabled: true,
+            },
+        );
+    }
+
+    fn remove_from_order<T: Plugin>(&mut self) {
+        let ty = TypeId::of::<T>();
+        self.order.retain(|existing| *existing != ty);
+    }
+
+    fn index_of<T: Plugin>(&self) -> usize {
+        let ty = TypeId::of::<T>();
+        self.order
+            .iter()
+            .position(|existing| *existing == ty)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Plugin {} does not exist in this group.",
+                    std::any::type_name::<T>()
+                )
+            })
+    }
+
+    fn entry_mut<T: Plugin>(&mut self) -> &mut PluginEntry {
+        self.plugins.get_mut(&TypeId::of::<T>()).unwrap_or_else(|| {
+            panic!(
+                "Plugin {} does not exist in this group.",
+                std::any::type_name::<T>()
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Order(Vec<&'static str>);
+
+    macro_rules! test_plugin {
+        ($name:ident) => {
+            struct $name;
+            impl Plugin for $name {
+                fn build(&self, app: &mut AppBuilder) {
+                    if !app.resources().contains::<Order>() {
+                        app.add_resource(Order(Vec::new()));
+                    }
+                    app.resources_mut()
+                        .get_mut::<Order>()
+                        .unwrap()
+                        .0
+                        .push(stringify!($name));
+                }
+            }
+        };
+    }
+
+    test_plugin!(PluginA);
+    test_plugin!(PluginB);
+    test_plugin!(PluginC);
+    test_plugin!(PluginD);
+
+    struct TestPlugins;
+
+    impl PluginGroup for TestPlugins {
+        fn build(&mut self, group: &mut PluginGroupBuilder) {
+            group.add(PluginA).add(PluginB).add(PluginC);
+        }
+    }
+
+    fn added_plugins(app: &AppBuilder) -> Vec<&'static str> {
+        app.resources().get::<Order>().unwrap().0.clone()
+    }
+
+    #[test]
+    fn add_plugin_group() {
+        let mut app = AppBuilder::empty();
+        app.add_plugins(TestPlugins);
+        assert_eq!(added_plugins(&app), vec!["PluginA", "PluginB", "PluginC"]);
+    }
+
+    #[test]
+    fn configure_plugin_group() {
+        let mut app = AppBuilder::empty();
+        app.add_plugins_with(TestPlugins, |group| {
+            group.disable::<PluginA>().add_before::<PluginB, _>(PluginD)
+        });
+        assert_eq!(added_plugins(&app), vec!["PluginD", "PluginB", "PluginC"]);
+
+        let mut app = AppBuilder::empty();
+        app.add_plugins_with(TestPlugins, |group| group.add_after::<PluginC, _>(PluginA));
+        assert_eq!(added_plugins(&app), vec!["PluginB", "PluginC", "PluginA"]);
+
+        let mut app = AppBuilder::empty();
+        app.add_plugins_with(TestPlugins, |group| group.replace::<PluginB, _>(PluginD));
+        assert_eq!(added_plugins(&app), vec!["PluginA", "PluginD", "PluginC"]);
+    }
+}
@@ -0,0 +1,160 @@
+use bevy_ecs::{Commands, Component, Entity, Query};
+use bevy_tasks::{Task, TaskPool};
+use std::future::Future;
+
+/// A collection of [Task]s that complete across frames. Systems can drain finished task outputs each frame
+/// and apply them using [Commands].
+///
+/// # Example
+/// ```
+/// use bevy_app::Tasks;
+/// use bevy_tasks::TaskPool;
+///
+/// let pool = TaskPool::new();
+/// let mut tasks = Tasks::<usize>::default();
+/// tasks.spawn(&pool, async { 1 + 1 });
+///
+/// // usually this is called once per frame inside a system
+/// let mut outputs = Vec::new();
+/// while outputs.is_empty() {
+///     outputs.extend(tasks.drain_finished());
+/// }
+///
+/// assert_eq!(outputs, vec![2]);
+/// assert!(tasks.is_empty());
+/// ```
+pub struct Tasks<T> {
+    tasks: Vec<Task<T>>,
+}
+
+impl<T> Default for Tasks<T> {
+    fn default() -> Self {
+        Tasks { tasks: Vec::new() }
+    }
+}
+
+impl<T> Tasks<T>
+where
+    T: Send + 'static,
+{
+    /// Spawns `future` on the given `task_pool` and tracks the resulting [Task]
+    pub fn spawn(
+        &mut self,
+        task_pool: &TaskPool,
+        future: impl Future<Output = T> + Send + 'static,
+    ) {
+        self.push(task_pool.spawn(future));
+    }
+
+    /// Tracks a [Task] that has already been spawned
+    pub fn push(&mut self, task: Task<T>) {
+        self.tasks.push(task);
+    }
+
+    /// Polls every tracked task once without blocking and returns the outputs of the tasks that have completed.
+    /// Completed tasks are no longer tracked.
+    pub fn drain_finished(&mut self) -> impl Iterator<Item = T> {
+        let mut finished = Vec::new();
+        let mut i = 0;
+        while i < self.tasks.len() {
+            if let Some(output) = self.tasks[i].poll_once() {
+                finished.push(output);
+                self.tasks.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        finished.into_iter()
+    }
+
+    /// The number of tasks that have not completed yet
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+/// A component that holds a running [Task]. Once the task completes, [pending_task_system] removes this component
+/// and inserts the task's output into the same entity.
+///
+/// Use [AppBuilder::add_task_component](crate::AppBuilder::add_task_component) to register the system for a given output type.
+pub struct PendingTask<T> {
+    task: Option<Task<T>>,
+}
+
+impl<T> PendingTask<T>
+where
+    T: Send + 'static,
+{
+    pub fn new(task: Task<T>) -> Self {
+        PendingTask { task: Some(task) }
+    }
+
+    /// Spawns `future` on the given `task_pool` and wraps the resulting [Task]
+    pub fn spawn(task_pool: &TaskPool, future: impl Future<Output = T> + Send + 'static) -> Self {
+        Self::new(task_pool.spawn(future))
+    }
+
+    /// Polls the task once without blocking. Returns the output if the task completed since the last poll.
+    pub fn poll_once(&mut self) -> Option<T> {
+        let output = self.task.as_mut()?.poll_once();
+        if output.is_some() {
+            self.task = None;
+        }
+
+        output
+    }
+
+    /// Returns true if the task's output has already been taken
+    pub fn is_finished(&self) -> bool {
+        self.task.is_none()
+    }
+}
+
+/// Polls all [PendingTask]s with output `T`. Finished tasks are replaced by their output component.
+pub fn pending_task_system<T: Component>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut PendingTask<T>)>,
+) {
+    for (entity, mut pending_task) in &mut query.iter() {
+        if let Some(output) = pending_task.poll_once() {
+            commands
+                .remove_one::<PendingTask<T>>(entity)
+                .insert_one(entity, output);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{IntoQuerySystem, Resources, Schedule, World};
+
+    #[derive(Debug, PartialEq)]
+    struct Path(Vec<usize>);
+
+    #[test]
+    fn pending_task_inserts_output() {
+        let task_pool = TaskPool::new();
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", pending_task_system::<Path>.system());
+
+        let entity = world.spawn((PendingTask::spawn(&task_pool, async {
+            Path(vec![1, 2, 3])
+        }),));
+
+        while world.get::<Path>(entity).is_err() {
+            schedule.run(&mut world, &mut resources);
+        }
+
+        assert_eq!(*world.get::<Path>(entity).unwrap(), Path(vec![1, 2, 3]));
+        assert!(world.get::<PendingTask<Path>>(entity).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{app::App, event::Events};
+use bevy_ecs::{Ref, Resource, Resources, World};
+
+/// Steps an [App] manually, one [App::update] at a time, without a runner or window. This makes it possible to
+/// write deterministic tests that send events into an app and assert on the resulting world state.
+///
+/// Construct one using [AppBuilder::test_app](crate::AppBuilder::test_app). Startup systems run immediately.
+/// Apps that use `bevy_core::Time` can insert `TimeUpdateStrategy::ManualDuration` to advance time by a fixed
+/// amount per update. Rendering apps should use their headless configuration, as no window is ever created.
+///
+/// ## Example
+/// ```
+/// use bevy_app::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// struct Jump;
+/// #[derive(Default)]
+/// struct Jumps(usize);
+///
+/// fn jump_system(
+///     mut reader: Local<EventReader<Jump>>,
+///     events: Res<Events<Jump>>,
+///     mut jumps: ResMut<Jumps>,
+/// ) {
+///     jumps.0 += reader.iter(&events).count();
+/// }
+///
+/// let mut app = App::build()
+///     .add_event::<Jump>()
+///     .init_resource::<Jumps>()
+///     .add_system(jump_system.system())
+///     .test_app();
+///
+/// app.send_event(Jump);
+/// app.update_n(3);
+///
+/// assert_eq!(app.resource::<Jumps>().0, 1);
+/// ```
+pub struct TestApp {
+    pub app: App,
+}
+
+impl TestApp {
+    /// Wraps the given `app` and runs its startup schedule
+    pub fn new(mut app: App) -> Self {
+        app.startup();
+        TestApp { app }
+    }
+
+    /// Runs the app's schedule once
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    /// Runs the app's schedule `count` times
+    pub fn update_n(&mut self, count: usize) {
+        for _ in 0..count {
+            self.app.update();
+        }
+    }
+
+    /// Sends `event` to the app's [Events] resource. Panics if `T` was not registered using
+    /// [AppBuilder::add_event](crate::AppBuilder::add_event).
+    pub fn send_event<T: Resource>(&mut self, event: T) {
+        self.app
+            .resources
+            .get_mut::<Events<T>>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "Events<{}> is not a resource. Register it with add_event.",
+                    std::any::type_name::<T>()
+                )
+            })
+            .send(event);
+    }
+
+    /// Borrows resource `T`. Panics if the resource does not exist.
+    pub fn resource<T: Resource>(&self) -> Ref<'_, T> {
+        self.app
+            .resources
+            .get::<T>()
+            .unwrap_or_else(|| panic!("Resource {} does not exist.", std::any::type_name::<T>()))
+    }
+
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    pub fn resources(&self) -> &Resources {
+        &self.app.resources
+    }
+
+    pub fn resources_mut(&mut self) -> &mut Resources {
+        &mut self.app.resources
+    }
+}
@@ -1,17 +1,25 @@
+#[cfg(feature = "filesystem_watcher")]
+use crate::{get_labeled_path, AssetChange, DebouncedChanges};
 use crate::{
-    filesystem_watcher::FilesystemWatcher, AssetLoadError, AssetLoadRequestHandler, AssetLoader,
-    Assets, Handle, HandleId, LoadRequest,
+    get_meta_path, hash_source, split_label, AssetChannel, AssetIo, AssetIoError, AssetLoadError,
+    AssetLoadFailure, AssetLoadRequestHandler, AssetLoader, AssetProcessor, AssetResult,
+    AssetSaver, Assets, FileAssetIo, Handle, HandleId, ImportCache, ImportedAsset, LabeledAsset,
+    LoadContext, LoadRequest, LoaderSettings, RefChange,
 };
 use anyhow::Result;
 use bevy_ecs::{Res, Resource, Resources};
+use bevy_tasks::{IOTaskPool, Task, TaskPool};
 use bevy_utils::{HashMap, HashSet};
-use crossbeam_channel::TryRecvError;
-use parking_lot::RwLock;
+use crossbeam_channel::Sender;
+use futures_lite::future;
+use parking_lot::{Mutex, RwLock};
+#[cfg(feature = "filesystem_watcher")]
+use std::time::{Duration, Instant};
 use std::{
-    env, fs, io,
+    any::{Any, TypeId},
+    collections::VecDeque,
     path::{Path, PathBuf},
     sync::Arc,
-    thread,
 };
 
 use thiserror::Error;
@@ -24,24 +32,29 @@ pub type AssetVersion = usize;
 pub enum AssetServerError {
     #[error("Asset folder path is not a directory.")]
     AssetFolderNotADirectory(String),
-    #[error("Invalid root path")]
-    InvalidRootPath,
     #[error("No AssetHandler found for the given extension.")]
     MissingAssetHandler,
     #[error("No AssetLoader found for the given extension.")]
     MissingAssetLoader,
     #[error("Encountered an error while loading an asset.")]
     AssetLoadError(#[from] AssetLoadError),
-    #[error("Encountered an io error.")]
-    Io(#[from] io::Error),
-    #[error("Failed to watch asset folder.")]
-    AssetWatchError { path: PathBuf },
+    #[error("Encountered an error while accessing an asset.")]
+    AssetIoError(#[from] AssetIoError),
+    #[error("No AssetSaver found for the given asset type.")]
+    MissingAssetSaver,
+    #[error("Encountered an error while saving an asset.")]
+    AssetSaveError(#[source] anyhow::Error),
+    #[error("Encountered an error while processing asset {0:?}.")]
+    AssetProcessError(PathBuf, #[source] anyhow::Error),
+    #[error("Failed to read or write the import cache.")]
+    ImportCacheError(#[from] ron::Error),
 }
 
-struct LoaderThread {
-    // NOTE: these must remain private. the LoaderThread Arc counters are used to determine thread liveness
-    // if there is one reference, the loader thread is dead. if there are two references, the loader thread is active
-    requests: Arc<RwLock<Vec<LoadRequest>>>,
+/// Load requests that wait to be started and the loads that are currently running
+#[derive(Default)]
+struct LoadQueue {
+    pending: VecDeque<LoadRequest>,
+    running: HashMap<HandleId, Task<()>>,
 }
 
 /// Info about a specific asset, such as its path and its current load state
@@ -50,6 +63,10 @@ pub struct AssetInfo {
     pub handle_id: HandleId,
     pub path: PathBuf,
     pub load_state: LoadState,
+    /// The assets this asset loaded using [LoadContext::load_dependency]
+    pub dependencies: Vec<HandleId>,
+    /// Why the asset failed to load, if its state is [LoadState::Failed]
+    pub failure: Option<Arc<AssetLoadFailure>>,
 }
 
 /// The load state of an asset
@@ -70,41 +87,166 @@ impl LoadState {
     }
 }
 
-/// Loads assets from the filesystem on background threads
+/// The parts of an [AssetServer] that are shared with its load tasks
+pub(crate) struct SharedAssetState {
+    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_info_paths: RwLock<HashMap<PathBuf, HandleId>>,
+    ref_change_senders: RwLock<HashMap<TypeId, Sender<RefChange>>>,
+    asset_senders: RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+}
+
+impl SharedAssetState {
+    /// Returns a strong handle if the asset type `T` is registered and a weak handle otherwise
+    pub(crate) fn get_handle_with_id<T: Resource>(&self, handle_id: HandleId) -> Handle<T> {
+        match self.ref_change_senders.read().get(&TypeId::of::<T>()) {
+            Some(ref_change_sender) => Handle::strong(handle_id, ref_change_sender.clone()),
+            None => Handle::weak(handle_id),
+        }
+    }
+
+    /// Returns the sender of the [AssetChannel] for the asset type `T`, if the type was registered
+    pub(crate) fn get_asset_sender<T: Resource>(&self) -> Option<Sender<AssetResult<T>>> {
+        self.asset_senders
+            .read()
+            .get(&TypeId::of::<T>())
+            .and_then(|sender| sender.downcast_ref::<Sender<AssetResult<T>>>())
+            .cloned()
+    }
+
+    /// Returns the id of the asset at the given path. If the path isn't known yet, an id is reserved for it.
+    pub(crate) fn get_or_reserve_handle_id(&self, path: &Path) -> HandleId {
+        let handle_id = self.handle_id_for_path(path);
+        reserve_handle_id(&mut self.asset_info_paths.write(), path, handle_id)
+    }
+
+    /// Returns the id of the asset at the given path, without reserving it. Ids are stable across runs: files use
+    /// the uuid from their meta file, if they have one, and an id derived from their path otherwise. The ids of
+    /// labeled assets are derived from the id of their file and their label.
+    ///
+    /// Meta files of unknown paths are read here, so this must be called before taking the `asset_info` or
+    /// `asset_info_paths` locks. The id is then reserved with [reserve_handle_id].
+    pub(crate) fn handle_id_for_path(&self, path: &Path) -> HandleId {
+        if let Some(handle_id) = self.asset_info_paths.read().get(path) {
+            return *handle_id;
+        }
+
+        match split_label(path) {
+            (source_path, Some(label)) => {
+                HandleId::from_label(self.handle_id_for_path(source_path), label)
+            }
+            (_, None) => self.get_file_handle_id(path),
+        }
+    }
+
+    fn get_file_handle_id(&self, path: &Path) -> HandleId {
+        self.asset_io
+            .load_path(&get_meta_path(path))
+            .ok()
+            .and_then(|bytes| LoaderSettings::from_meta_bytes(bytes).ok())
+            .and_then(|settings| settings.uuid())
+            .map_or_else(|| HandleId::from_path(path), HandleId)
+    }
+}
+
+/// Adds `path` to `asset_info_paths` with an id from [SharedAssetState::handle_id_for_path]. If the path was
+/// reserved in the meantime, its existing id is kept and returned.
+pub(crate) fn reserve_handle_id(
+    asset_info_paths: &mut HashMap<PathBuf, HandleId>,
+    path: &Path,
+    handle_id: HandleId,
+) -> HandleId {
+    *asset_info_paths.entry(path.to_owned()).or_insert(handle_id)
+}
+
+/// Loads assets asynchronously on the [IOTaskPool]. Assets are read using an [AssetIo] implementation, which reads
+/// from the filesystem by default.
+///
+/// Loads are started by [AssetServer::start_load_tasks_system]. By default, at most one load per thread of the
+/// [IOTaskPool] runs at a time. Loads are cancelled once every strong handle to their asset is dropped.
+///
+/// Assets that were converted by [AssetServer::process_assets] are loaded from their processed file instead of
+/// their source.
 pub struct AssetServer {
     asset_folders: RwLock<Vec<PathBuf>>,
-    loader_threads: RwLock<Vec<LoaderThread>>,
-    max_loader_threads: usize,
-    asset_handlers: Arc<RwLock<Vec<Box<dyn AssetLoadRequestHandler>>>>,
+    load_queue: Mutex<LoadQueue>,
+    max_concurrent_loads: Option<usize>,
+    asset_handlers: RwLock<Vec<Arc<dyn AssetLoadRequestHandler>>>,
     // TODO: this is a hack to enable retrieving generic AssetLoader<T>s. there must be a better way!
     loaders: Vec<Resources>,
     extension_to_handler_index: HashMap<String, usize>,
     extension_to_loader_index: HashMap<String, usize>,
+    // NOTE: like loaders, savers are stored as Box<dyn AssetSaver<T>> resources so they can be retrieved by type
+    savers: Resources,
+    processors: Vec<Box<dyn AssetProcessor>>,
+    extension_to_processor_index: HashMap<String, usize>,
+    import_folder: PathBuf,
+    import_cache: RwLock<ImportCache>,
     asset_info: RwLock<HashMap<HandleId, AssetInfo>>,
-    asset_info_paths: RwLock<HashMap<PathBuf, HandleId>>,
     #[cfg(feature = "filesystem_watcher")]
-    filesystem_watcher: Arc<RwLock<Option<FilesystemWatcher>>>,
+    changes: Mutex<DebouncedChanges>,
+    shared: Arc<SharedAssetState>,
 }
 
 impl Default for AssetServer {
     fn default() -> Self {
+        AssetServer::new(FileAssetIo::default())
+    }
+}
+
+impl AssetServer {
+    /// Creates an AssetServer that reads assets using the given [AssetIo] implementation
+    pub fn new<T: AssetIo>(asset_io: T) -> Self {
         AssetServer {
-            #[cfg(feature = "filesystem_watcher")]
-            filesystem_watcher: Arc::new(RwLock::new(None)),
-            max_loader_threads: 4,
+            shared: Arc::new(SharedAssetState {
+                asset_io: Box::new(asset_io),
+                asset_info_paths: Default::default(),
+                ref_change_senders: Default::default(),
+                asset_senders: Default::default(),
+            }),
+            max_concurrent_loads: None,
             asset_folders: Default::default(),
-            loader_threads: Default::default(),
+            load_queue: Default::default(),
             asset_handlers: Default::default(),
             loaders: Default::default(),
             extension_to_handler_index: Default::default(),
             extension_to_loader_index: Default::default(),
-            asset_info_paths: Default::default(),
+            savers: Default::default(),
+            processors: Default::default(),
+            extension_to_processor_index: Default::default(),
+            import_folder: PathBuf::from("imported"),
+            import_cache: Default::default(),
             asset_info: Default::default(),
+            #[cfg(feature = "filesystem_watcher")]
+            changes: Mutex::new(DebouncedChanges::new(Duration::from_millis(100))),
         }
     }
-}
 
-impl AssetServer {
+    /// Limits the number of assets that are loaded at the same time. By default, this is the number of threads of
+    /// the [IOTaskPool].
+    pub fn with_max_concurrent_loads(mut self, max_concurrent_loads: usize) -> Self {
+        self.max_concurrent_loads = Some(max_concurrent_loads);
+        self
+    }
+
+    /// Sets the folder that processed assets are written to, relative to the asset root. This is `imported` by
+    /// default.
+    pub fn with_import_folder<P: AsRef<Path>>(mut self, import_folder: P) -> Self {
+        self.import_folder = import_folder.as_ref().to_owned();
+        self
+    }
+
+    /// Sets how long changes to watched files have to settle before assets are reloaded. Saving a file often
+    /// changes it several times in a row. This is 100 milliseconds by default.
+    #[cfg(feature = "filesystem_watcher")]
+    pub fn with_change_debounce(mut self, debounce: Duration) -> Self {
+        self.changes = Mutex::new(DebouncedChanges::new(debounce));
+        self
+    }
+
+    pub fn asset_io(&self) -> &dyn AssetIo {
+        &*self.shared.asset_io
+    }
+
     pub fn add_handler<T>(&mut self, asset_handler: T)
     where
         T: AssetLoadRequestHandler,
@@ -116,7 +258,7 @@ impl AssetServer {
                 .insert(extension.to_string(), handler_index);
         }
 
-        asset_handlers.push(Box::new(asset_handler));
+        asset_handlers.push(Arc::new(asset_handler));
     }
 
     pub fn add_loader<TLoader, TAsset>(&mut self, loader: TLoader)
@@ -135,49 +277,198 @@ impl AssetServer {
         self.loaders.push(resources);
     }
 
+    /// Adds the saver that [AssetServer::save] uses for assets of type `TAsset`, replacing any previous saver for
+    /// this type
+    pub fn add_saver<TSaver, TAsset>(&mut self, saver: TSaver)
+    where
+        TSaver: AssetSaver<TAsset>,
+        TAsset: 'static,
+    {
+        self.savers
+            .insert::<Box<dyn AssetSaver<TAsset>>>(Box::new(saver));
+    }
+
+    pub fn add_processor<T>(&mut self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        let processor_index = self.processors.len();
+        for extension in processor.extensions().iter() {
+            self.extension_to_processor_index
+                .insert(extension.to_string(), processor_index);
+        }
+
+        self.processors.push(Box::new(processor));
+    }
+
+    /// Registers the asset type `T`, which allows [AssetServer::load] to return strong handles for it. This is called
+    /// by [AddAsset::add_asset](crate::AddAsset::add_asset).
+    pub fn register_asset_type<T: Resource>(&mut self, assets: &Assets<T>) {
+        self.shared
+            .ref_change_senders
+            .write()
+            .insert(TypeId::of::<T>(), assets.ref_change_sender.clone());
+    }
+
+    /// Registers the channel that assets of type `T` are sent to, which allows loaders to add labeled assets of
+    /// type `T`
+    pub(crate) fn register_asset_channel<T: Resource>(&mut self, asset_channel: &AssetChannel<T>) {
+        self.shared
+            .asset_senders
+            .write()
+            .insert(TypeId::of::<T>(), Box::new(asset_channel.sender.clone()));
+    }
+
+    /// Forgets everything about the asset with the given id, so loading its path again will load it from disk. Loads
+    /// of the asset that didn't finish yet are cancelled. This is called once the last strong handle to the asset was
+    /// dropped.
+    pub(crate) fn free_asset(&self, handle_id: HandleId) {
+        {
+            let mut load_queue = self.load_queue.lock();
+            load_queue
+                .pending
+                .retain(|load_request| load_request.handle_id != handle_id);
+            // dropping a task cancels it
+            load_queue.running.remove(&handle_id);
+        }
+
+        // NOTE: locks are taken in the same order as in load_untyped
+        let path = {
+            let mut asset_info = self.asset_info.write();
+            let mut asset_info_paths = self.shared.asset_info_paths.write();
+            asset_info_paths.retain(|_, id| *id != handle_id);
+            asset_info
+                .remove(&handle_id)
+                .map(|asset_info| asset_info.path)
+        };
+
+        // labeled assets share the path of the file they were loaded from, which stays watched
+        if let Some(path) = path.filter(|path| split_label(path).1.is_none()) {
+            let _ = self.shared.asset_io.unwatch_path_for_changes(&path);
+            let _ = self
+                .shared
+                .asset_io
+                .unwatch_path_for_changes(&get_meta_path(&path));
+
+            // the file may have been freed while labeled assets requested in the meantime wait for it
+            let labeled_assets_loading = self.asset_info.read().values().any(|asset_info| {
+                matches!(asset_info.load_state, LoadState::Loading(_))
+                    && is_labeled_asset_of(&asset_info.path, &path)
+            });
+            if labeled_assets_loading {
+                let _ = self.load_untyped(&path);
+            }
+        }
+    }
+
+    /// Loads every asset in the given folder and its subfolders that has a handler for its extension. While
+    /// [AssetServer::watch_for_changes] is enabled, files that are added to the folder later are loaded as well.
     pub fn load_asset_folder<P: AsRef<Path>>(
         &self,
         path: P,
     ) -> Result<Vec<HandleId>, AssetServerError> {
-        let root_path = self.get_root_path()?;
-        let asset_folder = root_path.join(path);
-        let handle_ids = self.load_assets_in_folder_recursive(&asset_folder)?;
-        self.asset_folders.write().push(asset_folder);
+        let asset_folder = path.as_ref();
+        let handle_ids = self.load_assets_in_folder_recursive(asset_folder)?;
+        #[cfg(feature = "filesystem_watcher")]
+        self.shared.asset_io.watch_path_for_changes(asset_folder)?;
+        self.asset_folders.write().push(asset_folder.to_owned());
         Ok(handle_ids)
     }
 
-    pub fn get_handle<T, P: AsRef<Path>>(&self, path: P) -> Option<Handle<T>> {
-        self.asset_info_paths
-            .read()
-            .get(path.as_ref())
-            .map(|handle_id| Handle::from(*handle_id))
-    }
-
-    #[cfg(feature = "filesystem_watcher")]
-    fn watch_path_for_changes<P: AsRef<Path>>(
-        filesystem_watcher: &mut Option<FilesystemWatcher>,
+    /// Saves the asset to the given path using the [AssetSaver] for its type. The path should have the extension of
+    /// the saver, so the asset can be loaded again.
+    pub fn save<T: Resource, P: AsRef<Path>>(
+        &self,
+        asset: &T,
         path: P,
     ) -> Result<(), AssetServerError> {
-        if let Some(watcher) = filesystem_watcher {
-            watcher
-                .watch(&path)
-                .map_err(|_error| AssetServerError::AssetWatchError {
-                    path: path.as_ref().to_owned(),
-                })?;
+        let saver = self
+            .savers
+            .get::<Box<dyn AssetSaver<T>>>()
+            .ok_or(AssetServerError::MissingAssetSaver)?;
+        let bytes = saver
+            .save(asset)
+            .map_err(AssetServerError::AssetSaveError)?;
+        self.shared.asset_io.save_path(path.as_ref(), &bytes)?;
+        Ok(())
+    }
+
+    /// Converts every asset in the given folder that has an [AssetProcessor] for its extension and writes the
+    /// processed files to the import folder, along with the [ImportCache]. Assets whose source didn't change since
+    /// they were last processed are skipped. Returns the number of processed assets.
+    ///
+    /// This is meant to run offline, before the app is shipped. At runtime, [AssetServer::load_import_cache] makes
+    /// the processed files available.
+    pub fn process_assets<P: AsRef<Path>>(&self, path: P) -> Result<usize, AssetServerError> {
+        let mut import_cache = self.import_cache.write();
+        let processed =
+            self.process_assets_in_folder_recursive(path.as_ref(), &mut import_cache)?;
+        let import_cache_ron = ron::ser::to_string_pretty(&*import_cache, Default::default())?;
+        self.shared.asset_io.save_path(
+            &self.import_folder.join(ImportCache::FILE_NAME),
+            import_cache_ron.as_bytes(),
+        )?;
+        Ok(processed)
+    }
+
+    /// Reads the [ImportCache] written by [AssetServer::process_assets], so processed assets are loaded in place of
+    /// their source. Nothing changes if assets were never processed, or if the cache was written with another
+    /// [ImportCache::HASH_VERSION]. This is called by the [AssetPlugin](crate::AssetPlugin).
+    pub fn load_import_cache(&self) -> Result<(), AssetServerError> {
+        match self
+            .shared
+            .asset_io
+            .load_path(&self.import_folder.join(ImportCache::FILE_NAME))
+        {
+            Ok(bytes) => {
+                let import_cache: ImportCache = ron::de::from_bytes(&bytes)?;
+                if import_cache.hash_version == ImportCache::HASH_VERSION {
+                    *self.import_cache.write() = import_cache;
+                } else {
+                    log::warn!(
+                        "Ignoring the import cache, which was written by another version. Process the assets again to update it."
+                    );
+                }
+            }
+            Err(AssetIoError::NotFound(_)) => {}
+            Err(err) => return Err(err.into()),
         }
 
         Ok(())
     }
 
+    pub fn get_handle<T: Resource, P: AsRef<Path>>(&self, path: P) -> Option<Handle<T>> {
+        let handle_id = *self.shared.asset_info_paths.read().get(path.as_ref())?;
+        Some(self.shared.get_handle_with_id(handle_id))
+    }
+
+    /// Returns the path the given asset was loaded from. Assets that were added to their [Assets] collection
+    /// directly don't have a path.
+    pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<PathBuf> {
+        self.asset_info
+            .read()
+            .get(&handle.into())
+            .map(|asset_info| asset_info.path.clone())
+    }
+
+    /// Reloads assets when their files or meta files change and loads new files in the folders that were loaded
+    /// with [AssetServer::load_asset_folder]. Changes are applied once they settled for the duration set with
+    /// [AssetServer::with_change_debounce].
     #[cfg(feature = "filesystem_watcher")]
     pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
-        let mut filesystem_watcher = self.filesystem_watcher.write();
-
-        let _ = filesystem_watcher.get_or_insert_with(FilesystemWatcher::default);
-        // watch current files
-        let asset_info_paths = self.asset_info_paths.read();
+        self.shared.asset_io.watch_for_changes()?;
+        // watch current files and folders
+        let asset_info_paths = self.shared.asset_info_paths.read();
         for asset_path in asset_info_paths.keys() {
-            Self::watch_path_for_changes(&mut filesystem_watcher, asset_path)?;
+            if split_label(asset_path).1.is_none() {
+                self.shared.asset_io.watch_path_for_changes(asset_path)?;
+                self.shared
+                    .asset_io
+                    .watch_path_for_changes(&get_meta_path(asset_path))?;
+            }
+        }
+        for asset_folder in self.asset_folders.read().iter() {
+            self.shared.asset_io.watch_path_for_changes(asset_folder)?;
         }
 
         Ok(())
@@ -185,62 +476,242 @@ impl AssetServer {
 
     #[cfg(feature = "filesystem_watcher")]
     pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
-        let mut changed = HashSet::default();
+        asset_server.reload_changed_assets();
+    }
 
-        loop {
-            let result = {
-                let rwlock_guard = asset_server.filesystem_watcher.read();
-                if let Some(filesystem_watcher) = rwlock_guard.as_ref() {
-                    filesystem_watcher.receiver.try_recv()
-                } else {
-                    break;
+    /// Applies the changes that settled: assets whose files changed are reloaded along with every asset that depends
+    /// on them, renamed assets keep their handles and new files in asset folders are loaded. Removed files don't
+    /// unload their assets.
+    #[cfg(feature = "filesystem_watcher")]
+    fn reload_changed_assets(&self) {
+        let changes = {
+            let mut debounced_changes = self.changes.lock();
+            let now = Instant::now();
+            debounced_changes.add(self.shared.asset_io.get_changes(), now);
+            debounced_changes.take_settled(now)
+        };
+
+        let mut changed_ids = Vec::new();
+        let mut new_paths = Vec::new();
+        for change in changes {
+            match change {
+                AssetChange::Created(path) => {
+                    self.apply_change(&path, true, &mut changed_ids, &mut new_paths)
                 }
-            };
-            let event = match result {
-                Ok(result) => result.unwrap(),
-                Err(TryRecvError::Empty) => break,
-                Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected"),
-            };
-            if let notify::event::Event {
-                kind: notify::event::EventKind::Modify(_),
-                paths,
-                ..
-            } = event
-            {
-                for path in paths.iter() {
-                    if !changed.contains(path) {
-                        let root_path = asset_server.get_root_path().unwrap();
-                        let relative_path = path.strip_prefix(root_path).unwrap();
-                        match asset_server.load_untyped(relative_path) {
-                            Ok(_) => {}
-                            Err(AssetServerError::AssetLoadError(error)) => panic!("{:?}", error),
-                            Err(_) => {}
-                        }
+                AssetChange::Modified(path) => {
+                    self.apply_change(&path, false, &mut changed_ids, &mut new_paths)
+                }
+                AssetChange::Removed(_) => {}
+                // editors often save by renaming a temporary file over the asset
+                AssetChange::Renamed { from, to } => {
+                    if self.get_loaded_id(&to).is_none() && self.get_loaded_id(&from).is_some() {
+                        self.rename_asset(&from, &to);
+                    } else {
+                        self.apply_change(&to, true, &mut changed_ids, &mut new_paths);
                     }
                 }
-                changed.extend(paths);
+            }
+        }
+
+        let mut reloaded = HashSet::default();
+        for handle_id in changed_ids {
+            self.reload_with_dependents(handle_id, &mut reloaded);
+        }
+        for path in new_paths {
+            // new files may have been loaded as dependencies of reloaded assets
+            if self.get_loaded_id(&path).is_some() {
+                continue;
+            }
+            match self.load_untyped(&path) {
+                Ok(_) | Err(AssetServerError::MissingAssetHandler) => {}
+                Err(err) => log::error!("Failed to load new asset {:?}: {:?}", path, err),
             }
         }
     }
 
-    fn get_root_path(&self) -> Result<PathBuf, AssetServerError> {
-        if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
-            Ok(PathBuf::from(manifest_dir))
-        } else {
-            match env::current_exe() {
-                Ok(exe_path) => exe_path
-                    .parent()
-                    .ok_or(AssetServerError::InvalidRootPath)
-                    .map(|exe_parent_path| exe_parent_path.to_owned()),
-                Err(err) => Err(AssetServerError::Io(err)),
+    /// Collects the asset that has to be reloaded because the file at the given path changed. New files in asset
+    /// folders are collected in `new_paths`.
+    #[cfg(feature = "filesystem_watcher")]
+    fn apply_change(
+        &self,
+        path: &Path,
+        created: bool,
+        changed_ids: &mut Vec<HandleId>,
+        new_paths: &mut Vec<PathBuf>,
+    ) {
+        // changing a meta file changes the settings of its asset
+        let path = match path.to_str().and_then(|path| path.strip_suffix(".meta")) {
+            Some(asset_path) => Path::new(asset_path),
+            None => path,
+        };
+
+        // the processed files of a changed source are outdated
+        self.import_cache.write().imports.retain(|import_path, _| {
+            import_path != path && !is_labeled_asset_of(import_path, path)
+        });
+
+        // assets that were freed are no longer reloaded
+        if let Some(handle_id) = self
+            .get_loaded_id(path)
+            .or_else(|| self.get_loaded_labeled_id(path))
+        {
+            if !changed_ids.contains(&handle_id) {
+                changed_ids.push(handle_id);
+            }
+        } else if created && self.is_in_asset_folder(path) {
+            if self.shared.asset_io.is_directory(path) {
+                // files that are created along with their folder may not be reported separately
+                if let Ok(child_paths) = self.shared.asset_io.read_directory(path) {
+                    for child_path in child_paths {
+                        self.apply_change(&child_path, true, changed_ids, new_paths);
+                    }
+                }
+            } else if self.get_handler_index(path).is_ok() && !new_paths.iter().any(|p| p == path) {
+                new_paths.push(path.to_owned());
+            }
+        }
+    }
+
+    /// Moves the asset at `from` and its labeled assets to `to`. They keep their ids, so existing handles stay valid.
+    #[cfg(feature = "filesystem_watcher")]
+    fn rename_asset(&self, from: &Path, to: &Path) {
+        {
+            let mut asset_info = self.asset_info.write();
+            let mut asset_info_paths = self.shared.asset_info_paths.write();
+            let renamed = asset_info_paths
+                .iter()
+                .filter(|(path, _)| *path == from || is_labeled_asset_of(path, from))
+                .map(|(path, handle_id)| (path.clone(), *handle_id))
+                .collect::<Vec<_>>();
+            for (path, handle_id) in renamed {
+                asset_info_paths.remove(&path);
+                let new_path = match split_label(&path).1 {
+                    Some(label) => PathBuf::from(format!("{}#{}", to.to_string_lossy(), label)),
+                    None => to.to_owned(),
+                };
+                if let Some(asset_info) = asset_info.get_mut(&handle_id) {
+                    asset_info.path = new_path.clone();
+                }
+                asset_info_paths.insert(new_path, handle_id);
             }
         }
+
+        let mut import_cache = self.import_cache.write();
+        let renamed_imports = import_cache
+            .imports
+            .keys()
+            .filter(|path| *path == from || is_labeled_asset_of(path, from))
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in renamed_imports {
+            let imported_asset = import_cache.imports.remove(&path).unwrap();
+            let new_path = match split_label(&path).1 {
+                Some(label) => get_labeled_path(to, label),
+                None => to.to_owned(),
+            };
+            import_cache.imports.insert(new_path, imported_asset);
+        }
+
+        let asset_io = &self.shared.asset_io;
+        let _ = asset_io.unwatch_path_for_changes(from);
+        let _ = asset_io.unwatch_path_for_changes(&get_meta_path(from));
+        let _ = asset_io.watch_path_for_changes(to);
+        let _ = asset_io.watch_path_for_changes(&get_meta_path(to));
+    }
+
+    /// Returns the id of the asset at the given path, if it is loaded or loading
+    fn get_loaded_id(&self, path: &Path) -> Option<HandleId> {
+        let asset_info = self.asset_info.read();
+        let asset_info_paths = self.shared.asset_info_paths.read();
+        asset_info_paths
+            .get(path)
+            .filter(|handle_id| asset_info.contains_key(handle_id))
+            .copied()
+    }
+
+    /// Returns the id of a loaded labeled asset of the file at the given path. Processed labeled assets are loaded
+    /// without their file.
+    #[cfg(feature = "filesystem_watcher")]
+    fn get_loaded_labeled_id(&self, path: &Path) -> Option<HandleId> {
+        self.asset_info
+            .read()
+            .values()
+            .find(|asset_info| is_labeled_asset_of(&asset_info.path, path))
+            .map(|asset_info| asset_info.handle_id)
+    }
+
+    #[cfg(feature = "filesystem_watcher")]
+    fn is_in_asset_folder(&self, path: &Path) -> bool {
+        self.asset_folders
+            .read()
+            .iter()
+            .any(|asset_folder| path.starts_with(asset_folder))
     }
 
+    #[cfg(feature = "filesystem_watcher")]
+    fn reload_with_dependents(&self, handle_id: HandleId, reloaded: &mut HashSet<HandleId>) {
+        if !reloaded.insert(handle_id) {
+            return;
+        }
+
+        // labeled assets are reloaded along with their file
+        let (path, version) = match self.asset_info.read().get(&handle_id) {
+            Some(asset_info) => (
+                split_label(&asset_info.path).0.to_owned(),
+                asset_info.load_state.get_version(),
+            ),
+            None => return,
+        };
+        if let Err(AssetServerError::AssetLoadError(error)) = self.load_untyped(&path) {
+            let loader = match self.get_handler_index(&path) {
+                Ok(index) => self.asset_handlers.read()[index].loader_name(),
+                Err(_) => "no loader",
+            };
+            let failure = AssetLoadFailure {
+                path: path.clone(),
+                loader,
+                error,
+            };
+            log::error!("{}", failure);
+            self.set_load_failure(handle_id, version, Arc::new(failure));
+        }
+
+        // assets that depend on the labeled assets of this asset are reloaded too
+        let dependents = {
+            let asset_info = self.asset_info.read();
+            let mut reloaded_ids = vec![handle_id];
+            reloaded_ids.extend(
+                asset_info
+                    .values()
+                    .filter(|asset_info| is_labeled_asset_of(&asset_info.path, &path))
+                    .map(|asset_info| asset_info.handle_id),
+            );
+            asset_info
+                .values()
+                .filter(|asset_info| {
+                    asset_info
+                        .dependencies
+                        .iter()
+                        .any(|dependency| reloaded_ids.contains(dependency))
+                })
+                .map(|asset_info| asset_info.handle_id)
+                .collect::<Vec<_>>()
+        };
+        for dependent in dependents {
+            self.reload_with_dependents(dependent, reloaded);
+        }
+    }
+
+    /// Loads the asset at the given path on the [IOTaskPool] and returns a strong handle to it. Once every strong
+    /// handle to the asset is dropped, the asset is unloaded and loading the path again will load it from its
+    /// [AssetIo].
     // TODO: add type checking here. people shouldn't be able to request a Handle<Texture> for a Mesh asset
-    pub fn load<T, P: AsRef<Path>>(&self, path: P) -> Result<Handle<T>, AssetServerError> {
-        self.load_untyped(self.get_root_path()?.join(path))
-            .map(Handle::from)
+    pub fn load<T: Resource, P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Handle<T>, AssetServerError> {
+        self.load_untyped(path)
+            .map(|handle_id| self.shared.get_handle_with_id(handle_id))
     }
 
     pub fn load_sync<T: Resource, P: AsRef<Path>>(
@@ -252,86 +723,268 @@ impl AssetServer {
         T: 'static,
     {
         let path = path.as_ref();
-        if let Some(ref extension) = path.extension() {
-            if let Some(index) = self.extension_to_loader_index.get(
-                extension
-                    .to_str()
-                    .expect("extension should be a valid string"),
-            ) {
-                let mut asset_info_paths = self.asset_info_paths.write();
-                let handle_id = HandleId::new();
-                let resources = &self.loaders[*index];
-                let loader = resources.get::<Box<dyn AssetLoader<T>>>().unwrap();
-                let asset = loader.load_from_file(path)?;
-                let handle = Handle::from(handle_id);
-
-                assets.set(handle, asset);
-                asset_info_paths.insert(path.to_owned(), handle_id);
-                Ok(handle)
-            } else {
-                Err(AssetServerError::MissingAssetHandler)
+        let (read_path, loader_index) = match self.get_import(path, &self.extension_to_loader_index)
+        {
+            Some(import) => import,
+            None => (
+                path.to_owned(),
+                get_extension_index(path, &self.extension_to_loader_index)
+                    .ok_or(AssetServerError::MissingAssetHandler)?,
+            ),
+        };
+
+        let resources = &self.loaders[loader_index];
+        let loader = resources.get::<Box<dyn AssetLoader<T>>>().unwrap();
+        let mut load_context = LoadContext::new(path.to_owned(), self.shared.clone());
+        load_context.read_meta()?;
+        let bytes = self.shared.asset_io.load_path(&read_path)?;
+        let asset = future::block_on(loader.load(bytes, &mut load_context))
+            .map_err(AssetLoadError::LoaderError)?;
+
+        let handle_id = self.shared.get_or_reserve_handle_id(path);
+        let (handle_id, version) = {
+            let mut asset_info = self.asset_info.write();
+            let version = asset_info
+                .get(&handle_id)
+                .map_or(0, |asset_info| asset_info.load_state.get_version());
+            asset_info.insert(
+                handle_id,
+                AssetInfo {
+                    handle_id,
+                    path: path.to_owned(),
+                    load_state: LoadState::Loaded(version),
+                    dependencies: Vec::new(),
+                    failure: None,
+                },
+            );
+            (handle_id, version)
+        };
+        let (dependencies, labeled_assets) = load_context.into_parts();
+        assets.set(handle_id, asset);
+        self.set_labeled_assets(path, version, labeled_assets);
+        self.set_dependencies(handle_id, dependencies);
+        Ok(assets.get_handle(handle_id))
+    }
+
+    /// Loads the asset at the given path on the [IOTaskPool]. Paths of the form `path#label` load the labeled
+    /// asset with the given label, which requires loading the file at `path` if the labeled asset isn't loaded yet.
+    pub fn load_untyped<P: AsRef<Path>>(&self, path: P) -> Result<HandleId, AssetServerError> {
+        let path = path.as_ref();
+        let (source_path, label) = split_label(path);
+        let handler_index = self.get_handler_index(source_path)?;
+        if label.is_some() {
+            return self.load_labeled(path, source_path);
+        }
+
+        match self.get_import(path, &self.extension_to_handler_index) {
+            Some((imported_path, handler_index)) => {
+                self.load_file(path, handler_index, Some(imported_path))
             }
-        } else {
-            Err(AssetServerError::MissingAssetHandler)
+            None => self.load_file(path, handler_index, None),
         }
     }
 
-    pub fn load_untyped<P: AsRef<Path>>(&self, path: P) -> Result<HandleId, AssetServerError> {
+    /// Returns the id of the asset at the given path and loads it, unless it is already loaded or loading. Unlike
+    /// [AssetServer::load_untyped], this never reloads an asset.
+    pub fn get_or_load_untyped<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<HandleId, AssetServerError> {
         let path = path.as_ref();
-        if let Some(ref extension) = path.extension() {
-            if let Some(index) = self.extension_to_handler_index.get(
-                extension
-                    .to_str()
-                    .expect("Extension should be a valid string."),
-            ) {
-                let mut new_version = 0;
-                let handle_id = {
-                    let mut asset_info = self.asset_info.write();
-                    let mut asset_info_paths = self.asset_info_paths.write();
-                    if let Some(asset_info) = asset_info_paths
-                        .get(path)
-                        .and_then(|handle_id| asset_info.get_mut(&handle_id))
-                    {
-                        asset_info.load_state =
-                            if let LoadState::Loaded(_version) = asset_info.load_state {
-                                new_version += 1;
-                                LoadState::Loading(new_version)
-                            } else {
-                                LoadState::Loading(new_version)
-                            };
-                        asset_info.handle_id
-                    } else {
-                        let handle_id = HandleId::new();
-                        asset_info.insert(
-                            handle_id,
-                            AssetInfo {
-                                handle_id,
-                                path: path.to_owned(),
-                                load_state: LoadState::Loading(new_version),
-                            },
-                        );
-                        asset_info_paths.insert(path.to_owned(), handle_id);
-                        handle_id
-                    }
-                };
+        match self.get_loaded_id(path) {
+            Some(handle_id) => Ok(handle_id),
+            None => self.load_untyped(path),
+        }
+    }
 
-                self.send_request_to_loader_thread(LoadRequest {
+    /// Loads the file at the given path with the given handler. If `imported_path` is set, the processed file at
+    /// that path is read instead.
+    fn load_file(
+        &self,
+        path: &Path,
+        handler_index: usize,
+        imported_path: Option<PathBuf>,
+    ) -> Result<HandleId, AssetServerError> {
+        let mut new_version = 0;
+        let new_handle_id = self.shared.handle_id_for_path(path);
+        let handle_id = {
+            let mut asset_info = self.asset_info.write();
+            let mut asset_info_paths = self.shared.asset_info_paths.write();
+            if let Some(asset_info) = asset_info_paths
+                .get(path)
+                .and_then(|handle_id| asset_info.get_mut(handle_id))
+            {
+                // results of earlier loads of this asset are outdated
+                new_version = asset_info.load_state.get_version() + 1;
+                asset_info.load_state = LoadState::Loading(new_version);
+                asset_info.failure = None;
+                asset_info.handle_id
+            } else {
+                // loaders may have reserved an id for this path when loading an asset that depends on it
+                let handle_id = reserve_handle_id(&mut asset_info_paths, path, new_handle_id);
+                asset_info.insert(
                     handle_id,
-                    path: path.to_owned(),
-                    handler_index: *index,
-                    version: new_version,
+                    AssetInfo {
+                        handle_id,
+                        path: path.to_owned(),
+                        load_state: LoadState::Loading(new_version),
+                        dependencies: Vec::new(),
+                        failure: None,
+                    },
+                );
+                handle_id
+            }
+        };
+
+        self.queue_load_request(LoadRequest {
+            handle_id,
+            path: path.to_owned(),
+            handler_index,
+            version: new_version,
+            imported_path,
+        });
+
+        #[cfg(feature = "filesystem_watcher")]
+        {
+            // processed labeled assets are loaded on their own, but change along with their file
+            let path = split_label(path).0;
+            self.shared.asset_io.watch_path_for_changes(path)?;
+            self.shared
+                .asset_io
+                .watch_path_for_changes(&get_meta_path(path))?;
+        }
+        Ok(handle_id)
+    }
+
+    fn get_handler_index(&self, path: &Path) -> Result<usize, AssetServerError> {
+        get_extension_index(path, &self.extension_to_handler_index)
+            .ok_or(AssetServerError::MissingAssetHandler)
+    }
+
+    /// Returns the processed file that is loaded in place of the asset at the given path, along with the index that
+    /// `extension_to_index` maps its extension to. Imports without a handler for their extension are ignored.
+    fn get_import(
+        &self,
+        path: &Path,
+        extension_to_index: &HashMap<String, usize>,
+    ) -> Option<(PathBuf, usize)> {
+        let import_cache = self.import_cache.read();
+        let imported_path = &import_cache.imports.get(path)?.path;
+        let index = get_extension_index(imported_path, extension_to_index)?;
+        Some((imported_path.clone(), index))
+    }
+
+    /// Returns the id of the labeled asset at the given path. Its file is loaded unless the labeled asset is already
+    /// known or the file is currently loading. Labeled assets that were processed are loaded from their own
+    /// processed file instead.
+    fn load_labeled(&self, path: &Path, source_path: &Path) -> Result<HandleId, AssetServerError> {
+        if let Some((imported_path, handler_index)) =
+            self.get_import(path, &self.extension_to_handler_index)
+        {
+            return match self.get_loaded_id(path) {
+                Some(handle_id) => Ok(handle_id),
+                None => self.load_file(path, handler_index, Some(imported_path)),
+            };
+        }
+
+        let new_handle_id = self.shared.handle_id_for_path(path);
+        let (handle_id, load_source) = {
+            let mut asset_info = self.asset_info.write();
+            let mut asset_info_paths = self.shared.asset_info_paths.write();
+            let handle_id = reserve_handle_id(&mut asset_info_paths, path, new_handle_id);
+            let source_loading = matches!(
+                asset_info_paths
+                    .get(source_path)
+                    .and_then(|source_id| asset_info.get(source_id))
+                    .map(|source_info| &source_info.load_state),
+                Some(LoadState::Loading(_))
+            );
+            let load_source = !asset_info.contains_key(&handle_id) && !source_loading;
+            asset_info.entry(handle_id).or_insert_with(|| AssetInfo {
+                handle_id,
+                path: path.to_owned(),
+                load_state: LoadState::Loading(0),
+                dependencies: Vec::new(),
+                failure: None,
+            });
+            (handle_id, load_source)
+        };
+
+        // processed files only contain a single asset, so labeled assets without a processed file of their own are
+        // loaded from their source
+        if load_source {
+            self.load_file(source_path, self.get_handler_index(source_path)?, None)?;
+        }
+
+        Ok(handle_id)
+    }
+
+    /// Records the labeled assets that were loaded from the file at `path` and sends them to their [Assets]
+    /// collections. Labeled assets of this file that are still loading but weren't loaded this time fail to load,
+    /// unless they are loaded from their own processed file.
+    pub(crate) fn set_labeled_assets(
+        &self,
+        path: &Path,
+        version: AssetVersion,
+        mut labeled_assets: Vec<LabeledAsset>,
+    ) {
+        labeled_assets.retain(|labeled_asset| {
+            if !labeled_asset.can_send() {
+                log::error!(
+                    "Failed to add labeled asset {:?} because its type was not added using AddAsset::add_asset",
+                    labeled_asset.path
+                );
+            }
+            labeled_asset.can_send()
+        });
+
+        {
+            let imported_paths = self
+                .import_cache
+                .read()
+                .imports
+                .keys()
+                .filter(|import_path| is_labeled_asset_of(import_path, path))
+                .cloned()
+                .collect::<Vec<_>>();
+            let new_handle_ids = labeled_assets
+                .iter()
+                .map(|labeled_asset| self.shared.handle_id_for_path(&labeled_asset.path))
+                .collect::<Vec<_>>();
+            let mut asset_info = self.asset_info.write();
+            let mut asset_info_paths = self.shared.asset_info_paths.write();
+            for (labeled_asset, new_handle_id) in labeled_assets.iter().zip(new_handle_ids) {
+                let handle_id =
+                    reserve_handle_id(&mut asset_info_paths, &labeled_asset.path, new_handle_id);
+                let info = asset_info.entry(handle_id).or_insert_with(|| AssetInfo {
+                    handle_id,
+                    path: labeled_asset.path.clone(),
+                    load_state: LoadState::Loading(version),
+                    dependencies: Vec::new(),
+                    failure: None,
                 });
+                if version >= info.load_state.get_version() {
+                    info.load_state = LoadState::Loading(version);
+                }
+            }
 
-                // TODO: watching each asset explicitly is a simpler implementation, its possible it would be more efficient to watch
-                // folders instead (when possible)
-                #[cfg(feature = "filesystem_watcher")]
-                Self::watch_path_for_changes(&mut self.filesystem_watcher.write(), path)?;
-                Ok(handle_id)
-            } else {
-                Err(AssetServerError::MissingAssetHandler)
+            for info in asset_info.values_mut() {
+                if let LoadState::Loading(loading_version) = info.load_state {
+                    if loading_version <= version
+                        && is_labeled_asset_of(&info.path, path)
+                        && !imported_paths.contains(&info.path)
+                        && !labeled_assets
+                            .iter()
+                            .any(|labeled_asset| labeled_asset.path == info.path)
+                    {
+                        info.load_state = LoadState::Failed(version);
+                    }
+                }
             }
-        } else {
-            Err(AssetServerError::MissingAssetHandler)
+        }
+
+        for labeled_asset in labeled_assets {
+            labeled_asset.send(version);
         }
     }
 
@@ -339,10 +992,61 @@ impl AssetServer {
         if let Some(asset_info) = self.asset_info.write().get_mut(&handle_id) {
             if load_state.get_version() >= asset_info.load_state.get_version() {
                 asset_info.load_state = load_state;
+                asset_info.failure = None;
             }
         }
     }
 
+    /// Marks the asset as [LoadState::Failed] and keeps the failure, unless the failed load is outdated
+    pub(crate) fn set_load_failure(
+        &self,
+        handle_id: HandleId,
+        version: AssetVersion,
+        failure: Arc<AssetLoadFailure>,
+    ) {
+        if let Some(asset_info) = self.asset_info.write().get_mut(&handle_id) {
+            if version >= asset_info.load_state.get_version() {
+                asset_info.load_state = LoadState::Failed(version);
+                asset_info.failure = Some(failure);
+            }
+        }
+    }
+
+    /// Returns why the given asset failed to load. Failures are kept until the asset is loaded again.
+    pub fn get_load_failure<H: Into<HandleId>>(&self, handle: H) -> Option<Arc<AssetLoadFailure>> {
+        self.asset_info
+            .read()
+            .get(&handle.into())
+            .and_then(|asset_info| asset_info.failure.clone())
+    }
+
+    /// Loads the given asset again if it failed to load, for example once the user fixed the asset's file. Labeled
+    /// assets are loaded again along with their file. Assets that didn't fail are left as they are.
+    pub fn retry_load<H: Into<HandleId>>(&self, handle: H) -> Result<(), AssetServerError> {
+        let path = {
+            let mut asset_info = self.asset_info.write();
+            match asset_info.get_mut(&handle.into()) {
+                Some(asset_info) if matches!(asset_info.load_state, LoadState::Failed(_)) => {
+                    let version = asset_info.load_state.get_version();
+                    asset_info.load_state = LoadState::Loading(version);
+                    asset_info.failure = None;
+                    asset_info.path.clone()
+                }
+                _ => return Ok(()),
+            }
+        };
+
+        match self.get_import(&path, &self.extension_to_handler_index) {
+            Some((imported_path, handler_index)) if split_label(&path).1.is_some() => {
+                self.load_file(&path, handler_index, Some(imported_path))?;
+            }
+            _ => {
+                self.load_untyped(split_label(&path).0)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_load_state_untyped(&self, handle_id: HandleId) -> Option<LoadState> {
         self.asset_info
             .read()
@@ -350,8 +1054,76 @@ impl AssetServer {
             .map(|asset_info| asset_info.load_state.clone())
     }
 
-    pub fn get_load_state<T>(&self, handle: Handle<T>) -> Option<LoadState> {
-        self.get_load_state_untyped(handle.id)
+    pub fn get_load_state<H: Into<HandleId>>(&self, handle: H) -> Option<LoadState> {
+        self.get_load_state_untyped(handle.into())
+    }
+
+    /// Returns the load state of the given asset, taking the assets it depends on into account. The asset is only
+    /// [LoadState::Loaded] once its dependencies, and their dependencies, are loaded as well. If any of them
+    /// failed to load, the state is [LoadState::Failed]. Dependencies that are unknown to the asset server, for
+    /// example because they were freed, count as [LoadState::Loading]. Returns `None` only if the given asset is
+    /// unknown.
+    pub fn get_recursive_load_state<H: Into<HandleId>>(&self, handle: H) -> Option<LoadState> {
+        let handle_id = handle.into();
+        let asset_info = self.asset_info.read();
+        let version = asset_info.get(&handle_id)?.load_state.get_version();
+
+        let mut loading = false;
+        let mut visited = HashSet::default();
+        let mut pending = vec![handle_id];
+        while let Some(handle_id) = pending.pop() {
+            if !visited.insert(handle_id) {
+                continue;
+            }
+
+            let info = match asset_info.get(&handle_id) {
+                Some(info) => info,
+                None => {
+                    loading = true;
+                    continue;
+                }
+            };
+            match info.load_state {
+                LoadState::Loaded(_) => {}
+                LoadState::Loading(_) => loading = true,
+                LoadState::Failed(_) => return Some(LoadState::Failed(version)),
+            }
+            pending.extend(info.dependencies.iter().copied());
+            // labeled assets depend on the dependencies of the file they were loaded from
+            if let (source_path, Some(_)) = split_label(&info.path) {
+                if let Some(source_id) = self.shared.asset_info_paths.read().get(source_path) {
+                    pending.push(*source_id);
+                }
+            }
+        }
+
+        Some(if loading {
+            LoadState::Loading(version)
+        } else {
+            LoadState::Loaded(version)
+        })
+    }
+
+    /// Records the assets the given asset depends on and loads the ones that aren't loaded yet
+    pub(crate) fn set_dependencies(&self, handle_id: HandleId, dependency_paths: Vec<PathBuf>) {
+        let mut dependencies = Vec::new();
+        for path in dependency_paths {
+            let dependency_id = match self.get_or_load_untyped(&path) {
+                Ok(dependency_id) => dependency_id,
+                Err(err) => {
+                    log::error!("Failed to load asset dependency {:?}: {:?}", path, err);
+                    continue;
+                }
+            };
+
+            if !dependencies.contains(&dependency_id) {
+                dependencies.push(dependency_id);
+            }
+        }
+
+        if let Some(asset_info) = self.asset_info.write().get_mut(&handle_id) {
+            asset_info.dependencies = dependencies;
+        }
     }
 
     pub fn get_group_load_state(&self, handle_ids: &[HandleId]) -> Option<LoadState> {
@@ -370,80 +1142,63 @@ impl AssetServer {
         Some(load_state)
     }
 
-    fn send_request_to_loader_thread(&self, load_request: LoadRequest) {
-        // NOTE: This lock makes the call to Arc::strong_count safe. Removing (or reordering) it could result in undefined behavior
-        let mut loader_threads = self.loader_threads.write();
-        if loader_threads.len() < self.max_loader_threads {
-            let loader_thread = LoaderThread {
-                requests: Arc::new(RwLock::new(vec![load_request])),
-            };
-            let requests = loader_thread.requests.clone();
-            loader_threads.push(loader_thread);
-            Self::start_thread(self.asset_handlers.clone(), requests);
-        } else {
-            let most_free_thread = loader_threads
-                .iter()
-                .min_by_key(|l| l.requests.read().len())
-                .unwrap();
-            let mut requests = most_free_thread.requests.write();
-            requests.push(load_request);
-            // if most free thread only has one reference, the thread as spun down. if so, we need to spin it back up!
-            if Arc::strong_count(&most_free_thread.requests) == 1 {
-                Self::start_thread(
-                    self.asset_handlers.clone(),
-                    most_free_thread.requests.clone(),
-                );
-            }
-        }
+    fn queue_load_request(&self, load_request: LoadRequest) {
+        let mut load_queue = self.load_queue.lock();
+        // only the latest version of an asset needs to be loaded
+        load_queue
+            .pending
+            .retain(|pending_request| pending_request.handle_id != load_request.handle_id);
+        load_queue.pending.push_back(load_request);
     }
 
-    fn start_thread(
-        request_handlers: Arc<RwLock<Vec<Box<dyn AssetLoadRequestHandler>>>>,
-        requests: Arc<RwLock<Vec<LoadRequest>>>,
-    ) {
-        thread::spawn(move || {
-            loop {
-                let request = {
-                    let mut current_requests = requests.write();
-                    if current_requests.len() == 0 {
-                        // if there are no requests, spin down the thread
-                        break;
-                    }
+    /// Starts queued loads on the [IOTaskPool]
+    pub fn start_load_tasks_system(asset_server: Res<AssetServer>, io_task_pool: Res<IOTaskPool>) {
+        asset_server.start_load_tasks(&io_task_pool);
+    }
 
-                    current_requests.pop().unwrap()
-                };
+    fn start_load_tasks(&self, task_pool: &TaskPool) {
+        let max_concurrent_loads = self
+            .max_concurrent_loads
+            .unwrap_or_else(|| task_pool.thread_num())
+            .max(1);
+        let mut load_queue = self.load_queue.lock();
+        load_queue
+            .running
+            .retain(|_, task| task.poll_once().is_none());
 
-                let handlers = request_handlers.read();
-                let request_handler = &handlers[request.handler_index];
-                request_handler.handle_request(&request);
-            }
-        });
+        while load_queue.running.len() < max_concurrent_loads {
+            let load_request = match load_queue.pending.pop_front() {
+                Some(load_request) => load_request,
+                None => break,
+            };
+
+            let handler = self.asset_handlers.read()[load_request.handler_index].clone();
+            let load_context = LoadContext::new(load_request.path.clone(), self.shared.clone());
+            let handle_id = load_request.handle_id;
+            let task = task_pool.spawn(async move {
+                handler.handle_request(&load_request, load_context).await;
+            });
+            // this cancels the load of an older version of the asset
+            load_queue.running.insert(handle_id, task);
+        }
     }
 
     fn load_assets_in_folder_recursive(
         &self,
         path: &Path,
     ) -> Result<Vec<HandleId>, AssetServerError> {
-        if !path.is_dir() {
+        if !self.shared.asset_io.is_directory(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
-        let root_path = self.get_root_path()?;
         let mut handle_ids = Vec::new();
-        for entry in fs::read_dir(path)? {
-            let entry = entry?;
-            let child_path = entry.path();
-            if child_path.is_dir() {
+        for child_path in self.shared.asset_io.read_directory(path)? {
+            if self.shared.asset_io.is_directory(&child_path) {
                 handle_ids.extend(self.load_assets_in_folder_recursive(&child_path)?);
             } else {
-                let relative_child_path = child_path.strip_prefix(&root_path).unwrap();
-                let handle = match self.load_untyped(
-                    relative_child_path
-                        .to_str()
-                        .expect("Path should be a valid string"),
-                ) {
+                let handle = match self.load_untyped(&child_path) {
                     Ok(handle) => handle,
                     Err(AssetServerError::MissingAssetHandler) => continue,
                     Err(err) => return Err(err),
@@ -455,4 +1210,760 @@ impl AssetServer {
 
         Ok(handle_ids)
     }
+
+    fn process_assets_in_folder_recursive(
+        &self,
+        path: &Path,
+        import_cache: &mut ImportCache,
+    ) -> Result<usize, AssetServerError> {
+        if !self.shared.asset_io.is_directory(path) {
+            return Err(AssetServerError::AssetFolderNotADirectory(
+                path.to_str().unwrap().to_string(),
+            ));
+        }
+
+        let mut processed = 0;
+        for child_path in self.shared.asset_io.read_directory(path)? {
+            if child_path.starts_with(&self.import_folder) {
+                continue;
+            }
+
+            if self.shared.asset_io.is_directory(&child_path) {
+                processed += self.process_assets_in_folder_recursive(&child_path, import_cache)?;
+                continue;
+            }
+
+            let processor =
+                match get_extension_index(&child_path, &self.extension_to_processor_index) {
+                    Some(index) => &self.processors[index],
+                    None => continue,
+                };
+            let mut load_context = LoadContext::new(child_path.clone(), self.shared.clone());
+            load_context.read_meta()?;
+            let bytes = self.shared.asset_io.load_path(&child_path)?;
+            // changing the settings of an asset changes how it is processed
+            let source_hash = hash_source(&bytes, load_context.settings().meta_bytes());
+            if let Some(imported_asset) = import_cache.imports.get(&child_path) {
+                if imported_asset.source_hash == source_hash {
+                    continue;
+                }
+            }
+
+            let processed_bytes = processor
+                .process(bytes, &mut load_context)
+                .map_err(|err| AssetServerError::AssetProcessError(child_path.clone(), err))?;
+            let imported_path = self.import_folder.join(format!(
+                "{:016x}.{}",
+                source_hash,
+                processor.processed_extension()
+            ));
+            self.shared
+                .asset_io
+                .save_path(&imported_path, &processed_bytes)?;
+
+            // labeled assets are saved to their own processed files, so they can be loaded without their source
+            import_cache
+                .imports
+                .retain(|path, _| !is_labeled_asset_of(path, &child_path));
+            let (_, labeled_assets) = load_context.into_parts();
+            for (index, labeled_asset) in labeled_assets.iter().enumerate() {
+                // labeled assets without a saver for their type are still loaded from the source
+                let (labeled_bytes, extension) = match labeled_asset.save(&self.savers) {
+                    Some(result) => result.map_err(|err| {
+                        AssetServerError::AssetProcessError(labeled_asset.path.clone(), err)
+                    })?,
+                    None => continue,
+                };
+                let labeled_imported_path = self
+                    .import_folder
+                    .join(format!("{:016x}_{}.{}", source_hash, index, extension));
+                self.shared
+                    .asset_io
+                    .save_path(&labeled_imported_path, &labeled_bytes)?;
+                import_cache.imports.insert(
+                    labeled_asset.path.clone(),
+                    ImportedAsset {
+                        source_hash,
+                        path: labeled_imported_path,
+                    },
+                );
+            }
+
+            import_cache.imports.insert(
+                child_path,
+                ImportedAsset {
+                    source_hash,
+                    path: imported_path,
+                },
+            );
+            processed += 1;
+        }
+
+        Ok(processed)
+    }
+}
+
+fn get_extension_index(path: &Path, extension_to_index: &HashMap<String, usize>) -> Option<usize> {
+    path.extension()
+        .and_then(|extension| {
+            extension_to_index.get(
+                extension
+                    .to_str()
+                    .expect("Extension should be a valid string."),
+            )
+        })
+        .copied()
+}
+
+fn is_labeled_asset_of(path: &Path, source_path: &Path) -> bool {
+    matches!(split_label(path), (path, Some(_)) if path == source_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AddAsset, AssetEvent, AssetPlugin, ChannelAssetHandler, LoadAndSave, MemoryAssetIo,
+    };
+    use bevy_app::{App, Events, TestApp};
+    use bevy_type_registry::TypeRegistryPlugin;
+    use bevy_utils::BoxedFuture;
+    use serde::Deserialize;
+    use std::{thread, time::Duration};
+    use uuid::Uuid;
+
+    struct Text {
+        text: String,
+        dependencies: Vec<Handle<Text>>,
+    }
+
+    #[derive(Default, Deserialize)]
+    #[serde(default)]
+    struct TextSettings {
+        uppercase: bool,
+    }
+
+    /// Loads text files. Lines starting with "load: " load the given path as a dependency and lines of the form
+    /// "label name: text" add a labeled asset.
+    #[derive(Default)]
+    struct TextLoader;
+
+    impl AssetLoader<Text> for TextLoader {
+        fn from_bytes(
+            &self,
+            _asset_path: &Path,
+            bytes: Vec<u8>,
+            settings: &LoaderSettings,
+        ) -> Result<Text> {
+            let mut text = String::from_utf8(bytes)?;
+            if settings.get::<TextSettings>()?.uppercase {
+                text = text.to_uppercase();
+            }
+
+            Ok(Text {
+                text,
+                dependencies: Vec::new(),
+            })
+        }
+
+        fn load<'a>(
+            &'a self,
+            bytes: Vec<u8>,
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<Text>> {
+            Box::pin(async move {
+                let mut text =
+                    self.from_bytes(load_context.path(), bytes, load_context.settings())?;
+                for line in text.text.lines() {
+                    if let Some(path) = line.strip_prefix("load: ") {
+                        text.dependencies.push(load_context.load_dependency(path));
+                    } else if let Some(labeled_text) = line.strip_prefix("label ") {
+                        let mut parts = labeled_text.splitn(2, ": ");
+                        let label = parts.next().unwrap();
+                        let labeled_asset = Text {
+                            text: parts.next().unwrap_or_default().to_string(),
+                            dependencies: Vec::new(),
+                        };
+                        load_context.set_labeled_asset(label, labeled_asset);
+                    }
+                }
+
+                Ok(text)
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt", "text"]
+        }
+    }
+
+    /// Saves texts in upper case
+    #[derive(Default)]
+    struct UppercaseSaver;
+
+    impl AssetSaver<Text> for UppercaseSaver {
+        fn save(&self, text: &Text) -> Result<Vec<u8>> {
+            Ok(text.text.to_uppercase().into_bytes())
+        }
+
+        fn extension(&self) -> &str {
+            "text"
+        }
+    }
+
+    fn text_app(asset_io: MemoryAssetIo) -> TestApp {
+        text_app_with_server(AssetServer::new(asset_io))
+    }
+
+    fn text_app_with_server(asset_server: AssetServer) -> TestApp {
+        App::build()
+            .add_resource(asset_server)
+            .add_plugin(TypeRegistryPlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Text>()
+            .add_asset_loader::<Text, TextLoader>()
+            .test_app()
+    }
+
+    /// Updates the app until `condition` is met. Assets are loaded on other threads, so this may take a few updates.
+    fn update_until(app: &mut TestApp, condition: impl Fn(&AssetServer) -> bool) {
+        for _ in 0..1000 {
+            app.update();
+            if condition(&app.resource::<AssetServer>()) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        panic!("condition was not met");
+    }
+
+    #[test]
+    fn free_asset_forgets_path() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.txt", b"a".to_vec());
+
+        let mut assets = Assets::<Text>::default();
+        let mut asset_server = AssetServer::new(asset_io.clone());
+        asset_server.add_loader(TextLoader);
+        asset_server.register_asset_type(&assets);
+
+        let handle = asset_server.load_sync(&mut assets, "a.txt").unwrap();
+        let path_handle = asset_server.get_handle::<Text, _>("a.txt").unwrap();
+        assert!(handle.is_strong());
+        assert_eq!(handle, path_handle);
+
+        drop(path_handle);
+        drop(handle);
+        for handle_id in assets.free_unused_assets() {
+            asset_server.free_asset(handle_id);
+        }
+        assert!(assets.is_empty());
+        assert!(asset_server.get_handle::<Text, _>("a.txt").is_none());
+
+        asset_io.insert("a.txt", b"b".to_vec());
+        let handle = asset_server.load_sync(&mut assets, "a.txt").unwrap();
+        assert_eq!(
+            assets.get(&handle).map(|text| text.text.as_str()),
+            Some("b")
+        );
+    }
+
+    #[test]
+    fn load_asset_folder() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("assets/a.txt", b"a".to_vec());
+        asset_io.insert("assets/texts/b.txt", b"b".to_vec());
+        asset_io.insert("assets/texts/c.png", Vec::new());
+
+        let mut asset_server = AssetServer::new(asset_io);
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        asset_server.add_handler(ChannelAssetHandler::new(TextLoader, sender));
+
+        let handle_ids = asset_server.load_asset_folder("assets").unwrap();
+        let mut paths = handle_ids
+            .iter()
+            .map(|handle_id| asset_server.asset_info.read()[handle_id].path.clone())
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("assets/a.txt"),
+                PathBuf::from("assets/texts/b.txt")
+            ]
+        );
+        assert!(matches!(
+            asset_server.load_asset_folder("assets/a.txt"),
+            Err(AssetServerError::AssetFolderNotADirectory(_))
+        ));
+    }
+
+    #[test]
+    fn concurrent_loads_are_limited_and_cancelled() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.txt", b"a".to_vec());
+        asset_io.insert("b.txt", b"b".to_vec());
+
+        let mut asset_server = AssetServer::new(asset_io).with_max_concurrent_loads(1);
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        asset_server.add_handler(ChannelAssetHandler::new(TextLoader, sender));
+        let task_pool = TaskPool::new();
+
+        let a = asset_server.load_untyped("a.txt").unwrap();
+        let b = asset_server.load_untyped("b.txt").unwrap();
+        asset_server.start_load_tasks(&task_pool);
+        {
+            let load_queue = asset_server.load_queue.lock();
+            assert!(load_queue.running.contains_key(&a));
+            assert_eq!(load_queue.pending.len(), 1);
+        }
+
+        asset_server.free_asset(a);
+        assert!(asset_server.load_queue.lock().running.is_empty());
+
+        asset_server.start_load_tasks(&task_pool);
+        let load_queue = asset_server.load_queue.lock();
+        assert!(load_queue.running.contains_key(&b));
+        assert!(load_queue.pending.is_empty());
+    }
+
+    #[test]
+    fn save_asset() {
+        let asset_io = MemoryAssetIo::default();
+        let mut asset_server = AssetServer::new(asset_io.clone());
+        let text = Text {
+            text: "a".to_string(),
+            dependencies: Vec::new(),
+        };
+        assert!(matches!(
+            asset_server.save(&text, "a.text"),
+            Err(AssetServerError::MissingAssetSaver)
+        ));
+
+        asset_server.add_saver(UppercaseSaver);
+        asset_server.save(&text, "a.text").unwrap();
+        assert_eq!(asset_io.load_path(Path::new("a.text")).unwrap(), b"A");
+    }
+
+    #[test]
+    fn process_assets() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("assets/a.txt", b"a".to_vec());
+        asset_io.insert("assets/b.png", Vec::new());
+
+        let mut asset_server = AssetServer::new(asset_io.clone());
+        asset_server.add_processor(LoadAndSave::new(TextLoader, UppercaseSaver));
+        assert_eq!(asset_server.process_assets("assets").unwrap(), 1);
+        // unchanged sources are not processed again
+        assert_eq!(asset_server.process_assets("assets").unwrap(), 0);
+        asset_io.insert("assets/a.txt", b"b".to_vec());
+        assert_eq!(asset_server.process_assets("assets").unwrap(), 1);
+
+        // the processed file is loaded in place of its source
+        let mut app = text_app(asset_io.clone());
+        let a = app
+            .resource::<AssetServer>()
+            .load::<Text, _>("assets/a.txt")
+            .unwrap();
+        update_until(&mut app, |asset_server| {
+            asset_server.get_load_state(&a) == Some(LoadState::Loaded(0))
+        });
+        assert_eq!(app.resource::<Assets<Text>>().get(&a).unwrap().text, "B");
+
+        // import caches that were written with another source hash are ignored, so sources are loaded instead
+        let import_cache_path = Path::new("imported").join(ImportCache::FILE_NAME);
+        let import_cache =
+            String::from_utf8(asset_io.load_path(&import_cache_path).unwrap()).unwrap();
+        assert!(import_cache.contains("hash_version: 1"));
+        asset_io.insert(
+            import_cache_path,
+            import_cache
+                .replace("hash_version: 1", "hash_version: 0")
+                .into_bytes(),
+        );
+        let mut app = text_app(asset_io);
+        let a = app
+            .resource::<AssetServer>()
+            .load::<Text, _>("assets/a.txt")
+            .unwrap();
+        update_until(&mut app, |asset_server| {
+            asset_server.get_load_state(&a) == Some(LoadState::Loaded(0))
+        });
+        assert_eq!(app.resource::<Assets<Text>>().get(&a).unwrap().text, "b");
+    }
+
+    #[test]
+    fn process_labeled_assets() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("assets/a.txt", b"a\nlabel x: x text".to_vec());
+
+        let mut asset_server = AssetServer::new(asset_io.clone());
+        asset_server.add_processor(LoadAndSave::new(TextLoader, UppercaseSaver));
+        asset_server.add_saver(UppercaseSaver);
+        assert_eq!(asset_server.process_assets("assets").unwrap(), 1);
+
+        // the labeled asset is loaded from its own processed file, so it doesn't fail when the processed file of
+        // its source doesn't contain it
+        let mut app = text_app(asset_io);
+        let (a, x) = {
+            let asset_server = app.resource::<AssetServer>();
+            let x = asset_server.load::<Text, _>("assets/a.txt#x").unwrap();
+            asset_server.set_labeled_assets(Path::new("assets/a.txt"), 0, Vec::new());
+            assert_eq!(asset_server.get_load_state(&x), Some(LoadState::Loading(0)));
+            (asset_server.load::<Text, _>("assets/a.txt").unwrap(), x)
+        };
+        update_until(&mut app, |asset_server| {
+            asset_server.get_load_state(&a) == Some(LoadState::Loaded(0))
+                && asset_server.get_load_state(&x) == Some(LoadState::Loaded(0))
+        });
+        assert_eq!(
+            app.resource::<Assets<Text>>().get(&x).unwrap().text,
+            "X TEXT"
+        );
+    }
+
+    #[test]
+    fn meta_settings() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.txt", b"a".to_vec());
+        asset_io.insert(
+            "a.txt.meta",
+            br#"(
+                uuid: "9b8a3c6e-2a4f-4c8e-8f3b-1d2e3f4a5b6c",
+                settings: (uppercase: true),
+            )"#
+            .to_vec(),
+        );
+        asset_io.insert("b.txt", b"b".to_vec());
+        asset_io.insert("b.txt.meta", b"(settings: (uppercase: true))".to_vec());
+
+        let mut assets = Assets::<Text>::default();
+        let mut asset_server = AssetServer::new(asset_io);
+        asset_server.add_loader(TextLoader);
+        asset_server.register_asset_type(&assets);
+
+        let a = asset_server.load_sync(&mut assets, "a.txt").unwrap();
+        assert_eq!(assets.get(&a).unwrap().text, "A");
+        assert!(matches!(
+            asset_server.load_sync(&mut assets, "b.txt"),
+            Err(AssetServerError::AssetLoadError(AssetLoadError::MetaError(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn stable_handle_ids() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.txt", b"a".to_vec());
+        asset_io.insert("b.txt", b"b\nlabel c: c".to_vec());
+        asset_io.insert(
+            "b.txt.meta",
+            br#"(uuid: "9b8a3c6e-2a4f-4c8e-8f3b-1d2e3f4a5b6c")"#.to_vec(),
+        );
+
+        let mut assets = Assets::<Text>::default();
+        let mut asset_server = AssetServer::new(asset_io);
+        asset_server.add_loader(TextLoader);
+        asset_server.register_asset_type(&assets);
+
+        let a = asset_server.load_sync(&mut assets, "a.txt").unwrap();
+        assert_eq!(a.id, HandleId::from_path(Path::new("a.txt")));
+        assert_eq!(
+            asset_server.get_handle_path(&a),
+            Some(PathBuf::from("a.txt"))
+        );
+
+        let b = asset_server.load_sync(&mut assets, "b.txt").unwrap();
+        let b_id = HandleId(Uuid::parse_str("9b8a3c6e-2a4f-4c8e-8f3b-1d2e3f4a5b6c").unwrap());
+        assert_eq!(b.id, b_id);
+        assert_eq!(
+            asset_server.get_handle::<Text, _>("b.txt#c").unwrap().id,
+            HandleId::from_label(b_id, "c")
+        );
+
+        // ids stay the same once assets are freed and loaded again
+        drop(a);
+        for handle_id in assets.free_unused_assets() {
+            asset_server.free_asset(handle_id);
+        }
+        let a = asset_server.load_sync(&mut assets, "a.txt").unwrap();
+        assert_eq!(a.id, HandleId::from_path(Path::new("a.txt")));
+    }
+
+    #[test]
+    fn recursive_load_state() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.txt", b"a\nload: b.txt".to_vec());
+        asset_io.insert("b.txt", b"b\nload: c.txt".to_vec());
+        asset_io.insert("c.txt", b"c".to_vec());
+        asset_io.insert("d.txt", b"d\nload: missing.txt".to_vec());
+
+        let mut app = text_app(asset_io);
+        let (a, d) = {
+            let asset_server = app.resource::<AssetServer>();
+            (
+                asset_server.load::<Text, _>("a.txt").unwrap(),
+                asset_server.load::<Text, _>("d.txt").unwrap(),
+            )
+        };
+
+        update_until(&mut app, |asset_server| {
+            asset_server.get_recursive_load_state(&a) == Some(LoadState::Loaded(0))
+        });
+        {
+            let asset_server = app.resource::<AssetServer>();
+            let c = asset_server.get_handle::<Text, _>("c.txt").unwrap();
+            assert_eq!(asset_server.get_load_state(&c), Some(LoadState::Loaded(0)));
+
+            let texts = app.resource::<Assets<Text>>();
+            let b = &texts.get(&a).unwrap().dependencies[0];
+            assert_eq!(texts.get(b).unwrap().text, "b\nload: c.txt");
+            assert_eq!(texts.get(b).unwrap().dependencies[0], c);
+
+            // a freed dependency isn't loaded, but the asset that depends on it is still known
+            asset_server.free_asset(c.id);
+            assert_eq!(
+                asset_server.get_recursive_load_state(&a),
+                Some(LoadState::Loading(0))
+            );
+        }
+
+        update_until(&mut app, |asset_server| {
+            asset_server.get_recursive_load_state(&d) != Some(LoadState::Loading(0))
+        });
+        let asset_server = app.resource::<AssetServer>();
+        assert_eq!(asset_server.get_load_state(&d), Some(LoadState::Loaded(0)));
+        assert_eq!(
+            asset_server.get_recursive_load_state(&d),
+            Some(LoadState::Failed(0))
+        );
+    }
+
+    #[test]
+    fn load_failures() {
+        let asset_io = MemoryAssetIo::default();
+        let mut app = text_app(asset_io.clone());
+        let mut event_reader = app.resource::<Events<AssetEvent<Text>>>().get_reader();
+        let a = app
+            .resource::<AssetServer>()
+            .load::<Text, _>("a.txt")
+            .unwrap();
+
+        update_until(&mut app, |asset_server| {
+            asset_server.get_load_state(&a) == Some(LoadState::Failed(0))
+        });
+        {
+            let failure = app.resource::<AssetServer>().get_load_failure(&a).unwrap();
+            assert_eq!(failure.path, Path::new("a.txt"));
+            assert!(failure.loader.ends_with("TextLoader"));
+            assert!(matches!(
+                failure
+                    .chain()
+                    .nth(2)
+                    .unwrap()
+                    .downcast_ref::<AssetIoError>(),
+                Some(AssetIoError::NotFound(_))
+            ));
+
+            let events = app.resource::<Events<AssetEvent<Text>>>();
+            assert!(event_reader.iter(&events).any(|event| matches!(
+                event,
+                AssetEvent::Failed { handle, failure: event_failure }
+                    if *handle == a && Arc::ptr_eq(event_failure, &failure)
+            )));
+        }
+
+        asset_io.insert("a.txt", b"a".to_vec());
+        app.resource::<AssetServer>().retry_load(&a).unwrap();
+        update_until(&mut app, |asset_server| {
+            asset_server.get_load_state(&a) == Some(LoadState::Loaded(1))
+        });
+        assert!(app.resource::<AssetServer>().get_load_failure(&a).is_none());
+        assert_eq!(app.resource::<Assets<Text>>().get(&a).unwrap().text, "a");
+    }
+
+    #[test]
+    #[cfg(feature = "filesystem_watcher")]
+    fn hot_reload_dependents() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.txt", b"a\nload: b.txt".to_vec());
+        asset_io.insert("b.txt", b"b".to_vec());
+        asset_io.insert("c.txt", b"c".to_vec());
+
+        let mut app = text_app(asset_io.clone());
+        let (a, c) = {
+            let asset_server = app.resource::<AssetServer>();
+            asset_server.watch_for_changes().unwrap();
+            (
+                asset_server.load::<Text, _>("a.txt").unwrap(),
+                asset_server.load::<Text, _>("c.txt").unwrap(),
+            )
+        };
+        update_until(&mut app, |asset_server| {
+            asset_server.get_recursive_load_state(&a) == Some(LoadState::Loaded(0))
+                && asset_server.get_load_state(&c) == Some(LoadState::Loaded(0))
+        });
+
+        asset_io.insert("b.txt", b"b2".to_vec());
+        update_until(&mut app, |asset_server| {
+            asset_server.get_recursive_load_state(&a) == Some(LoadState::Loaded(1))
+        });
+
+        let asset_server = app.resource::<AssetServer>();
+        let b = asset_server.get_handle::<Text, _>("b.txt").unwrap();
+        assert_eq!(asset_server.get_load_state(&b), Some(LoadState::Loaded(1)));
+        assert_eq!(asset_server.get_load_state(&c), Some(LoadState::Loaded(0)));
+        assert_eq!(app.resource::<Assets<Text>>().get(&b).unwrap().text, "b2");
+    }
+
+    /// Creates, replaces and renames files in a watched asset folder using `write` and `rename`
+    #[cfg(feature = "filesystem_watcher")]
+    fn hot_reload_asset_folder<T: AssetIo>(
+        asset_io: T,
+        write: impl Fn(&str, &str),
+        rename: impl Fn(&str, &str),
+    ) {
+        write("texts/a.txt", "a");
+        write("texts/b.txt", "b");
+        let mut app = text_app_with_server(
+            AssetServer::new(asset_io).with_change_debounce(Duration::from_millis(50)),
+        );
+        let (a, b) = {
+            let asset_server = app.resource::<AssetServer>();
+            asset_server.watch_for_changes().unwrap();
+            asset_server.load_asset_folder("texts").unwrap();
+            (
+                asset_server.get_handle::<Text, _>("texts/a.txt").unwrap(),
+                asset_server.get_handle::<Text, _>("texts/b.txt").unwrap(),
+            )
+        };
+        update_until(&mut app, |asset_server| {
+            asset_server.get_load_state(&a) == Some(LoadState::Loaded(0))
+                && asset_server.get_load_state(&b) == Some(LoadState::Loaded(0))
+        });
+
+        // new files are only loaded inside of asset folders. Nothing holds a handle to them, so dropping one would
+        // free them.
+        write("texts/new.txt", "new");
+        write("other.txt", "other");
+        let new_path = Path::new("texts/new.txt");
+        update_until(&mut app, |asset_server| {
+            matches!(
+                asset_server
+                    .get_loaded_id(new_path)
+                    .and_then(|new| asset_server.get_load_state(new)),
+                Some(LoadState::Loaded(_))
+            )
+        });
+        {
+            let asset_server = app.resource::<AssetServer>();
+            let new = Handle::<Text>::weak(asset_server.get_loaded_id(new_path).unwrap());
+            assert_eq!(
+                app.resource::<Assets<Text>>().get(&new).unwrap().text,
+                "new"
+            );
+            assert!(asset_server.get_loaded_id(Path::new("other.txt")).is_none());
+        }
+
+        // saving by renaming a temporary file reloads the asset
+        write("texts/a.txt.tmp", "a2");
+        rename("texts/a.txt.tmp", "texts/a.txt");
+        update_until(
+            &mut app,
+            |asset_server| matches!(asset_server.get_load_state(&a), Some(LoadState::Loaded(version)) if version > 0),
+        );
+        assert_eq!(app.resource::<Assets<Text>>().get(&a).unwrap().text, "a2");
+
+        // renamed assets keep their handles
+        rename("texts/b.txt", "texts/c.txt");
+        update_until(&mut app, |asset_server| {
+            asset_server.get_handle_path(&b) == Some(PathBuf::from("texts/c.txt"))
+        });
+        let asset_server = app.resource::<AssetServer>();
+        assert_eq!(asset_server.get_load_state(&b), Some(LoadState::Loaded(0)));
+        assert!(asset_server.get_handle::<Text, _>("texts/b.txt").is_none());
+        assert_eq!(
+            asset_server.get_handle::<Text, _>("texts/c.txt"),
+            Some(b.clone())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "filesystem_watcher")]
+    fn hot_reload_memory_asset_folder() {
+        let asset_io = MemoryAssetIo::default();
+        hot_reload_asset_folder(
+            asset_io.clone(),
+            |path, text| asset_io.insert(path, text.as_bytes().to_vec()),
+            |from, to| asset_io.rename(from, to),
+        );
+    }
+
+    // depends on how fast the file system watcher reports changes, so it's only run with `cargo test -- --ignored`
+    #[test]
+    #[ignore]
+    #[cfg(feature = "filesystem_watcher")]
+    fn hot_reload_file_asset_folder() {
+        // the directory is removed when the test ends, even if it fails
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("texts")).unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        hot_reload_asset_folder(
+            FileAssetIo::new(&root_path),
+            |path, text| std::fs::write(root_path.join(path), text).unwrap(),
+            |from, to| std::fs::rename(root_path.join(from), root_path.join(to)).unwrap(),
+        );
+    }
+
+    #[test]
+    fn labeled_assets() {
+        assert_eq!(
+            split_label(Path::new("models/a.gltf#Mesh0/Primitive1")),
+            (Path::new("models/a.gltf"), Some("Mesh0/Primitive1"))
+        );
+        assert_eq!(split_label(Path::new("a.txt")), (Path::new("a.txt"), None));
+
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.txt", b"a\nlabel x: x text\nlabel y: y text".to_vec());
+        let mut app = text_app(asset_io);
+
+        let x = app
+            .resource::<AssetServer>()
+            .load::<Text, _>("a.txt#x")
+            .unwrap();
+        update_until(&mut app, |asset_server| {
+            asset_server.get_load_state(&x) == Some(LoadState::Loaded(0))
+        });
+        {
+            let asset_server = app.resource::<AssetServer>();
+            let a = asset_server.get_handle::<Text, _>("a.txt").unwrap();
+            assert_eq!(asset_server.get_load_state(&a), Some(LoadState::Loaded(0)));
+            assert_eq!(
+                asset_server.get_recursive_load_state(&x),
+                Some(LoadState::Loaded(0))
+            );
+
+            let texts = app.resource::<Assets<Text>>();
+            assert_eq!(texts.get(&x).unwrap().text, "x text");
+            assert_eq!(texts.get(&a).unwrap().text.lines().next(), Some("a"));
+        }
+
+        // labels that the loader doesn't add fail to load
+        let missing = app
+            .resource::<AssetServer>()
+            .load::<Text, _>("a.txt#missing")
+            .unwrap();
+        update_until(&mut app, |asset_server| {
+            !matches!(
+                asset_server.get_load_state(&missing),
+                Some(LoadState::Loading(_))
+            )
+        });
+        assert!(matches!(
+            app.resource::<AssetServer>().get_load_state(&missing),
+            Some(LoadState::Failed(_))
+        ));
+    }
 }
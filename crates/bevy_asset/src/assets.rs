@@ -1,90 +1,128 @@
 use crate::{
-    update_asset_storage_system, AssetChannel, AssetLoader, AssetServer, ChannelAssetHandler,
-    Handle, HandleId,
+    update_asset_storage_system, AssetChannel, AssetLoadFailure, AssetLoader, AssetProcessor,
+    AssetSaver, AssetServer, ChannelAssetHandler, Handle, HandleId, RefChange,
 };
 use bevy_app::{prelude::Events, AppBuilder};
-use bevy_ecs::{FromResources, IntoQuerySystem, ResMut, Resource};
+use bevy_ecs::{FromResources, IntoQuerySystem, Res, ResMut, Resource};
 use bevy_type_registry::RegisterType;
 use bevy_utils::HashMap;
+use crossbeam_channel::{Receiver, Sender};
+use std::sync::Arc;
 
-/// Events that happen on assets of type `T`
+/// Events that happen on assets of type `T`. The handles in these events are weak.
 pub enum AssetEvent<T: Resource> {
-    Created { handle: Handle<T> },
-    Modified { handle: Handle<T> },
-    Removed { handle: Handle<T> },
+    Created {
+        handle: Handle<T>,
+    },
+    Modified {
+        handle: Handle<T>,
+    },
+    Removed {
+        handle: Handle<T>,
+    },
+    /// The asset failed to load. If it was loaded before, the previously loaded asset is kept.
+    Failed {
+        handle: Handle<T>,
+        failure: Arc<AssetLoadFailure>,
+    },
 }
 
 /// Stores Assets of a given type and tracks changes to them.
+///
+/// Assets are removed once the last strong [Handle] to them is dropped, which sends an [AssetEvent::Removed] event.
 pub struct Assets<T: Resource> {
-    assets: HashMap<Handle<T>, T>,
+    assets: HashMap<HandleId, T>,
     events: Events<AssetEvent<T>>,
+    ref_counts: HashMap<HandleId, usize>,
+    pub(crate) ref_change_sender: Sender<RefChange>,
+    ref_change_receiver: Receiver<RefChange>,
 }
 
 impl<T: Resource> Default for Assets<T> {
     fn default() -> Self {
+        let (ref_change_sender, ref_change_receiver) = crossbeam_channel::unbounded();
         Assets {
             assets: HashMap::default(),
             events: Events::default(),
+            ref_counts: HashMap::default(),
+            ref_change_sender,
+            ref_change_receiver,
         }
     }
 }
 
 impl<T: Resource> Assets<T> {
+    /// Adds an asset and returns a strong handle to it
     pub fn add(&mut self, asset: T) -> Handle<T> {
-        let handle = Handle::new();
-        self.assets.insert(handle, asset);
-        self.events.send(AssetEvent::Created { handle });
-        handle
+        let id = HandleId::new();
+        self.assets.insert(id, asset);
+        self.events.send(AssetEvent::Created {
+            handle: Handle::weak(id),
+        });
+        self.get_handle(id)
     }
 
-    pub fn set(&mut self, handle: Handle<T>, asset: T) {
-        let exists = self.assets.contains_key(&handle);
-        self.assets.insert(handle, asset);
+    pub fn set<H: Into<HandleId>>(&mut self, handle: H, asset: T) {
+        let id = handle.into();
+        let exists = self.assets.contains_key(&id);
+        self.assets.insert(id, asset);
 
         if exists {
-            self.events.send(AssetEvent::Modified { handle });
+            self.events.send(AssetEvent::Modified {
+                handle: Handle::weak(id),
+            });
         } else {
-            self.events.send(AssetEvent::Created { handle });
+            self.events.send(AssetEvent::Created {
+                handle: Handle::weak(id),
+            });
         }
     }
 
     pub fn add_default(&mut self, asset: T) -> Handle<T> {
         let handle = Handle::default();
-        let exists = self.assets.contains_key(&handle);
-        self.assets.insert(handle, asset);
-        if exists {
-            self.events.send(AssetEvent::Modified { handle });
-        } else {
-            self.events.send(AssetEvent::Created { handle });
-        }
+        self.set(&handle, asset);
         handle
     }
 
+    /// Returns a strong handle for the given id, which keeps the asset alive until it is dropped
+    pub fn get_handle<H: Into<HandleId>>(&self, handle: H) -> Handle<T> {
+        Handle::strong(handle.into(), self.ref_change_sender.clone())
+    }
+
     pub fn get_with_id(&self, id: HandleId) -> Option<&T> {
-        self.get(&Handle::from_id(id))
+        self.assets.get(&id)
     }
 
     pub fn get_with_id_mut(&mut self, id: HandleId) -> Option<&mut T> {
-        self.get_mut(&Handle::from_id(id))
+        self.events.send(AssetEvent::Modified {
+            handle: Handle::weak(id),
+        });
+        self.assets.get_mut(&id)
     }
 
     pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
-        self.assets.get(&handle)
+        self.get_with_id(handle.id)
     }
 
     pub fn get_mut(&mut self, handle: &Handle<T>) -> Option<&mut T> {
-        self.events.send(AssetEvent::Modified { handle: *handle });
-        self.assets.get_mut(&handle)
+        self.get_with_id_mut(handle.id)
     }
 
-    pub fn get_or_insert_with(
+    pub fn contains(&self, handle: &Handle<T>) -> bool {
+        self.assets.contains_key(&handle.id)
+    }
+
+    pub fn get_or_insert_with<H: Into<HandleId>>(
         &mut self,
-        handle: Handle<T>,
+        handle: H,
         insert_fn: impl FnOnce() -> T,
     ) -> &mut T {
+        let id = handle.into();
         let mut event = None;
-        let borrowed = self.assets.entry(handle).or_insert_with(|| {
-            event = Some(AssetEvent::Created { handle });
+        let borrowed = self.assets.entry(id).or_insert_with(|| {
+            event = Some(AssetEvent::Created {
+                handle: Handle::weak(id),
+            });
             insert_fn()
         });
 
@@ -94,12 +132,81 @@ impl<T: Resource> Assets<T> {
         borrowed
     }
 
+    /// Iterates over all assets. The returned handles are weak.
     pub fn iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
-        self.assets.iter().map(|(k, v)| (*k, v))
+        self.assets.iter().map(|(k, v)| (Handle::weak(*k), v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
     }
 
+    /// Removes an asset, regardless of how many strong handles to it still exist
     pub fn remove(&mut self, handle: &Handle<T>) -> Option<T> {
-        self.assets.remove(&handle)
+        self.remove_with_id(handle.id)
+    }
+
+    fn remove_with_id(&mut self, id: HandleId) -> Option<T> {
+        let asset = self.assets.remove(&id);
+        if asset.is_some() {
+            self.events.send(AssetEvent::Removed {
+                handle: Handle::weak(id),
+            });
+        }
+        asset
+    }
+
+    pub(crate) fn send_failed(&mut self, id: HandleId, failure: Arc<AssetLoadFailure>) {
+        self.events.send(AssetEvent::Failed {
+            handle: Handle::weak(id),
+            failure,
+        });
+    }
+
+    /// Applies the reference count changes of strong handles and removes assets that are no longer referenced.
+    /// Returns the ids of the removed assets.
+    pub(crate) fn free_unused_assets(&mut self) -> Vec<HandleId> {
+        let mut potentially_unused = Vec::new();
+        for ref_change in self.ref_change_receiver.try_iter() {
+            match ref_change {
+                RefChange::Increment(id) => *self.ref_counts.entry(id).or_insert(0) += 1,
+                RefChange::Decrement(id) => {
+                    let ref_count = self
+                        .ref_counts
+                        .get_mut(&id)
+                        .expect("strong handle was dropped more often than it was created");
+                    *ref_count -= 1;
+                    if *ref_count == 0 {
+                        potentially_unused.push(id);
+                    }
+                }
+            }
+        }
+
+        // handles can be dropped and recreated between two updates, so only the final count matters
+        let mut unused = Vec::new();
+        for id in potentially_unused {
+            if self.ref_counts.get(&id) == Some(&0) {
+                self.ref_counts.remove(&id);
+                self.remove_with_id(id);
+                unused.push(id);
+            }
+        }
+
+        unused
+    }
+
+    pub fn free_unused_assets_system(
+        mut assets: ResMut<Assets<T>>,
+        asset_server: Res<AssetServer>,
+    ) {
+        for id in assets.free_unused_assets() {
+            asset_server.free_asset(id);
+        }
     }
 
     pub fn asset_event_system(
@@ -119,6 +226,13 @@ pub trait AddAsset {
     where
         TLoader: AssetLoader<TAsset> + FromResources,
         TAsset: Send + Sync + 'static;
+    fn add_asset_saver<TAsset, TSaver>(&mut self) -> &mut Self
+    where
+        TSaver: AssetSaver<TAsset> + FromResources,
+        TAsset: Send + Sync + 'static;
+    fn add_asset_processor<TProcessor>(&mut self) -> &mut Self
+    where
+        TProcessor: AssetProcessor + FromResources;
 }
 
 impl AddAsset for AppBuilder {
@@ -126,8 +240,20 @@ impl AddAsset for AppBuilder {
     where
         T: Resource,
     {
-        self.init_resource::<Assets<T>>()
-            .register_component::<Handle<T>>()
+        self.init_resource::<Assets<T>>();
+        add_asset_channel::<T>(self);
+        {
+            let assets = self.resources().get::<Assets<T>>().unwrap();
+            if let Some(mut asset_server) = self.resources().get_mut::<AssetServer>() {
+                asset_server.register_asset_type(&assets);
+            }
+        }
+
+        self.register_component::<Handle<T>>()
+            .add_system_to_stage(
+                super::stage::ASSET_EVENTS,
+                Assets::<T>::free_unused_assets_system.system(),
+            )
             .add_system_to_stage(
                 super::stage::ASSET_EVENTS,
                 Assets::<T>::asset_event_system.system(),
@@ -140,14 +266,8 @@ impl AddAsset for AppBuilder {
         TLoader: AssetLoader<TAsset> + FromResources,
         TAsset: Send + Sync + 'static,
     {
+        add_asset_channel::<TAsset>(self);
         {
-            if !self.resources().contains::<AssetChannel<TAsset>>() {
-                self.resources_mut().insert(AssetChannel::<TAsset>::new());
-                self.add_system_to_stage(
-                    crate::stage::LOAD_ASSETS,
-                    update_asset_storage_system::<TAsset>.system(),
-                );
-            }
             let asset_channel = self
                 .resources()
                 .get::<AssetChannel<TAsset>>()
@@ -165,4 +285,89 @@ impl AddAsset for AppBuilder {
         }
         self
     }
+
+    fn add_asset_saver<TAsset, TSaver>(&mut self) -> &mut Self
+    where
+        TSaver: AssetSaver<TAsset> + FromResources,
+        TAsset: Send + Sync + 'static,
+    {
+        let saver = TSaver::from_resources(self.resources());
+        self.resources()
+            .get_mut::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_saver(saver);
+        self
+    }
+
+    fn add_asset_processor<TProcessor>(&mut self) -> &mut Self
+    where
+        TProcessor: AssetProcessor + FromResources,
+    {
+        let processor = TProcessor::from_resources(self.resources());
+        self.resources()
+            .get_mut::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_processor(processor);
+        self
+    }
+}
+
+/// Adds the [AssetChannel] that loaded assets of type `T` are sent to, unless it already exists
+fn add_asset_channel<T: Resource>(app: &mut AppBuilder) {
+    if app.resources().contains::<AssetChannel<T>>() {
+        return;
+    }
+
+    let asset_channel = AssetChannel::<T>::new();
+    if let Some(mut asset_server) = app.resources().get_mut::<AssetServer>() {
+        asset_server.register_asset_channel(&asset_channel);
+    }
+    app.resources_mut().insert(asset_channel);
+    app.add_system_to_stage(
+        crate::stage::LOAD_ASSETS,
+        update_asset_storage_system::<T>.system(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_unused_assets() {
+        let mut assets = Assets::<u32>::default();
+        let handle = assets.add(1);
+        let weak_handle = handle.clone_weak();
+        let cloned_handle = handle.clone();
+        assert!(handle.is_strong() && cloned_handle.is_strong() && weak_handle.is_weak());
+
+        drop(handle);
+        assert!(assets.free_unused_assets().is_empty());
+        assert_eq!(assets.get(&weak_handle), Some(&1));
+
+        drop(cloned_handle);
+        assert_eq!(assets.free_unused_assets(), vec![weak_handle.id]);
+        assert!(!assets.contains(&weak_handle));
+
+        let events = assets.events.drain().collect::<Vec<_>>();
+        assert!(matches!(events[0], AssetEvent::Created { .. }));
+        assert!(matches!(&events[1], AssetEvent::Removed { handle } if *handle == weak_handle));
+    }
+
+    #[test]
+    fn weak_assets_are_kept() {
+        let mut assets = Assets::<u32>::default();
+        let handle = Handle::<u32>::new();
+        assets.set(&handle, 1);
+
+        // dropping a strong handle that was created in the meantime frees the asset
+        let strong_handle = assets.get_handle(&handle);
+        assert!(assets.free_unused_assets().is_empty());
+        drop(strong_handle);
+        assert_eq!(assets.free_unused_assets(), vec![handle.id]);
+
+        assets.set(&handle, 2);
+        assert!(assets.free_unused_assets().is_empty());
+        assert_eq!(assets.get(&handle), Some(&2));
+    }
 }
@@ -3,15 +3,25 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+use crate::Assets;
+use bevy_ecs::Resource;
 use bevy_property::{Properties, Property};
+use crossbeam_channel::Sender;
 use serde::{Deserialize, Serialize};
-use std::{any::TypeId, marker::PhantomData};
+use std::{
+    any::TypeId,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
 use uuid::Uuid;
 
 /// The ID of the "default" asset
 pub(crate) const DEFAULT_HANDLE_ID: HandleId =
     HandleId(Uuid::from_u128(240940089166493627844978703213080810552));
 
+/// The namespace of the ids that are derived from asset paths
+const ASSET_PATH_NAMESPACE: Uuid = Uuid::from_u128(65321574055158848723812875481330331709);
+
 /// A unique id that corresponds to a specific asset in the [Assets](crate::Assets) collection.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, Property)]
 pub struct HandleId(pub Uuid);
@@ -21,11 +31,49 @@ impl HandleId {
     pub fn new() -> HandleId {
         HandleId(Uuid::new_v4())
     }
+
+    /// Returns the id of the asset at the given path. The id only depends on the path, so it is the same in every run
+    /// of the app and on every platform.
+    pub fn from_path(path: &Path) -> HandleId {
+        let path = path.to_string_lossy().replace('\\', "/");
+        HandleId(Uuid::new_v5(&ASSET_PATH_NAMESPACE, path.as_bytes()))
+    }
+
+    /// Returns the id of the asset with the given label in the file with the id `source_id`
+    pub fn from_label(source_id: HandleId, label: &str) -> HandleId {
+        HandleId(Uuid::new_v5(&source_id.0, label.as_bytes()))
+    }
+}
+
+/// The path of an asset, relative to the asset root. Saved scenes refer to assets that were loaded from a file by
+/// their path, which is loaded using the [AssetServer](crate::AssetServer) when the scene is spawned.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Property)]
+pub struct AssetPath(pub PathBuf);
+
+/// A change to the reference count of an asset, sent whenever a strong [Handle] is created or dropped
+#[derive(Debug)]
+pub(crate) enum RefChange {
+    Increment(HandleId),
+    Decrement(HandleId),
+}
+
+/// Whether a [Handle] keeps its asset alive
+#[derive(Debug)]
+enum HandleType {
+    Weak,
+    Strong(Sender<RefChange>),
 }
 
 /// A handle into a specific Asset of type `T`
 ///
 /// Handles contain a unique id that corresponds to a specific asset in the [Assets](crate::Assets) collection.
+///
+/// Handles are either strong or weak. Strong handles are reference counted: an asset is removed from its
+/// [Assets](crate::Assets) collection once the last strong handle to it is dropped. Weak handles do not keep an
+/// asset alive. Strong handles are returned by [Assets::add](crate::Assets::add),
+/// [Assets::get_handle](crate::Assets::get_handle) and [AssetServer::load](crate::AssetServer::load). All other
+/// handles, including handles created from an id, are weak. Assets that never had a strong handle are never removed
+/// automatically.
 #[derive(Properties)]
 pub struct Handle<T>
 where
@@ -33,44 +81,53 @@ where
 {
     pub id: HandleId,
     #[property(ignore)]
+    handle_type: HandleType,
+    #[property(ignore)]
     marker: PhantomData<T>,
 }
 
 impl<T> Handle<T> {
+    /// Creates a weak handle with a new random id
     pub fn new() -> Self {
+        Handle::weak(HandleId::new())
+    }
+
+    pub(crate) fn strong(id: HandleId, ref_change_sender: Sender<RefChange>) -> Self {
+        // the receiver is only gone once the Assets collection was dropped, in which case there is nothing to count
+        let _ = ref_change_sender.send(RefChange::Increment(id));
+        Handle {
+            id,
+            handle_type: HandleType::Strong(ref_change_sender),
+            marker: PhantomData,
+        }
+    }
+
+    pub const fn weak(id: HandleId) -> Self {
         Handle {
-            id: HandleId::new(),
+            id,
+            handle_type: HandleType::Weak,
             marker: PhantomData,
         }
     }
 
     /// Gets a handle for the given type that has this handle's id. This is useful when an
     /// asset is derived from another asset. In this case, a common handle can be used to
-    /// correlate them.
+    /// correlate them. The returned handle is always weak.
     /// NOTE: This pattern might eventually be replaced by a more formal asset dependency system.
     pub fn as_handle<U>(&self) -> Handle<U> {
-        Handle::from_id(self.id)
+        Handle::weak(self.id)
     }
 
     pub const fn from_id(id: HandleId) -> Self {
-        Handle {
-            id,
-            marker: PhantomData,
-        }
+        Handle::weak(id)
     }
 
     pub const fn from_u128(value: u128) -> Self {
-        Handle {
-            id: HandleId(Uuid::from_u128(value)),
-            marker: PhantomData,
-        }
+        Handle::weak(HandleId(Uuid::from_u128(value)))
     }
 
     pub const fn from_bytes(bytes: [u8; 16]) -> Self {
-        Handle {
-            id: HandleId(Uuid::from_bytes(bytes)),
-            marker: PhantomData,
-        }
+        Handle::weak(HandleId(Uuid::from_bytes(bytes)))
     }
 
     pub fn from_untyped(untyped_handle: HandleUntyped) -> Option<Handle<T>>
@@ -78,11 +135,42 @@ impl<T> Handle<T> {
         T: 'static,
     {
         if TypeId::of::<T>() == untyped_handle.type_id {
-            Some(Handle::from_id(untyped_handle.id))
+            Some(Handle::weak(untyped_handle.id))
         } else {
             None
         }
     }
+
+    pub fn is_weak(&self) -> bool {
+        matches!(self.handle_type, HandleType::Weak)
+    }
+
+    pub fn is_strong(&self) -> bool {
+        matches!(self.handle_type, HandleType::Strong(_))
+    }
+
+    /// Returns a weak handle with the same id as this handle. Unlike [Clone::clone], this never keeps the asset alive.
+    pub fn clone_weak(&self) -> Self {
+        Handle::weak(self.id)
+    }
+
+    /// Turns this handle into a strong handle that keeps its asset in `assets` alive
+    pub fn make_strong(&mut self, assets: &Assets<T>)
+    where
+        T: Resource,
+    {
+        if self.is_weak() {
+            *self = assets.get_handle(self.id);
+        }
+    }
+}
+
+impl<T> Drop for Handle<T> {
+    fn drop(&mut self) {
+        if let HandleType::Strong(ref_change_sender) = &self.handle_type {
+            let _ = ref_change_sender.send(RefChange::Decrement(self.id));
+        }
+    }
 }
 
 impl<T> From<HandleId> for Handle<T> {
@@ -109,16 +197,25 @@ where
 {
     fn from(handle: HandleUntyped) -> Self {
         if TypeId::of::<T>() == handle.type_id {
-            Handle {
-                id: handle.id,
-                marker: PhantomData::default(),
-            }
+            Handle::weak(handle.id)
         } else {
             panic!("attempted to convert untyped handle to incorrect typed handle")
         }
     }
 }
 
+impl<T> From<Handle<T>> for HandleId {
+    fn from(handle: Handle<T>) -> Self {
+        handle.id
+    }
+}
+
+impl<T> From<&Handle<T>> for HandleId {
+    fn from(handle: &Handle<T>) -> Self {
+        handle.id
+    }
+}
+
 impl<T> Hash for Handle<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.id.hash(state);
@@ -142,24 +239,23 @@ impl<T> Debug for Handle<T> {
 
 impl<T> Default for Handle<T> {
     fn default() -> Self {
-        Handle {
-            id: DEFAULT_HANDLE_ID,
-            marker: PhantomData,
-        }
+        Handle::weak(DEFAULT_HANDLE_ID)
     }
 }
 
 impl<T> Clone for Handle<T> {
+    /// Clones the handle. Cloning a strong handle increments the reference count of its asset.
     fn clone(&self) -> Self {
-        Handle {
-            id: self.id,
-            marker: PhantomData,
+        match &self.handle_type {
+            HandleType::Weak => Handle::weak(self.id),
+            HandleType::Strong(ref_change_sender) => {
+                Handle::strong(self.id, ref_change_sender.clone())
+            }
         }
     }
 }
-impl<T> Copy for Handle<T> {}
 
-// SAFE: T is phantom data and Handle::id is an integer
+// SAFE: T is phantom data, Handle::id is an integer and the ref change Sender is Send + Sync
 unsafe impl<T> Send for Handle<T> {}
 unsafe impl<T> Sync for Handle<T> {}
 
@@ -183,6 +279,15 @@ where
     T: 'static,
 {
     fn from(handle: Handle<T>) -> Self {
+        HandleUntyped::from(&handle)
+    }
+}
+
+impl<T> From<&Handle<T>> for HandleUntyped
+where
+    T: 'static,
+{
+    fn from(handle: &Handle<T>) -> Self {
         HandleUntyped {
             id: handle.id,
             type_id: TypeId::of::<T>(),
@@ -0,0 +1,277 @@
+#[cfg(feature = "filesystem_watcher")]
+use super::filesystem_watcher::FilesystemWatcher;
+use super::{AssetChange, AssetIo, AssetIoError};
+#[cfg(feature = "filesystem_watcher")]
+use bevy_utils::HashSet;
+#[cfg(feature = "filesystem_watcher")]
+use parking_lot::RwLock;
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Reads and writes assets on the filesystem, relative to a root directory
+///
+/// Changes are detected by watching the whole root directory, so files that are replaced by renaming a temporary
+/// file, as many editors do when saving, are still detected. Paths outside of the root directory, which are loaded
+/// using absolute paths, are watched individually.
+pub struct FileAssetIo {
+    root_path: PathBuf,
+    #[cfg(feature = "filesystem_watcher")]
+    filesystem_watcher: RwLock<Option<FilesystemWatcher>>,
+    #[cfg(feature = "filesystem_watcher")]
+    watched_paths: RwLock<HashSet<PathBuf>>,
+}
+
+impl FileAssetIo {
+    pub fn new<P: AsRef<Path>>(root_path: P) -> Self {
+        FileAssetIo {
+            root_path: root_path.as_ref().to_owned(),
+            #[cfg(feature = "filesystem_watcher")]
+            filesystem_watcher: Default::default(),
+            #[cfg(feature = "filesystem_watcher")]
+            watched_paths: Default::default(),
+        }
+    }
+
+    /// The directory of the current cargo package or, if the app wasn't started by cargo, the directory of the
+    /// executable
+    pub fn get_root_path() -> Result<PathBuf, io::Error> {
+        if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+            Ok(PathBuf::from(manifest_dir))
+        } else {
+            let exe_path = env::current_exe()?;
+            exe_path
+                .parent()
+                .map(|exe_parent_path| exe_parent_path.to_owned())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "Executable has no parent directory",
+                    )
+                })
+        }
+    }
+
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+
+    /// Returns true if `path` isn't covered by the watch of the root path
+    #[cfg(feature = "filesystem_watcher")]
+    fn is_outside_root(&self, path: &Path) -> bool {
+        !self.root_path.join(path).starts_with(&self.root_path)
+    }
+
+    /// Makes a path reported by the [FilesystemWatcher] relative to the root path. Paths outside of the root path
+    /// were loaded using absolute paths.
+    #[cfg(feature = "filesystem_watcher")]
+    fn get_relative_path(&self, path: PathBuf) -> PathBuf {
+        match path.strip_prefix(&self.root_path) {
+            Ok(relative_path) => relative_path.to_owned(),
+            Err(_) => path,
+        }
+    }
+}
+
+impl Default for FileAssetIo {
+    fn default() -> Self {
+        FileAssetIo::new(FileAssetIo::get_root_path().expect("Invalid root path"))
+    }
+}
+
+impl AssetIo for FileAssetIo {
+    fn load_path(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let full_path = self.root_path.join(path);
+        match fs::File::open(&full_path) {
+            Ok(mut file) => {
+                let mut bytes = Vec::new();
+                file.read_to_end(&mut bytes)?;
+                Ok(bytes)
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Err(AssetIoError::NotFound(full_path))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn save_path(&self, path: &Path, bytes: &[u8]) -> Result<(), AssetIoError> {
+        let full_path = self.root_path.join(path);
+        if let Some(parent_path) = full_path.parent() {
+            fs::create_dir_all(parent_path)?;
+        }
+
+        fs::write(full_path, bytes)?;
+        Ok(())
+    }
+
+    fn read_directory(&self, path: &Path) -> Result<Vec<PathBuf>, AssetIoError> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(self.root_path.join(path))? {
+            paths.push(path.join(entry?.file_name()));
+        }
+
+        Ok(paths)
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.root_path.join(path).is_dir()
+    }
+
+    #[cfg(feature = "filesystem_watcher")]
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        if self.is_outside_root(path) {
+            // paths outside of the root are watched by watch_for_changes if it wasn't called yet
+            if let Some(filesystem_watcher) = self.filesystem_watcher.write().as_mut() {
+                filesystem_watcher
+                    .watch(path)
+                    .map_err(|_error| AssetIoError::PathWatchError(path.to_owned()))?;
+            }
+        }
+        self.watched_paths.write().insert(path.to_owned());
+        Ok(())
+    }
+
+    #[cfg(not(feature = "filesystem_watcher"))]
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "filesystem_watcher")]
+    fn unwatch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        if self.watched_paths.write().remove(path) && self.is_outside_root(path) {
+            if let Some(filesystem_watcher) = self.filesystem_watcher.write().as_mut() {
+                // the path may already be gone, which also removes its watch
+                let _ = filesystem_watcher.unwatch(path);
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "filesystem_watcher"))]
+    fn unwatch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "filesystem_watcher")]
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        let mut filesystem_watcher = self.filesystem_watcher.write();
+        if filesystem_watcher.is_none() {
+            let mut watcher = FilesystemWatcher::default();
+            watcher
+                .watch(&self.root_path)
+                .map_err(|_error| AssetIoError::PathWatchError(self.root_path.clone()))?;
+            for path in self.watched_paths.read().iter() {
+                if self.is_outside_root(path) {
+                    watcher
+                        .watch(path)
+                        .map_err(|_error| AssetIoError::PathWatchError(path.clone()))?;
+                }
+            }
+            *filesystem_watcher = Some(watcher);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "filesystem_watcher"))]
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "filesystem_watcher")]
+    fn get_changes(&self) -> Vec<AssetChange> {
+        use crossbeam_channel::TryRecvError;
+        use notify::event::{EventKind, ModifyKind, RenameMode};
+
+        let mut changes = Vec::new();
+        let filesystem_watcher = self.filesystem_watcher.read();
+        let filesystem_watcher = match filesystem_watcher.as_ref() {
+            Some(filesystem_watcher) => filesystem_watcher,
+            None => return changes,
+        };
+
+        let watched_paths = self.watched_paths.read();
+        loop {
+            let event = match filesystem_watcher.receiver.try_recv() {
+                Ok(Ok(event)) => event,
+                Ok(Err(error)) => {
+                    log::warn!("Failed to watch assets for changes: {:?}", error);
+                    continue;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected"),
+            };
+            let mut paths = event
+                .paths
+                .into_iter()
+                .map(|path| self.get_relative_path(path));
+            let change = match event.kind {
+                EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                    paths.next().map(AssetChange::Created)
+                }
+                EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                    paths.next().map(AssetChange::Removed)
+                }
+                EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                    match (paths.next(), paths.next()) {
+                        (Some(from), Some(to)) => Some(AssetChange::Renamed { from, to }),
+                        _ => None,
+                    }
+                }
+                // some platforms don't report whether a renamed path is the old or the new path
+                EventKind::Modify(ModifyKind::Name(_)) => paths.next().map(|path| {
+                    if self.root_path.join(&path).exists() {
+                        AssetChange::Created(path)
+                    } else {
+                        AssetChange::Removed(path)
+                    }
+                }),
+                EventKind::Modify(_) => paths.next().map(AssetChange::Modified),
+                _ => None,
+            };
+            if let Some(change) = change.filter(|change| change.is_watched(&watched_paths)) {
+                changes.push(change);
+            }
+        }
+
+        changes
+    }
+
+    #[cfg(not(feature = "filesystem_watcher"))]
+    fn get_changes(&self) -> Vec<AssetChange> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "filesystem_watcher")]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn watch_absolute_path_outside_root() {
+        let temp_dir = env::temp_dir().join(format!("bevy_file_asset_io_{}", std::process::id()));
+        let root_path = temp_dir.join("assets");
+        let outside_path = temp_dir.join("outside.txt");
+        fs::create_dir_all(&root_path).unwrap();
+        fs::write(&outside_path, b"a").unwrap();
+
+        let asset_io = FileAssetIo::new(&root_path);
+        asset_io.watch_path_for_changes(&outside_path).unwrap();
+        asset_io.watch_for_changes().unwrap();
+        fs::write(&outside_path, b"b").unwrap();
+
+        let start = Instant::now();
+        let mut changes = Vec::new();
+        while changes.is_empty() && start.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(10));
+            changes = asset_io.get_changes();
+        }
+        fs::remove_dir_all(&temp_dir).unwrap();
+        assert!(changes.contains(&AssetChange::Modified(outside_path)));
+    }
+}
@@ -2,7 +2,8 @@ use crossbeam_channel::Receiver;
 use notify::{Event, RecommendedWatcher, RecursiveMode, Result, Watcher};
 use std::path::Path;
 
-/// Watches for changes to assets on the filesystem. This is used by the `AssetServer` to reload them
+/// Watches for changes to assets on the filesystem. This is used by the `AssetServer` to reload them. Directories
+/// are watched recursively.
 pub struct FilesystemWatcher {
     pub watcher: RecommendedWatcher,
     pub receiver: Receiver<Result<Event>>,
@@ -23,4 +24,8 @@ impl FilesystemWatcher {
     pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
         self.watcher.watch(path, RecursiveMode::Recursive)
     }
+
+    pub fn unwatch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.watcher.unwatch(path)
+    }
 }
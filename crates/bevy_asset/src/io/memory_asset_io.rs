@@ -0,0 +1,205 @@
+use super::{AssetChange, AssetIo, AssetIoError};
+use bevy_utils::{HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Keeps assets in memory instead of reading them from a filesystem. This is useful in tests and for assets that are
+/// embedded in the executable.
+///
+/// Changes made with [MemoryAssetIo::insert], [MemoryAssetIo::remove] and [MemoryAssetIo::rename] are reported to the
+/// [AssetServer](crate::AssetServer) once it watches for changes. Clones share the same assets, so a clone can be
+/// used to modify the assets of an AssetServer.
+#[derive(Clone, Default)]
+pub struct MemoryAssetIo {
+    state: Arc<MemoryAssetIoState>,
+}
+
+#[derive(Default)]
+struct MemoryAssetIoState {
+    files: RwLock<HashMap<PathBuf, Vec<u8>>>,
+    watching: AtomicBool,
+    watched_paths: RwLock<HashSet<PathBuf>>,
+    changes: RwLock<Vec<AssetChange>>,
+}
+
+impl MemoryAssetIo {
+    /// Adds or replaces the asset at the given path
+    pub fn insert<P: AsRef<Path>>(&self, path: P, bytes: Vec<u8>) {
+        let path = path.as_ref();
+        let replaced = self
+            .state
+            .files
+            .write()
+            .insert(path.to_owned(), bytes)
+            .is_some();
+        if replaced {
+            self.changed(AssetChange::Modified(path.to_owned()));
+        } else {
+            self.changed(AssetChange::Created(path.to_owned()));
+        }
+    }
+
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> Option<Vec<u8>> {
+        let path = path.as_ref();
+        let bytes = self.state.files.write().remove(path);
+        if bytes.is_some() {
+            self.changed(AssetChange::Removed(path.to_owned()));
+        }
+        bytes
+    }
+
+    /// Moves the asset at `from` to `to`, replacing the asset at `to` if there is one. Nothing happens if there is no
+    /// asset at `from`.
+    pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(&self, from: P, to: Q) {
+        let (from, to) = (from.as_ref(), to.as_ref());
+        let mut files = self.state.files.write();
+        if let Some(bytes) = files.remove(from) {
+            files.insert(to.to_owned(), bytes);
+            drop(files);
+            self.changed(AssetChange::Renamed {
+                from: from.to_owned(),
+                to: to.to_owned(),
+            });
+        }
+    }
+
+    fn changed(&self, change: AssetChange) {
+        if self.state.watching.load(Ordering::Relaxed)
+            && change.is_watched(&self.state.watched_paths.read())
+        {
+            self.state.changes.write().push(change);
+        }
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        self.state
+            .files
+            .read()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+    }
+
+    fn save_path(&self, path: &Path, bytes: &[u8]) -> Result<(), AssetIoError> {
+        self.insert(path, bytes.to_vec());
+        Ok(())
+    }
+
+    fn read_directory(&self, path: &Path) -> Result<Vec<PathBuf>, AssetIoError> {
+        if !self.is_directory(path) {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+
+        let mut paths = Vec::new();
+        for file_path in self.state.files.read().keys() {
+            // directories are implied by the files inside of them
+            if let Some(child) = file_path
+                .strip_prefix(path)
+                .ok()
+                .and_then(|relative_path| relative_path.iter().next())
+            {
+                let child_path = path.join(child);
+                if !paths.contains(&child_path) {
+                    paths.push(child_path);
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.state
+            .files
+            .read()
+            .keys()
+            .any(|file_path| file_path != path && file_path.starts_with(path))
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        self.state.watched_paths.write().insert(path.to_owned());
+        Ok(())
+    }
+
+    fn unwatch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        self.state.watched_paths.write().remove(path);
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.state.watching.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn get_changes(&self) -> Vec<AssetChange> {
+        std::mem::take(&mut *self.state.changes.write())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_directory() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("assets/a.txt", b"a".to_vec());
+        asset_io.insert("assets/models/b.txt", b"b".to_vec());
+        asset_io.insert("assets/models/c.txt", b"c".to_vec());
+
+        assert!(asset_io.is_directory(Path::new("assets/models")));
+        assert!(!asset_io.is_directory(Path::new("assets/a.txt")));
+        assert_eq!(
+            asset_io.load_path(Path::new("assets/a.txt")).unwrap(),
+            b"a".to_vec()
+        );
+
+        let mut paths = asset_io.read_directory(Path::new("assets")).unwrap();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("assets/a.txt"),
+                PathBuf::from("assets/models")
+            ]
+        );
+    }
+
+    #[test]
+    fn changes() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.txt", b"a".to_vec());
+        asset_io.watch_path_for_changes(Path::new("a.txt")).unwrap();
+        asset_io.watch_path_for_changes(Path::new("texts")).unwrap();
+        asset_io.insert("a.txt", b"b".to_vec());
+        assert!(asset_io.get_changes().is_empty());
+
+        asset_io.watch_for_changes().unwrap();
+        asset_io.insert("a.txt", b"c".to_vec());
+        asset_io.insert("b.txt", b"b".to_vec());
+        asset_io.insert("texts/c.txt", b"c".to_vec());
+        asset_io.rename("a.txt", "d.txt");
+        asset_io.remove("texts/c.txt");
+        assert_eq!(
+            asset_io.get_changes(),
+            vec![
+                AssetChange::Modified(PathBuf::from("a.txt")),
+                AssetChange::Created(PathBuf::from("texts/c.txt")),
+                AssetChange::Renamed {
+                    from: PathBuf::from("a.txt"),
+                    to: PathBuf::from("d.txt")
+                },
+                AssetChange::Removed(PathBuf::from("texts/c.txt")),
+            ]
+        );
+        assert!(asset_io.get_changes().is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+mod file_asset_io;
+#[cfg(feature = "filesystem_watcher")]
+mod filesystem_watcher;
+mod memory_asset_io;
+
+pub use file_asset_io::*;
+pub use memory_asset_io::*;
+
+use bevy_utils::HashSet;
+#[cfg(feature = "filesystem_watcher")]
+use std::time::{Duration, Instant};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Errors that occur while reading assets with an [AssetIo] implementation
+#[derive(Error, Debug)]
+pub enum AssetIoError {
+    #[error("Path not found: {0}")]
+    NotFound(PathBuf),
+    #[error("Encountered an io error while reading an asset.")]
+    Io(#[from] io::Error),
+    #[error("Failed to watch path: {0}")]
+    PathWatchError(PathBuf),
+}
+
+/// Reads and writes assets and watches them for changes on behalf of an [AssetServer](crate::AssetServer).
+///
+/// Paths are relative to the root of the asset source, which is defined by the implementation.
+/// [FileAssetIo] is used by default. [MemoryAssetIo] keeps assets in memory, which is useful in tests.
+pub trait AssetIo: Send + Sync + 'static {
+    /// Reads all bytes of the asset at the given path
+    fn load_path(&self, path: &Path) -> Result<Vec<u8>, AssetIoError>;
+    /// Writes the given bytes to the given path, replacing the file if it exists
+    fn save_path(&self, path: &Path, bytes: &[u8]) -> Result<(), AssetIoError>;
+    /// Returns the paths of the files and directories directly inside the given directory
+    fn read_directory(&self, path: &Path) -> Result<Vec<PathBuf>, AssetIoError>;
+    fn is_directory(&self, path: &Path) -> bool;
+    /// Starts watching the given file or directory for changes. Watching a directory reports changes to every file
+    /// inside of it, including files that are created later. Changes are only reported once
+    /// [AssetIo::watch_for_changes] was called.
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
+    fn unwatch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
+    /// Enables change detection. Until this is called, changes are not reported.
+    fn watch_for_changes(&self) -> Result<(), AssetIoError>;
+    /// Returns the changes to watched paths since the last call, in the order they happened
+    fn get_changes(&self) -> Vec<AssetChange>;
+}
+
+/// A change to a watched path, as reported by [AssetIo::get_changes]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AssetChange {
+    Created(PathBuf),
+    Modified(PathBuf),
+    Removed(PathBuf),
+    /// The file at `from` was moved to `to`. Changes are reported if either path is watched.
+    Renamed {
+        from: PathBuf,
+        to: PathBuf,
+    },
+}
+
+impl AssetChange {
+    /// Returns true if a path of this change or one of the directories that contain it is in `watched_paths`
+    pub(crate) fn is_watched(&self, watched_paths: &HashSet<PathBuf>) -> bool {
+        let is_path_watched = |path: &Path| {
+            path.ancestors()
+                .any(|ancestor| watched_paths.contains(ancestor))
+        };
+        match self {
+            AssetChange::Created(path)
+            | AssetChange::Modified(path)
+            | AssetChange::Removed(path) => is_path_watched(path),
+            AssetChange::Renamed { from, to } => is_path_watched(from) || is_path_watched(to),
+        }
+    }
+}
+
+/// Collects [AssetChange]s until no change happened for the debounce duration. Editors often write a file several
+/// times or write a temporary file and rename it when saving, which would otherwise reload the asset repeatedly.
+#[cfg(feature = "filesystem_watcher")]
+pub(crate) struct DebouncedChanges {
+    debounce: Duration,
+    changes: Vec<AssetChange>,
+    last_change: Option<Instant>,
+}
+
+#[cfg(feature = "filesystem_watcher")]
+impl DebouncedChanges {
+    pub fn new(debounce: Duration) -> Self {
+        DebouncedChanges {
+            debounce,
+            changes: Vec::new(),
+            last_change: None,
+        }
+    }
+
+    /// Adds the changes that happened at `now`. Repeated changes are only kept once, at the position of their last
+    /// occurrence.
+    pub fn add(&mut self, changes: Vec<AssetChange>, now: Instant) {
+        if changes.is_empty() {
+            return;
+        }
+
+        for change in changes {
+            self.changes
+                .retain(|pending_change| *pending_change != change);
+            self.changes.push(change);
+        }
+        self.last_change = Some(now);
+    }
+
+    /// Takes the collected changes if none were added during the debounce duration before `now`
+    pub fn take_settled(&mut self, now: Instant) -> Vec<AssetChange> {
+        match self.last_change {
+            Some(last_change) if now.duration_since(last_change) >= self.debounce => {
+                self.last_change = None;
+                std::mem::take(&mut self.changes)
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "filesystem_watcher")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debounced_changes() {
+        let start = Instant::now();
+        let mut debounced = DebouncedChanges::new(Duration::from_millis(100));
+        let a = || AssetChange::Modified(PathBuf::from("a.txt"));
+        let b = AssetChange::Renamed {
+            from: PathBuf::from("b.tmp"),
+            to: PathBuf::from("b.txt"),
+        };
+
+        debounced.add(vec![a(), a()], start);
+        debounced.add(vec![b.clone()], start + Duration::from_millis(50));
+        debounced.add(vec![a()], start + Duration::from_millis(100));
+        assert!(debounced
+            .take_settled(start + Duration::from_millis(150))
+            .is_empty());
+        assert_eq!(
+            debounced.take_settled(start + Duration::from_millis(200)),
+            vec![b, a()]
+        );
+        assert!(debounced
+            .take_settled(start + Duration::from_millis(400))
+            .is_empty());
+    }
+}
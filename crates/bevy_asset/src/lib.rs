@@ -1,16 +1,22 @@
 mod asset_server;
 mod assets;
-#[cfg(feature = "filesystem_watcher")]
-mod filesystem_watcher;
 mod handle;
+mod io;
 mod load_request;
 mod loader;
+mod meta;
+mod processor;
+mod saver;
 
 pub use asset_server::*;
 pub use assets::*;
 pub use handle::*;
+pub use io::*;
 pub use load_request::*;
 pub use loader::*;
+pub use meta::*;
+pub use processor::*;
+pub use saver::*;
 
 /// The names of asset stages in an App Schedule
 pub mod stage {
@@ -28,6 +34,9 @@ use bevy_type_registry::RegisterType;
 
 /// Adds support for Assets to an App. Assets are typed collections with change tracking, which are added as App Resources.
 /// Examples of assets: textures, sounds, 3d models, maps, scenes
+///
+/// Assets are read from the filesystem by default. To read them from somewhere else, insert an
+/// [AssetServer] created with [AssetServer::new] before adding this plugin.
 #[derive(Default)]
 pub struct AssetPlugin;
 
@@ -35,8 +44,24 @@ impl Plugin for AssetPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_stage_before(bevy_app::stage::PRE_UPDATE, stage::LOAD_ASSETS)
             .add_stage_after(bevy_app::stage::POST_UPDATE, stage::ASSET_EVENTS)
-            .init_resource::<AssetServer>()
-            .register_property::<HandleId>();
+            .register_property::<HandleId>()
+            .register_property::<AssetPath>();
+
+        if app.resources().get::<AssetServer>().is_none() {
+            app.init_resource::<AssetServer>();
+        }
+
+        {
+            let asset_server = app.resources().get::<AssetServer>().unwrap();
+            if let Err(err) = asset_server.load_import_cache() {
+                log::error!("Failed to load the import cache: {:?}", err);
+            }
+        }
+
+        app.add_system_to_stage(
+            stage::LOAD_ASSETS,
+            AssetServer::start_load_tasks_system.system(),
+        );
 
         #[cfg(feature = "filesystem_watcher")]
         app.add_system_to_stage(
@@ -1,9 +1,11 @@
-use crate::{AssetLoadError, AssetLoader, AssetResult, AssetVersion, Handle, HandleId};
+use crate::{
+    AssetLoadError, AssetLoadFailure, AssetLoader, AssetResult, AssetVersion, Handle, HandleId,
+    LoadContext,
+};
 use anyhow::Result;
+use bevy_utils::BoxedFuture;
 use crossbeam_channel::Sender;
-use fs::File;
-use io::Read;
-use std::{fs, io, path::PathBuf};
+use std::path::PathBuf;
 
 /// A request from an [AssetServer](crate::AssetServer) to load an asset.
 #[derive(Debug)]
@@ -12,12 +14,20 @@ pub struct LoadRequest {
     pub handle_id: HandleId,
     pub handler_index: usize,
     pub version: AssetVersion,
+    /// The processed file that is read in place of `path`, if the asset was processed
+    pub imported_path: Option<PathBuf>,
 }
 
-/// Handles load requests from an AssetServer
+/// Handles load requests from an AssetServer. Requests are handled on the [IOTaskPool](bevy_tasks::IOTaskPool).
 pub trait AssetLoadRequestHandler: Send + Sync + 'static {
-    fn handle_request(&self, load_request: &LoadRequest);
+    fn handle_request<'a>(
+        &'a self,
+        load_request: &'a LoadRequest,
+        load_context: LoadContext,
+    ) -> BoxedFuture<'a, ()>;
     fn extensions(&self) -> &[&str];
+    /// The type name of the [AssetLoader] that handles the requests
+    fn loader_name(&self) -> &'static str;
 }
 
 pub(crate) struct ChannelAssetHandler<TLoader, TAsset>
@@ -37,19 +47,19 @@ where
         ChannelAssetHandler { sender, loader }
     }
 
-    fn load_asset(&self, load_request: &LoadRequest) -> Result<TAsset, AssetLoadError> {
-        match File::open(&load_request.path) {
-            Ok(mut file) => {
-                let mut bytes = Vec::new();
-                file.read_to_end(&mut bytes)?;
-                let asset = self.loader.from_bytes(&load_request.path, bytes)?;
-                Ok(asset)
-            }
-            Err(e) => Err(AssetLoadError::Io(std::io::Error::new(
-                e.kind(),
-                format!("{}", load_request.path.display()),
-            ))),
-        }
+    async fn load_asset(
+        &self,
+        load_request: &LoadRequest,
+        load_context: &mut LoadContext,
+    ) -> Result<TAsset, AssetLoadError> {
+        load_context.read_meta()?;
+        let read_path = load_request
+            .imported_path
+            .as_ref()
+            .unwrap_or(&load_request.path);
+        let bytes = load_context.read_asset_bytes(read_path)?;
+        let asset = self.loader.load(bytes, load_context).await?;
+        Ok(asset)
     }
 }
 
@@ -58,20 +68,40 @@ where
     TLoader: AssetLoader<TAsset> + 'static,
     TAsset: Send + 'static,
 {
-    fn handle_request(&self, load_request: &LoadRequest) {
-        let result = self.load_asset(load_request);
-        let asset_result = AssetResult {
-            handle: Handle::from(load_request.handle_id),
-            result,
-            path: load_request.path.clone(),
-            version: load_request.version,
-        };
-        self.sender
-            .send(asset_result)
-            .expect("loaded asset should have been sent");
+    fn handle_request<'a>(
+        &'a self,
+        load_request: &'a LoadRequest,
+        mut load_context: LoadContext,
+    ) -> BoxedFuture<'a, ()> {
+        Box::pin(async move {
+            let result = self
+                .load_asset(load_request, &mut load_context)
+                .await
+                .map_err(|error| AssetLoadFailure {
+                    path: load_request.path.clone(),
+                    loader: self.loader.name(),
+                    error,
+                });
+            let (dependencies, labeled_assets) = load_context.into_parts();
+            let asset_result = AssetResult {
+                handle: Handle::from(load_request.handle_id),
+                result,
+                path: load_request.path.clone(),
+                version: load_request.version,
+                dependencies,
+                labeled_assets,
+            };
+            self.sender
+                .send(asset_result)
+                .expect("loaded asset should have been sent");
+        })
     }
 
     fn extensions(&self) -> &[&str] {
         self.loader.extensions()
     }
+
+    fn loader_name(&self) -> &'static str {
+        self.loader.name()
+    }
 }
@@ -1,12 +1,16 @@
-use crate::{AssetServer, AssetVersion, Assets, Handle, LoadState};
+use crate::{
+    get_meta_path, AssetIoError, AssetSaver, AssetServer, AssetVersion, Assets, Handle, LoadState,
+    LoaderSettings, SharedAssetState,
+};
 use anyhow::Result;
-use bevy_ecs::{Res, ResMut, Resource};
+use bevy_ecs::{Res, ResMut, Resource, Resources};
+use bevy_utils::BoxedFuture;
 use crossbeam_channel::{Receiver, Sender, TryRecvError};
-use fs::File;
-use io::Read;
 use std::{
-    fs, io,
+    any::Any,
+    io,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use thiserror::Error;
 
@@ -15,29 +19,238 @@ use thiserror::Error;
 pub enum AssetLoadError {
     #[error("Encountered an io error while loading asset.")]
     Io(#[from] io::Error),
+    #[error("Encountered an error while reading an asset.")]
+    AssetIoError(#[from] AssetIoError),
+    #[error("The asset's meta file is invalid.")]
+    MetaError(#[from] ron::Error),
     #[error("This asset's loader encountered an error while loading.")]
     LoaderError(#[from] anyhow::Error),
 }
 
+/// An [AssetLoadError] along with the asset it occurred on. The [AssetServer] keeps the failure of an asset until
+/// the asset is loaded again, see [AssetServer::get_load_failure].
+#[derive(Error, Debug)]
+#[error("Failed to load asset {path:?} using {loader}.")]
+pub struct AssetLoadFailure {
+    pub path: PathBuf,
+    /// The type name of the [AssetLoader] that loaded the asset
+    pub loader: &'static str,
+    #[source]
+    pub error: AssetLoadError,
+}
+
+impl AssetLoadFailure {
+    /// Iterates over this failure and the errors that caused it, such as the error returned by the loader
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        std::iter::successors(Some(self as &(dyn std::error::Error + 'static)), |error| {
+            error.source()
+        })
+    }
+}
+
 /// A loader for a given asset of type `T`
 pub trait AssetLoader<T>: Send + Sync + 'static {
-    fn from_bytes(&self, asset_path: &Path, bytes: Vec<u8>) -> Result<T, anyhow::Error>;
+    /// Loads an asset from the bytes of its file. `settings` come from the asset's meta file, if it has one.
+    fn from_bytes(
+        &self,
+        asset_path: &Path,
+        bytes: Vec<u8>,
+        settings: &LoaderSettings,
+    ) -> Result<T, anyhow::Error>;
+    /// Loads the asset with access to a [LoadContext], which allows loading the assets it depends on. Loads run on
+    /// the [IOTaskPool](bevy_tasks::IOTaskPool). By default, this calls [AssetLoader::from_bytes].
+    fn load<'a>(
+        &'a self,
+        bytes: Vec<u8>,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<T, anyhow::Error>> {
+        Box::pin(
+            async move { self.from_bytes(load_context.path(), bytes, load_context.settings()) },
+        )
+    }
     fn extensions(&self) -> &[&str];
-    fn load_from_file(&self, asset_path: &Path) -> Result<T, AssetLoadError> {
-        let mut file = File::open(asset_path)?;
-        let mut bytes = Vec::new();
-        file.read_to_end(&mut bytes)?;
-        let asset = self.from_bytes(asset_path, bytes)?;
-        Ok(asset)
+    /// The name of this loader, which is reported when it fails to load an asset
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Gives an [AssetLoader] access to the [AssetServer] while it loads an asset
+pub struct LoadContext {
+    path: PathBuf,
+    settings: LoaderSettings,
+    shared: Arc<SharedAssetState>,
+    dependencies: Vec<PathBuf>,
+    labeled_assets: Vec<LabeledAsset>,
+}
+
+impl LoadContext {
+    pub(crate) fn new(path: PathBuf, shared: Arc<SharedAssetState>) -> Self {
+        LoadContext {
+            path,
+            settings: LoaderSettings::default(),
+            shared,
+            dependencies: Vec::new(),
+            labeled_assets: Vec::new(),
+        }
+    }
+
+    /// The path of the asset that is being loaded
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The settings from the meta file of the asset that is being loaded
+    pub fn settings(&self) -> &LoaderSettings {
+        &self.settings
+    }
+
+    /// Reads the settings from the meta file of the asset that is being loaded, if it has one
+    pub(crate) fn read_meta(&mut self) -> Result<(), AssetLoadError> {
+        match self.shared.asset_io.load_path(&get_meta_path(&self.path)) {
+            Ok(bytes) => self.settings = LoaderSettings::from_meta_bytes(bytes)?,
+            Err(AssetIoError::NotFound(_)) => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        Ok(())
+    }
+
+    /// Reads the bytes at the given path, which is relative to the asset root. This is meant for files that belong to
+    /// the asset but aren't assets themselves, such as the buffers of a GLTF file.
+    pub fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
+        self.shared.asset_io.load_path(path.as_ref())
+    }
+
+    /// Returns a handle to the asset at the given path, which is relative to the asset root, and records it as a
+    /// dependency of the asset that is being loaded. Dependencies are loaded once this asset is loaded. Their
+    /// state is reflected in [AssetServer::get_recursive_load_state] and they are hot reloaded along with this
+    /// asset.
+    pub fn load_dependency<T: Resource, P: AsRef<Path>>(&mut self, path: P) -> Handle<T> {
+        let path = path.as_ref();
+        let handle_id = self.shared.get_or_reserve_handle_id(path);
+        if !self
+            .dependencies
+            .iter()
+            .any(|dependency| dependency == path)
+        {
+            self.dependencies.push(path.to_owned());
+        }
+
+        self.shared.get_handle_with_id(handle_id)
+    }
+
+    /// Adds an asset of any registered asset type that is loaded along with the asset that is being loaded, such as
+    /// a single mesh of a GLTF file. Labeled assets are addressed with a path of the form `path#label`, for
+    /// example `AssetServer::load("models/monkey.gltf#Mesh0/Primitive0")`. Like any other asset, labeled assets are
+    /// freed once their last strong handle is dropped.
+    pub fn set_labeled_asset<T: Resource>(&mut self, label: &str, asset: T) -> Handle<T> {
+        let path = get_labeled_path(&self.path, label);
+        let handle_id = self.shared.get_or_reserve_handle_id(&path);
+        // assets are processed without asset channels, so labeled assets are kept even if they can't be sent
+        let send = self.shared.get_asset_sender::<T>().map(|sender| {
+            let labeled_path = path.clone();
+            Box::new(move |asset: Box<dyn Any + Send + Sync>, version| {
+                // the receiver is only gone once the app shuts down
+                let _ = sender.send(AssetResult {
+                    result: Ok(*asset.downcast::<T>().unwrap()),
+                    handle: Handle::weak(handle_id),
+                    path: labeled_path,
+                    version,
+                    dependencies: Vec::new(),
+                    labeled_assets: Vec::new(),
+                });
+            }) as SendLabeledAsset
+        });
+        self.labeled_assets.push(LabeledAsset {
+            path,
+            asset: Box::new(asset),
+            send,
+            save: save_labeled_asset::<T>,
+        });
+
+        self.shared.get_handle_with_id(handle_id)
+    }
+
+    pub fn dependencies(&self) -> &[PathBuf] {
+        &self.dependencies
+    }
+
+    pub(crate) fn into_parts(self) -> (Vec<PathBuf>, Vec<LabeledAsset>) {
+        (self.dependencies, self.labeled_assets)
+    }
+}
+
+/// Returns the path of the asset with the given label in the file at `path`
+pub fn get_labeled_path(path: &Path, label: &str) -> PathBuf {
+    PathBuf::from(format!("{}#{}", path.to_string_lossy(), label))
+}
+
+/// Splits a path of the form `path#label` into the path of the file and the label
+pub fn split_label(path: &Path) -> (&Path, Option<&str>) {
+    match path.to_str() {
+        Some(path_str) => match path_str.find('#') {
+            Some(index) => (Path::new(&path_str[..index]), Some(&path_str[index + 1..])),
+            None => (path, None),
+        },
+        None => (path, None),
+    }
+}
+
+/// An asset that was added using [LoadContext::set_labeled_asset]. It is sent to its [AssetChannel] once the asset
+/// it was loaded with is loaded. When that asset is processed, it is saved to its own processed file instead.
+pub struct LabeledAsset {
+    pub path: PathBuf,
+    asset: Box<dyn Any + Send + Sync>,
+    send: Option<SendLabeledAsset>,
+    save: SaveLabeledAsset,
+}
+
+type SendLabeledAsset = Box<dyn FnOnce(Box<dyn Any + Send + Sync>, AssetVersion) + Send + Sync>;
+type SaveLabeledAsset = fn(&dyn Any, &Resources) -> Option<Result<(Vec<u8>, String)>>;
+
+impl LabeledAsset {
+    /// Returns false if the type of the asset was not added using `AddAsset::add_asset`
+    pub(crate) fn can_send(&self) -> bool {
+        self.send.is_some()
+    }
+
+    pub(crate) fn send(self, version: AssetVersion) {
+        if let Some(send) = self.send {
+            send(self.asset, version)
+        }
+    }
+
+    /// Saves the asset with the [AssetSaver] for its type in `savers` and returns the saved bytes along with the
+    /// extension of the saver. Returns `None` if there is no saver for its type.
+    pub(crate) fn save(&self, savers: &Resources) -> Option<Result<(Vec<u8>, String)>> {
+        (self.save)(&*self.asset, savers)
     }
 }
 
+fn save_labeled_asset<T: Resource>(
+    asset: &dyn Any,
+    savers: &Resources,
+) -> Option<Result<(Vec<u8>, String)>> {
+    let saver = savers.get::<Box<dyn AssetSaver<T>>>()?;
+    let asset = asset.downcast_ref::<T>()?;
+    Some(
+        saver
+            .save(asset)
+            .map(|bytes| (bytes, saver.extension().to_string())),
+    )
+}
+
 /// The result of loading an asset of type `T`
 pub struct AssetResult<T: 'static> {
-    pub result: Result<T, AssetLoadError>,
+    pub result: Result<T, AssetLoadFailure>,
     pub handle: Handle<T>,
     pub path: PathBuf,
     pub version: AssetVersion,
+    /// The paths of the assets this asset depends on
+    pub dependencies: Vec<PathBuf>,
+    /// The assets that were loaded from the same file as this asset
+    pub labeled_assets: Vec<LabeledAsset>,
 }
 
 /// A channel to send and receive [AssetResult]s
@@ -62,16 +275,30 @@ pub fn update_asset_storage_system<T: Resource>(
 ) {
     loop {
         match asset_channel.receiver.try_recv() {
+            // the asset was freed while it was loading
+            Ok(result) if asset_server.get_load_state(&result.handle).is_none() => {}
             Ok(result) => match result.result {
                 Ok(asset) => {
-                    assets.set(result.handle, asset);
+                    assets.set(&result.handle, asset);
+                    asset_server.set_labeled_assets(
+                        &result.path,
+                        result.version,
+                        result.labeled_assets,
+                    );
+                    asset_server.set_dependencies(result.handle.id, result.dependencies);
                     asset_server
                         .set_load_state(result.handle.id, LoadState::Loaded(result.version));
                 }
-                Err(err) => {
-                    asset_server
-                        .set_load_state(result.handle.id, LoadState::Failed(result.version));
-                    log::error!("Failed to load asset: {:?}", err);
+                Err(failure) => {
+                    log::error!("{}", failure);
+                    let failure = Arc::new(failure);
+                    asset_server.set_labeled_assets(&result.path, result.version, Vec::new());
+                    asset_server.set_load_failure(
+                        result.handle.id,
+                        result.version,
+                        failure.clone(),
+                    );
+                    assets.send_failed(result.handle.id, failure);
                 }
             },
             Err(TryRecvError::Empty) => {
@@ -0,0 +1,126 @@
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// The contents of a `.meta` file. Meta files are optional RON files that are stored next to the asset they
+/// describe, for example `textures/sprites.png.meta` for `textures/sprites.png`:
+///
+/// ```ron
+/// (
+///     uuid: "9b8a3c6e-2a4f-4c8e-8f3b-1d2e3f4a5b6c",
+///     settings: (
+///         min_filter: Nearest,
+///     ),
+/// )
+/// ```
+///
+/// As long as a meta file is renamed along with its asset, the asset keeps its uuid and settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetMeta<S = IgnoredAny> {
+    /// A stable id of the asset, which doesn't depend on its path
+    pub uuid: Uuid,
+    /// The settings of the [AssetLoader](crate::AssetLoader) that loads the asset. Missing settings use their
+    /// default values.
+    #[serde(default)]
+    pub settings: S,
+}
+
+impl<S> AssetMeta<S> {
+    /// Creates the meta of a new asset, which gets a random uuid
+    pub fn new(settings: S) -> Self {
+        AssetMeta {
+            uuid: Uuid::new_v4(),
+            settings,
+        }
+    }
+}
+
+/// Returns the path of the meta file of the asset at the given path
+pub fn get_meta_path(path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.meta", path.to_string_lossy()))
+}
+
+/// The settings an [AssetLoader](crate::AssetLoader) uses for a single asset, as read from the asset's meta file.
+/// Each loader defines its own settings type, which is deserialized with [LoaderSettings::get].
+#[derive(Debug, Clone, Default)]
+pub struct LoaderSettings {
+    uuid: Option<Uuid>,
+    meta: Option<Vec<u8>>,
+}
+
+impl LoaderSettings {
+    /// Reads the settings from the bytes of a meta file. This fails if the file isn't a valid [AssetMeta].
+    pub fn from_meta_bytes(bytes: Vec<u8>) -> Result<Self, ron::Error> {
+        let meta = ron::de::from_bytes::<AssetMeta>(&bytes)?;
+        Ok(LoaderSettings {
+            uuid: Some(meta.uuid),
+            meta: Some(bytes),
+        })
+    }
+
+    /// Deserializes the settings as `S`. Assets without a meta file use `S::default()`.
+    pub fn get<S: DeserializeOwned + Default>(&self) -> Result<S, ron::Error> {
+        match &self.meta {
+            Some(bytes) => Ok(ron::de::from_bytes::<AssetMeta<S>>(bytes)?.settings),
+            None => Ok(S::default()),
+        }
+    }
+
+    /// The uuid from the asset's meta file, if it has one
+    pub fn uuid(&self) -> Option<Uuid> {
+        self.uuid
+    }
+
+    pub(crate) fn meta_bytes(&self) -> Option<&[u8]> {
+        self.meta.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Deserialize)]
+    #[serde(default)]
+    struct Settings {
+        scale: f32,
+        flip: bool,
+    }
+
+    #[test]
+    fn typed_settings() {
+        let settings = LoaderSettings::from_meta_bytes(
+            br#"(
+                uuid: "9b8a3c6e-2a4f-4c8e-8f3b-1d2e3f4a5b6c",
+                settings: (scale: 2.0),
+            )"#
+            .to_vec(),
+        )
+        .unwrap();
+        assert_eq!(
+            settings.uuid(),
+            Some(Uuid::parse_str("9b8a3c6e-2a4f-4c8e-8f3b-1d2e3f4a5b6c").unwrap())
+        );
+        assert_eq!(
+            settings.get::<Settings>().unwrap(),
+            Settings {
+                scale: 2.0,
+                flip: false
+            }
+        );
+
+        let settings = LoaderSettings::from_meta_bytes(
+            br#"(uuid: "9b8a3c6e-2a4f-4c8e-8f3b-1d2e3f4a5b6c")"#.to_vec(),
+        )
+        .unwrap();
+        assert_eq!(settings.get::<Settings>().unwrap(), Settings::default());
+        assert_eq!(
+            LoaderSettings::default().get::<Settings>().unwrap(),
+            Settings::default()
+        );
+        assert!(LoaderSettings::from_meta_bytes(b"(settings: ())".to_vec()).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{AssetLoader, AssetSaver, LoadContext};
+use anyhow::Result;
+use bevy_ecs::{FromResources, Resources};
+use futures_lite::future;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, convert::TryInto, marker::PhantomData, path::PathBuf};
+
+/// Converts source assets into a format that is faster to load, such as PNG images into textures that don't need
+/// to be decoded.
+///
+/// Processing is an offline step that is started with
+/// [AssetServer::process_assets](crate::AssetServer::process_assets). Processed files are written to the import
+/// folder and loaded in place of their source at runtime, using the loader for their processed extension. Labeled
+/// assets that were added while processing are saved to processed files of their own, using the
+/// [AssetSaver] that was added for their type.
+pub trait AssetProcessor: Send + Sync + 'static {
+    fn process(
+        &self,
+        bytes: Vec<u8>,
+        load_context: &mut LoadContext,
+    ) -> Result<Vec<u8>, anyhow::Error>;
+    /// The extensions of the source files this processor converts
+    fn extensions(&self) -> &[&str];
+    /// The extension of the processed files
+    fn processed_extension(&self) -> &str;
+}
+
+/// An [AssetProcessor] that loads source assets with an [AssetLoader] and writes them with an [AssetSaver]
+///
+/// Processed files only contain the loaded asset. Labeled assets, like the meshes of a GLTF file, are saved to
+/// their own processed files. Labeled assets without an [AssetSaver] for their type are still loaded from the source
+/// file.
+pub struct LoadAndSave<TAsset, TLoader, TSaver> {
+    loader: TLoader,
+    saver: TSaver,
+    marker: PhantomData<fn() -> TAsset>,
+}
+
+impl<TAsset, TLoader, TSaver> LoadAndSave<TAsset, TLoader, TSaver>
+where
+    TLoader: AssetLoader<TAsset>,
+    TSaver: AssetSaver<TAsset>,
+{
+    pub fn new(loader: TLoader, saver: TSaver) -> Self {
+        LoadAndSave {
+            loader,
+            saver,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<TAsset, TLoader, TSaver> FromResources for LoadAndSave<TAsset, TLoader, TSaver>
+where
+    TLoader: AssetLoader<TAsset> + FromResources,
+    TSaver: AssetSaver<TAsset> + FromResources,
+{
+    fn from_resources(resources: &Resources) -> Self {
+        LoadAndSave::new(
+            TLoader::from_resources(resources),
+            TSaver::from_resources(resources),
+        )
+    }
+}
+
+impl<TAsset, TLoader, TSaver> AssetProcessor for LoadAndSave<TAsset, TLoader, TSaver>
+where
+    TAsset: 'static,
+    TLoader: AssetLoader<TAsset>,
+    TSaver: AssetSaver<TAsset>,
+{
+    fn process(&self, bytes: Vec<u8>, load_context: &mut LoadContext) -> Result<Vec<u8>> {
+        let asset = future::block_on(self.loader.load(bytes, load_context))?;
+        self.saver.save(&asset)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.loader.extensions()
+    }
+
+    fn processed_extension(&self) -> &str {
+        self.saver.extension()
+    }
+}
+
+/// Records which processed file is loaded in place of each processed source asset. It is written to
+/// [ImportCache::FILE_NAME] in the import folder.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportCache {
+    /// The version of the source hash the imports were recorded with. Caches of other versions are discarded, since
+    /// their hashes can't be compared to the current ones.
+    #[serde(default)]
+    pub hash_version: u32,
+    pub imports: BTreeMap<PathBuf, ImportedAsset>,
+}
+
+impl ImportCache {
+    pub const FILE_NAME: &'static str = "imports.ron";
+    /// The current version of [ImportedAsset::source_hash]: the first 8 bytes of a SHA-256 digest
+    pub const HASH_VERSION: u32 = 1;
+}
+
+impl Default for ImportCache {
+    fn default() -> Self {
+        ImportCache {
+            hash_version: Self::HASH_VERSION,
+            imports: BTreeMap::new(),
+        }
+    }
+}
+
+/// A processed file in the [ImportCache]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedAsset {
+    /// The hash of the source file and its meta file at the time it was processed. Sources are only processed again
+    /// once it changes.
+    pub source_hash: u64,
+    pub path: PathBuf,
+}
+
+/// Hashes the bytes of a source file and its meta file. Processed files are named after this hash. The hash is
+/// stored in the [ImportCache], so it uses SHA-256, which gives the same result on every platform and toolchain.
+/// Changing it requires a new [ImportCache::HASH_VERSION].
+pub(crate) fn hash_source(bytes: &[u8], meta_bytes: Option<&[u8]>) -> u64 {
+    let mut hasher = Sha256::new();
+    // the length separates the source from the meta file
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+    if let Some(meta_bytes) = meta_bytes {
+        hasher.update(meta_bytes);
+    }
+    u64::from_le_bytes(hasher.finalize()[..8].try_into().unwrap())
+}
@@ -0,0 +1,9 @@
+use anyhow::Result;
+
+/// A saver for a given asset of type `T`. It writes assets in a format that an [AssetLoader](crate::AssetLoader) for
+/// its extension can load again.
+pub trait AssetSaver<T>: Send + Sync + 'static {
+    fn save(&self, asset: &T) -> Result<Vec<u8>, anyhow::Error>;
+    /// The extension of the files this saver writes
+    fn extension(&self) -> &str;
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use bevy_asset::AssetLoader;
+use bevy_asset::{AssetLoader, LoaderSettings};
 use std::{io::Cursor, path::Path, sync::Arc};
 
 /// A source of audio data
@@ -19,7 +19,12 @@ impl AsRef<[u8]> for AudioSource {
 pub struct Mp3Loader;
 
 impl AssetLoader<AudioSource> for Mp3Loader {
-    fn from_bytes(&self, _asset_path: &Path, bytes: Vec<u8>) -> Result<AudioSource> {
+    fn from_bytes(
+        &self,
+        _asset_path: &Path,
+        bytes: Vec<u8>,
+        _settings: &LoaderSettings,
+    ) -> Result<AudioSource> {
         Ok(AudioSource {
             bytes: Arc::new(bytes),
         })
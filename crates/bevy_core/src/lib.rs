@@ -24,8 +24,10 @@ pub struct CorePlugin;
 impl Plugin for CorePlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_resource::<Time>()
+            .init_resource::<TimeUpdateStrategy>()
             .init_resource::<EntityLabels>()
             .register_component::<Timer>()
+            .register_method("reset", Timer::reset)
             .register_property::<Vec2>()
             .register_property::<Vec3>()
             .register_property::<Mat3>()
@@ -1,4 +1,4 @@
-use bevy_ecs::ResMut;
+use bevy_ecs::{Res, ResMut};
 use std::time::Duration;
 
 #[cfg(target_arch = "wasm32")]
@@ -29,9 +29,29 @@ impl Default for Time {
     }
 }
 
+/// Determines how [time_system] advances [Time] on each update
+#[derive(Debug, Clone, Copy)]
+pub enum TimeUpdateStrategy {
+    /// Advance [Time] using the system clock
+    Automatic,
+    /// Advance [Time] by the given duration on every update, regardless of how much real time has passed. This makes
+    /// updates deterministic, which is useful for tests.
+    ManualDuration(Duration),
+}
+
+impl Default for TimeUpdateStrategy {
+    fn default() -> Self {
+        TimeUpdateStrategy::Automatic
+    }
+}
+
 impl Time {
     pub fn update(&mut self) {
-        let now = Instant::now();
+        self.update_with_instant(Instant::now());
+    }
+
+    /// Updates time as if the current update happened at `now`
+    pub fn update_with_instant(&mut self, now: Instant) {
         if let Some(instant) = self.instant {
             self.delta = now - instant;
             self.delta_seconds_f64 = self.delta.as_secs_f64();
@@ -43,11 +63,40 @@ impl Time {
         self.instant = Some(now);
     }
 
+    /// Advances time by exactly `delta` since the last update, ignoring the system clock. The first call treats
+    /// startup as the last update.
+    pub fn advance_by(&mut self, delta: Duration) {
+        let last_instant = *self.instant.get_or_insert(self.startup);
+        self.update_with_instant(last_instant + delta);
+    }
+
+    /// Time elapsed between startup and the last update
     pub fn time_since_startup(&self) -> Duration {
-        Instant::now() - self.startup
+        self.instant.unwrap_or(self.startup) - self.startup
+    }
+}
+
+pub(crate) fn time_system(mut time: ResMut<Time>, strategy: Res<TimeUpdateStrategy>) {
+    match *strategy {
+        TimeUpdateStrategy::Automatic => time.update(),
+        TimeUpdateStrategy::ManualDuration(delta) => time.advance_by(delta),
     }
 }
 
-pub(crate) fn time_system(mut time: ResMut<Time>) {
-    time.update();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_by() {
+        let mut time = Time::default();
+        time.advance_by(Duration::from_millis(250));
+        assert_eq!(time.delta, Duration::from_millis(250));
+        assert_eq!(time.delta_seconds, 0.25);
+
+        time.advance_by(Duration::from_millis(500));
+        assert_eq!(time.delta, Duration::from_millis(500));
+        assert_eq!(time.seconds_since_startup, 0.75);
+        assert_eq!(time.time_since_startup(), Duration::from_millis(750));
+    }
 }
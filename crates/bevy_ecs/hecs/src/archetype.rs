@@ -311,6 +311,20 @@ impl Archetype {
         }
     }
 
+    /// Drops the components at `index` and moves the last row into its place. Returns the index the
+    /// moved row was at, if a row was moved.
+    ///
+    /// This is only meant for archetypes that aren't part of a [World](crate::World), which keeps
+    /// track of the rows of its entities itself. Panics if `index` is out of bounds.
+    #[doc(hidden)]
+    pub fn remove_type_erased_row(&mut self, index: usize) -> Option<usize> {
+        assert!(index < self.len(), "row index out of bounds");
+        let last = self.len() - 1;
+        // SAFETY: the index is in bounds, and the caller keeps track of the moved row using the
+        // returned index
+        unsafe { self.remove(index) }.map(|_| last)
+    }
+
     /// Returns the ID of the entity moved into `index`, if any
     pub(crate) unsafe fn remove(&mut self, index: usize) -> Option<Entity> {
         let last = self.len - 1;
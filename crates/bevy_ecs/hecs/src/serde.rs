@@ -1,7 +1,7 @@
 // modified by Bevy contributors
 
 use crate::entities::Entity;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 impl Serialize for Entity {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -11,3 +11,12 @@ impl Serialize for Entity {
         serializer.serialize_u32(self.id())
     }
 }
+
+impl<'de> Deserialize<'de> for Entity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u32::deserialize(deserializer).map(Entity::new)
+    }
+}
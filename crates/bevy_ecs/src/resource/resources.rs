@@ -63,6 +63,37 @@ impl Resources {
         self.insert_resource(resource, ResourceIndex::System(id))
     }
 
+    /// Removes the resource with the given `type_id`, including all of its system-local instances.
+    /// Returns true if the resource existed.
+    pub fn remove_type(&mut self, type_id: TypeId) -> bool {
+        self.resource_data.remove(&type_id).is_some()
+    }
+
+    /// Removes all local resources that belong to the system with the given `id`
+    pub fn remove_local_resources(&mut self, id: SystemId) {
+        for data in self.resource_data.values_mut() {
+            if let Some(index) = data.system_id_to_archetype_index.remove(&id.0) {
+                // the last resource is moved into the removed resource's place
+                if let Some(moved_index) = data.archetype.remove_type_erased_row(index) {
+                    if data.default_index == Some(moved_index) {
+                        data.default_index = Some(index);
+                    } else if let Some(system_index) = data
+                        .system_id_to_archetype_index
+                        .values_mut()
+                        .find(|system_index| **system_index == moved_index)
+                    {
+                        *system_index = index;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Iterates the types of all resources in the collection
+    pub fn type_ids(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.resource_data.keys().cloned()
+    }
+
     fn insert_resource<T: Resource>(&mut self, mut resource: T, resource_index: ResourceIndex) {
         let type_id = TypeId::of::<T>();
         let data = self.resource_data.entry(type_id).or_insert_with(|| {
@@ -325,6 +356,23 @@ mod tests {
         assert_eq!(*resources.get::<i32>().expect("resource exists"), 123);
     }
 
+    #[test]
+    fn remove_resources() {
+        let mut resources = Resources::default();
+        resources.insert(123);
+        resources.insert_local(SystemId(0), 1);
+        resources.insert_local(SystemId(1), 2);
+
+        resources.remove_local_resources(SystemId(0));
+        assert!(resources.get_local::<i32>(SystemId(0)).is_none());
+        assert_eq!(*resources.get_local::<i32>(SystemId(1)).unwrap(), 2);
+        assert_eq!(*resources.get::<i32>().unwrap(), 123);
+
+        assert!(resources.remove_type(std::any::TypeId::of::<i32>()));
+        assert!(resources.get::<i32>().is_none());
+        assert!(resources.get_local::<i32>(SystemId(1)).is_none());
+    }
+
     #[test]
     #[should_panic(expected = "i32 already borrowed")]
     fn resource_double_mut_panic() {
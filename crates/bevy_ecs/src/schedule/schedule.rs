@@ -9,11 +9,16 @@ use std::borrow::Cow;
 /// An ordered collection of stages, which each contain an ordered list of [System]s.
 /// Schedules are essentially the "execution plan" for an App's systems.
 /// They are run on a given [World] and [Resources] reference.
+///
+/// Each system is initialized once, the first time [Schedule::initialize] runs after it was added. Adding or removing
+/// other systems does not initialize it again, so its [Local](crate::Local) state is kept. A system that is removed
+/// and added again is initialized again.
 #[derive(Default)]
 pub struct Schedule {
     pub(crate) stages: HashMap<Cow<'static, str>, Vec<Box<dyn System>>>,
     pub(crate) stage_order: Vec<Cow<'static, str>>,
     pub(crate) system_ids: HashSet<SystemId>,
+    initialized_system_ids: HashSet<SystemId>,
     generation: usize,
     last_initialize_generation: usize,
 }
@@ -123,6 +128,25 @@ impl Schedule {
         self
     }
 
+    /// Removes the system with the given `system_id` from whichever stage contains it
+    pub fn remove_system(&mut self, system_id: SystemId) -> Option<Box<dyn System>> {
+        for systems in self.stages.values_mut() {
+            if let Some(index) = systems.iter().position(|system| system.id() == system_id) {
+                self.system_ids.remove(&system_id);
+                self.initialized_system_ids.remove(&system_id);
+                self.generation += 1;
+                return Some(systems.remove(index));
+            }
+        }
+
+        None
+    }
+
+    /// Iterates the ids of all systems in the schedule
+    pub fn system_ids(&self) -> impl Iterator<Item = SystemId> + '_ {
+        self.system_ids.iter().cloned()
+    }
+
     pub fn run(&mut self, world: &mut World, resources: &mut Resources) {
         for stage_name in self.stage_order.iter() {
             if let Some(stage_systems) = self.stages.get_mut(stage_name) {
@@ -159,6 +183,7 @@ impl Schedule {
         resources.clear_trackers();
     }
 
+    /// Initializes the systems that were added since the last call. Systems that were already initialized are skipped.
     // TODO: move this code to ParallelExecutor
     pub fn initialize(&mut self, world: &mut World, resources: &mut Resources) {
         if self.last_initialize_generation == self.generation {
@@ -167,7 +192,10 @@ impl Schedule {
 
         for stage in self.stages.values_mut() {
             for system in stage.iter_mut() {
-                system.initialize(world, resources);
+                // systems that were already initialized keep their local state
+                if self.initialized_system_ids.insert(system.id()) {
+                    system.initialize(world, resources);
+                }
             }
         }
 
@@ -178,3 +206,47 @@ impl Schedule {
         self.generation
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Schedule;
+    use crate::{
+        resource::{Local, ResMut, Resources},
+        system::IntoQuerySystem,
+    };
+    use bevy_hecs::World;
+
+    #[test]
+    fn remove_system_keeps_local_state() {
+        fn count_system(mut count: Local<usize>, mut total: ResMut<usize>) {
+            *count += 1;
+            *total = *count;
+        }
+
+        fn other_system() {}
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(0usize);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", count_system.system());
+        let other_system = other_system.system();
+        let other_id = other_system.id();
+        schedule.add_system_to_stage("update", other_system);
+
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+
+        assert!(schedule.remove_system(other_id).is_some());
+        assert!(schedule.remove_system(other_id).is_none());
+        assert_eq!(schedule.system_ids().count(), 1);
+
+        // re-initializing after the schedule changed must not reset the remaining system's Local
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 3);
+    }
+}
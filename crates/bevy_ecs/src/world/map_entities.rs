@@ -0,0 +1,64 @@
+use bevy_hecs::{Entity, World};
+use bevy_utils::HashMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MapEntitiesError {
+    #[error("The given entity does not exist in the map.")]
+    EntityNotFound(Entity),
+}
+
+/// Updates the entities that a component refers to using an [EntityMap]. This is implemented by components with
+/// [Entity] fields, so they keep pointing at the right entities when they are copied to new entities, for example
+/// when a scene is spawned.
+pub trait MapEntities {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError>;
+}
+
+/// Maps entities to the entities that replace them
+#[derive(Debug, Default)]
+pub struct EntityMap {
+    map: HashMap<Entity, Entity>,
+}
+
+impl EntityMap {
+    pub fn insert(&mut self, from: Entity, to: Entity) {
+        self.map.insert(from, to);
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        self.map.remove(&entity);
+    }
+
+    /// Returns the entity that replaces `entity`
+    pub fn get(&self, entity: Entity) -> Result<Entity, MapEntitiesError> {
+        self.map
+            .get(&entity)
+            .cloned()
+            .ok_or(MapEntitiesError::EntityNotFound(entity))
+    }
+
+    /// Returns the entity that replaces `entity`, reserving a new entity in `world` if there is none yet
+    pub fn get_or_reserve(&mut self, entity: Entity, world: &World) -> Entity {
+        *self
+            .map
+            .entry(entity)
+            .or_insert_with(|| world.reserve_entity())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.map.keys().cloned()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.map.values().cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
@@ -2,15 +2,16 @@ mod loader;
 pub use loader::*;
 
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
-use bevy_render::mesh::Mesh;
+use bevy_asset::{AddAsset, LoadAndSave};
+use bevy_render::mesh::{BinaryMeshSaver, Mesh};
 
-/// Adds support for GLTF file loading to Apps
+/// Adds support for GLTF file loading to Apps. GLTF files are processed into binary meshes.
 #[derive(Default)]
 pub struct GltfPlugin;
 
 impl Plugin for GltfPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.add_asset_loader::<Mesh, GltfLoader>();
+        app.add_asset_loader::<Mesh, GltfLoader>()
+            .add_asset_processor::<LoadAndSave<Mesh, GltfLoader, BinaryMeshSaver>>();
     }
 }
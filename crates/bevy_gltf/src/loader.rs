@@ -4,23 +4,68 @@ use bevy_render::{
 };
 
 use anyhow::Result;
-use bevy_asset::AssetLoader;
+use bevy_asset::{AssetIoError, AssetLoader, LoadContext, LoaderSettings};
+use bevy_utils::BoxedFuture;
 use gltf::{buffer::Source, mesh::Mode};
+use serde::{Deserialize, Serialize};
 use std::{fs, io, path::Path};
 use thiserror::Error;
 
 /// Loads meshes from GLTF files into Mesh assets
 ///
+/// The first mesh of the file is the loaded asset. Every primitive of every mesh is also added as a labeled asset,
+/// which can be loaded using paths like `models/monkey.gltf#Mesh0/Primitive0`.
+///
 /// NOTE: eventually this will loading into Scenes instead of Meshes
 #[derive(Default)]
 pub struct GltfLoader;
 
+/// Settings for [GltfLoader], which are read from the meta file of a GLTF file
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GltfSettings {
+    /// Leaves out the normals of meshes, for example for meshes that are drawn without lighting
+    pub skip_normals: bool,
+}
+
 impl AssetLoader<Mesh> for GltfLoader {
-    fn from_bytes(&self, asset_path: &Path, bytes: Vec<u8>) -> Result<Mesh> {
-        let mesh = load_gltf(asset_path, bytes)?;
+    fn from_bytes(
+        &self,
+        asset_path: &Path,
+        bytes: Vec<u8>,
+        settings: &LoaderSettings,
+    ) -> Result<Mesh> {
+        let settings = settings.get::<GltfSettings>()?;
+        let mesh = load_gltf(asset_path, bytes, &settings, |buffer_path| {
+            Ok(fs::read(buffer_path)?)
+        })?;
         Ok(mesh)
     }
 
+    fn load<'a>(
+        &'a self,
+        bytes: Vec<u8>,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Mesh>> {
+        Box::pin(async move {
+            let settings = load_context.settings().get::<GltfSettings>()?;
+            let gltf = gltf::Gltf::from_slice(&bytes)?;
+            let buffer_data = load_buffers(&gltf, load_context.path(), |buffer_path| {
+                Ok(load_context.read_asset_bytes(buffer_path)?)
+            })?;
+
+            for mesh in gltf.meshes() {
+                for primitive in mesh.primitives() {
+                    let label = format!("Mesh{}/Primitive{}", mesh.index(), primitive.index());
+                    let mesh = load_primitive(&buffer_data, &primitive, &settings)?;
+                    load_context.set_labeled_asset(&label, mesh);
+                }
+            }
+
+            Ok(load_first_mesh(&gltf, &buffer_data, &settings)?)
+        })
+    }
+
     fn extensions(&self) -> &[&str] {
         static EXTENSIONS: &[&str] = &["gltf", "glb"];
         EXTENSIONS
@@ -36,6 +81,8 @@ pub enum GltfError {
     Gltf(#[from] gltf::Error),
     #[error("Failed to load file.")]
     Io(#[from] io::Error),
+    #[error("Failed to read asset file.")]
+    AssetIo(#[from] AssetIoError),
     #[error("Binary blob is missing.")]
     MissingBlob,
     #[error("Failed to decode base64 mesh data.")]
@@ -56,12 +103,26 @@ fn get_primitive_topology(mode: Mode) -> Result<PrimitiveTopology, GltfError> {
 }
 
 // TODO: this should return a scene
-pub fn load_gltf(asset_path: &Path, bytes: Vec<u8>) -> Result<Mesh, GltfError> {
+/// Loads the first mesh of a GLTF file. `read_buffer` reads buffers that are stored in separate files.
+pub fn load_gltf(
+    asset_path: &Path,
+    bytes: Vec<u8>,
+    settings: &GltfSettings,
+    read_buffer: impl Fn(&Path) -> Result<Vec<u8>, GltfError>,
+) -> Result<Mesh, GltfError> {
     let gltf = gltf::Gltf::from_slice(&bytes)?;
-    let buffer_data = load_buffers(&gltf, asset_path)?;
+    let buffer_data = load_buffers(&gltf, asset_path, read_buffer)?;
+    load_first_mesh(&gltf, &buffer_data, settings)
+}
+
+fn load_first_mesh(
+    gltf: &gltf::Gltf,
+    buffer_data: &[Vec<u8>],
+    settings: &GltfSettings,
+) -> Result<Mesh, GltfError> {
     for scene in gltf.scenes() {
         if let Some(node) = scene.nodes().next() {
-            return Ok(load_node(&buffer_data, &node, 1)?);
+            return Ok(load_node(buffer_data, &node, 1, settings)?);
         }
     }
 
@@ -69,50 +130,68 @@ pub fn load_gltf(asset_path: &Path, bytes: Vec<u8>) -> Result<Mesh, GltfError> {
     panic!("no mesh found!")
 }
 
-fn load_node(buffer_data: &[Vec<u8>], node: &gltf::Node, depth: i32) -> Result<Mesh, GltfError> {
+fn load_node(
+    buffer_data: &[Vec<u8>],
+    node: &gltf::Node,
+    depth: i32,
+    settings: &GltfSettings,
+) -> Result<Mesh, GltfError> {
     if let Some(mesh) = node.mesh() {
         if let Some(primitive) = mesh.primitives().next() {
-            let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
-            let primitive_topology = get_primitive_topology(primitive.mode())?;
-            let mut mesh = Mesh::new(primitive_topology);
-
-            if let Some(vertex_attribute) = reader
-                .read_positions()
-                .map(|v| VertexAttribute::position(v.collect()))
-            {
-                mesh.attributes.push(vertex_attribute);
-            }
+            return load_primitive(buffer_data, &primitive, settings);
+        }
+    }
 
-            if let Some(vertex_attribute) = reader
-                .read_normals()
-                .map(|v| VertexAttribute::normal(v.collect()))
-            {
-                mesh.attributes.push(vertex_attribute);
-            }
+    if let Some(child) = node.children().next() {
+        return Ok(load_node(buffer_data, &child, depth + 1, settings)?);
+    }
 
-            if let Some(vertex_attribute) = reader
-                .read_tex_coords(0)
-                .map(|v| VertexAttribute::uv(v.into_f32().collect()))
-            {
-                mesh.attributes.push(vertex_attribute);
-            }
+    panic!("failed to find mesh")
+}
 
-            if let Some(indices) = reader.read_indices() {
-                mesh.indices = Some(indices.into_u32().collect::<Vec<u32>>());
-            };
+fn load_primitive(
+    buffer_data: &[Vec<u8>],
+    primitive: &gltf::Primitive,
+    settings: &GltfSettings,
+) -> Result<Mesh, GltfError> {
+    let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
+    let primitive_topology = get_primitive_topology(primitive.mode())?;
+    let mut mesh = Mesh::new(primitive_topology);
+
+    if let Some(vertex_attribute) = reader
+        .read_positions()
+        .map(|v| VertexAttribute::position(v.collect()))
+    {
+        mesh.attributes.push(vertex_attribute);
+    }
 
-            return Ok(mesh);
-        }
+    if let Some(vertex_attribute) = reader
+        .read_normals()
+        .filter(|_| !settings.skip_normals)
+        .map(|v| VertexAttribute::normal(v.collect()))
+    {
+        mesh.attributes.push(vertex_attribute);
     }
 
-    if let Some(child) = node.children().next() {
-        return Ok(load_node(buffer_data, &child, depth + 1)?);
+    if let Some(vertex_attribute) = reader
+        .read_tex_coords(0)
+        .map(|v| VertexAttribute::uv(v.into_f32().collect()))
+    {
+        mesh.attributes.push(vertex_attribute);
     }
 
-    panic!("failed to find mesh")
+    if let Some(indices) = reader.read_indices() {
+        mesh.indices = Some(indices.into_u32().collect::<Vec<u32>>());
+    };
+
+    Ok(mesh)
 }
 
-fn load_buffers(gltf: &gltf::Gltf, asset_path: &Path) -> Result<Vec<Vec<u8>>, GltfError> {
+fn load_buffers(
+    gltf: &gltf::Gltf,
+    asset_path: &Path,
+    read_buffer: impl Fn(&Path) -> Result<Vec<u8>, GltfError>,
+) -> Result<Vec<Vec<u8>>, GltfError> {
     const OCTET_STREAM_URI: &str = "data:application/octet-stream;base64,";
 
     let mut buffer_data = Vec::new();
@@ -127,7 +206,7 @@ fn load_buffers(gltf: &gltf::Gltf, asset_path: &Path) -> Result<Vec<Vec<u8>>, Gl
                     }
                 } else {
                     let buffer_path = asset_path.parent().unwrap().join(uri);
-                    let buffer_bytes = fs::read(buffer_path)?;
+                    let buffer_bytes = read_buffer(&buffer_path)?;
                     buffer_data.push(buffer_bytes);
                 }
             }
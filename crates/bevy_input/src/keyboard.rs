@@ -251,3 +251,40 @@ pub enum KeyCode {
     Paste,
     Cut,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputPlugin;
+
+    #[test]
+    fn keyboard_input_events_update_input() {
+        let mut app = App::build().add_plugin(InputPlugin).test_app();
+
+        app.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(KeyCode::Space),
+            state: ElementState::Pressed,
+        });
+        app.update();
+        assert!(app
+            .resource::<Input<KeyCode>>()
+            .just_pressed(KeyCode::Space));
+
+        app.update();
+        assert!(app.resource::<Input<KeyCode>>().pressed(KeyCode::Space));
+        assert!(!app
+            .resource::<Input<KeyCode>>()
+            .just_pressed(KeyCode::Space));
+
+        app.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(KeyCode::Space),
+            state: ElementState::Released,
+        });
+        app.update();
+        assert!(app
+            .resource::<Input<KeyCode>>()
+            .just_released(KeyCode::Space));
+    }
+}
@@ -4,14 +4,16 @@ mod modules;
 
 use modules::{get_modules, get_path};
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use proc_macro_crate::crate_name;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
     token::{Comma, Where},
-    Data, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Index, Member,
+    Data, DataEnum, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Index, Member, Path,
+    Variant,
 };
 
 #[derive(Default)]
@@ -24,86 +26,14 @@ static PROP_ATTRIBUTE_NAME: &str = "property";
 #[proc_macro_derive(Properties, attributes(property, module))]
 pub fn derive_properties(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
-    let unit_struct_punctuated = Punctuated::new();
-    let fields = match &ast.data {
-        Data::Struct(DataStruct {
-            fields: Fields::Named(fields),
-            ..
-        }) => &fields.named,
-        Data::Struct(DataStruct {
-            fields: Fields::Unnamed(fields),
-            ..
-        }) => &fields.unnamed,
-        Data::Struct(DataStruct {
-            fields: Fields::Unit,
-            ..
-        }) => &unit_struct_punctuated,
-        _ => panic!("expected a struct with named fields"),
-    };
-    let fields_and_args = fields
-        .iter()
-        .enumerate()
-        .map(|(i, f)| {
-            (
-                f,
-                f.attrs
-                    .iter()
-                    .find(|a| *a.path.get_ident().as_ref().unwrap() == PROP_ATTRIBUTE_NAME)
-                    .map(|a| {
-                        syn::custom_keyword!(ignore);
-                        let mut attribute_args = PropAttributeArgs { ignore: None };
-                        a.parse_args_with(|input: ParseStream| {
-                            if input.parse::<Option<ignore>>()?.is_some() {
-                                attribute_args.ignore = Some(true);
-                                return Ok(());
-                            }
-                            Ok(())
-                        })
-                        .expect("invalid 'property' attribute format");
-
-                        attribute_args
-                    }),
-                i,
-            )
-        })
-        .collect::<Vec<(&Field, Option<PropAttributeArgs>, usize)>>();
-    let active_fields = fields_and_args
-        .iter()
-        .filter(|(_field, attrs, _i)| {
-            attrs.is_none()
-                || match attrs.as_ref().unwrap().ignore {
-                    Some(ignore) => !ignore,
-                    None => true,
-                }
-        })
-        .map(|(f, _attr, i)| (*f, *i))
-        .collect::<Vec<(&Field, usize)>>();
-
     let modules = get_modules();
     let bevy_property_path = get_path(&modules.bevy_property);
 
-    let field_names = active_fields
-        .iter()
-        .map(|(field, index)| {
-            field
-                .ident
-                .as_ref()
-                .map(|i| i.to_string())
-                .unwrap_or_else(|| index.to_string())
-        })
-        .collect::<Vec<String>>();
-    let field_idents = active_fields
-        .iter()
-        .map(|(field, index)| {
-            field
-                .ident
-                .as_ref()
-                .map(|ident| Member::Named(ident.clone()))
-                .unwrap_or_else(|| Member::Unnamed(Index::from(*index)))
-        })
-        .collect::<Vec<_>>();
-    let field_count = active_fields.len();
-    let field_indices = (0..field_count).collect::<Vec<usize>>();
+    let (properties_fns, apply_props) = match &ast.data {
+        Data::Struct(DataStruct { fields, .. }) => struct_properties(fields, &bevy_property_path),
+        Data::Enum(DataEnum { variants, .. }) => enum_properties(variants, &bevy_property_path),
+        _ => panic!("expected a struct or an enum"),
+    };
 
     let generics = ast.generics;
     let (impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
@@ -112,44 +42,7 @@ pub fn derive_properties(input: TokenStream) -> TokenStream {
 
     TokenStream::from(quote! {
         impl #impl_generics #bevy_property_path::Properties for #struct_name#ty_generics {
-            fn prop(&self, name: &str) -> Option<&dyn #bevy_property_path::Property> {
-                match name {
-                    #(#field_names => Some(&self.#field_idents),)*
-                    _ => None,
-                }
-            }
-
-            fn prop_mut(&mut self, name: &str) -> Option<&mut dyn #bevy_property_path::Property> {
-                match name {
-                    #(#field_names => Some(&mut self.#field_idents),)*
-                    _ => None,
-                }
-            }
-
-            fn prop_with_index(&self, index: usize) -> Option<&dyn #bevy_property_path::Property> {
-                match index {
-                    #(#field_indices => Some(&self.#field_idents),)*
-                    _ => None,
-                }
-            }
-
-            fn prop_with_index_mut(&mut self, index: usize) -> Option<&mut dyn #bevy_property_path::Property> {
-                match index {
-                    #(#field_indices => Some(&mut self.#field_idents),)*
-                    _ => None,
-                }
-            }
-
-            fn prop_name(&self, index: usize) -> Option<&str> {
-                match index {
-                    #(#field_indices => Some(#field_names),)*
-                    _ => None,
-                }
-            }
-
-            fn prop_len(&self) -> usize {
-                #field_count
-            }
+            #properties_fns
 
             fn iter_props(&self) -> #bevy_property_path::PropertyIter {
                 #bevy_property_path::PropertyIter::new(self)
@@ -202,10 +95,7 @@ pub fn derive_properties(input: TokenStream) -> TokenStream {
                             properties.property_type()
                         );
                     }
-                    for (i, prop) in properties.iter_props().enumerate() {
-                        let name = properties.prop_name(i).unwrap();
-                        self.prop_mut(name).map(|p| p.apply(prop));
-                    }
+                    #apply_props
                 } else {
                     panic!("attempted to apply non-Properties type to Properties type");
                 }
@@ -216,6 +106,11 @@ pub fn derive_properties(input: TokenStream) -> TokenStream {
                 Some(self)
             }
 
+            #[inline]
+            fn as_properties_mut(&mut self) -> Option<&mut dyn #bevy_property_path::Properties> {
+                Some(self)
+            }
+
             fn serializable<'a>(&'a self, registry: &'a #bevy_property_path::PropertyTypeRegistry) -> #bevy_property_path::property_serde::Serializable<'a> {
                 #bevy_property_path::property_serde::Serializable::Owned(Box::new(#bevy_property_path::property_serde::MapSerializer::new(self, registry)))
             }
@@ -227,6 +122,263 @@ pub fn derive_properties(input: TokenStream) -> TokenStream {
     })
 }
 
+fn is_ignored(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .find(|a| *a.path.get_ident().as_ref().unwrap() == PROP_ATTRIBUTE_NAME)
+        .map(|a| {
+            syn::custom_keyword!(ignore);
+            let mut attribute_args = PropAttributeArgs { ignore: None };
+            a.parse_args_with(|input: ParseStream| {
+                if input.parse::<Option<ignore>>()?.is_some() {
+                    attribute_args.ignore = Some(true);
+                    return Ok(());
+                }
+                Ok(())
+            })
+            .expect("invalid 'property' attribute format");
+
+            attribute_args.ignore.unwrap_or(false)
+        })
+        .unwrap_or(false)
+}
+
+/// The members of the given fields and the names of their props. Tuple fields are named after their index.
+fn field_members(fields: &Fields) -> Vec<(&Field, Member, String)> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => (field, Member::Named(ident.clone()), ident.to_string()),
+            None => (
+                field,
+                Member::Unnamed(Index::from(index)),
+                index.to_string(),
+            ),
+        })
+        .collect()
+}
+
+fn struct_properties(fields: &Fields, bevy_property_path: &Path) -> (TokenStream2, TokenStream2) {
+    let active_fields = field_members(fields)
+        .into_iter()
+        .filter(|(field, _member, _name)| !is_ignored(field))
+        .collect::<Vec<_>>();
+    let field_names = active_fields
+        .iter()
+        .map(|(_field, _member, name)| name)
+        .collect::<Vec<_>>();
+    let field_idents = active_fields
+        .iter()
+        .map(|(_field, member, _name)| member)
+        .collect::<Vec<_>>();
+    let field_count = active_fields.len();
+    let field_indices = (0..field_count).collect::<Vec<usize>>();
+
+    let properties_fns = quote! {
+        fn prop(&self, name: &str) -> Option<&dyn #bevy_property_path::Property> {
+            match name {
+                #(#field_names => Some(&self.#field_idents),)*
+                _ => None,
+            }
+        }
+
+        fn prop_mut(&mut self, name: &str) -> Option<&mut dyn #bevy_property_path::Property> {
+            match name {
+                #(#field_names => Some(&mut self.#field_idents),)*
+                _ => None,
+            }
+        }
+
+        fn prop_with_index(&self, index: usize) -> Option<&dyn #bevy_property_path::Property> {
+            match index {
+                #(#field_indices => Some(&self.#field_idents),)*
+                _ => None,
+            }
+        }
+
+        fn prop_with_index_mut(&mut self, index: usize) -> Option<&mut dyn #bevy_property_path::Property> {
+            match index {
+                #(#field_indices => Some(&mut self.#field_idents),)*
+                _ => None,
+            }
+        }
+
+        fn prop_name(&self, index: usize) -> Option<&str> {
+            match index {
+                #(#field_indices => Some(#field_names),)*
+                _ => None,
+            }
+        }
+
+        fn prop_len(&self) -> usize {
+            #field_count
+        }
+    };
+    let apply_props = quote! {
+        for (i, prop) in properties.iter_props().enumerate() {
+            let name = properties.prop_name(i).unwrap();
+            self.prop_mut(name).map(|p| p.apply(prop));
+        }
+    };
+    (properties_fns, apply_props)
+}
+
+/// Enums expose the fields of their active variant as props. Applying properties of another variant replaces the
+/// value with that variant, see `bevy_property::VariantField` for how its fields are created. Unknown variants and
+/// variants whose fields can't be built are logged and leave the value unchanged.
+fn enum_properties(
+    variants: &Punctuated<Variant, Comma>,
+    bevy_property_path: &Path,
+) -> (TokenStream2, TokenStream2) {
+    let mut prop_arms = Vec::new();
+    let mut prop_with_index_arms = Vec::new();
+    let mut prop_name_arms = Vec::new();
+    let mut prop_len_arms = Vec::new();
+    let mut variant_arms = Vec::new();
+    let mut build_arms = Vec::new();
+    for variant in variants.iter() {
+        let variant_ident = &variant.ident;
+        let variant_name = variant_ident.to_string();
+        let fields = field_members(&variant.fields);
+        let active_fields = fields
+            .iter()
+            .filter(|(field, _member, _name)| !is_ignored(field))
+            .collect::<Vec<_>>();
+        let field_names = active_fields
+            .iter()
+            .map(|(_field, _member, name)| name)
+            .collect::<Vec<_>>();
+        let field_members = active_fields
+            .iter()
+            .map(|(_field, member, _name)| member)
+            .collect::<Vec<_>>();
+        let field_bindings = (0..active_fields.len())
+            .map(|index| format_ident!("field_{}", index))
+            .collect::<Vec<_>>();
+        let field_count = active_fields.len();
+        let field_indices = (0..field_count).collect::<Vec<usize>>();
+
+        let pattern = quote! {
+            Self::#variant_ident { #(#field_members: #field_bindings,)* .. }
+        };
+        prop_arms.push(quote! {
+            #pattern => match name {
+                #(#field_names => Some(#field_bindings),)*
+                _ => None,
+            }
+        });
+        prop_with_index_arms.push(quote! {
+            #pattern => match index {
+                #(#field_indices => Some(#field_bindings),)*
+                _ => None,
+            }
+        });
+        prop_name_arms.push(quote! {
+            Self::#variant_ident { .. } => match index {
+                #(#field_indices => Some(#field_names),)*
+                _ => None,
+            }
+        });
+        prop_len_arms.push(quote! {
+            Self::#variant_ident { .. } => #field_count
+        });
+        variant_arms.push(quote! {
+            Self::#variant_ident { .. } => #variant_name
+        });
+
+        // all active fields are built first, and the variant is only created if none of them failed
+        let built_fields = active_fields.iter().map(|(field, _member, name)| {
+            let ty = &field.ty;
+            quote! {
+                (&&#bevy_property_path::VariantField::<#ty>::new()).build(properties.prop(#name))
+            }
+        });
+        let ignored_members = fields
+            .iter()
+            .filter(|(field, _member, _name)| is_ignored(field))
+            .map(|(_field, member, _name)| member);
+        build_arms.push(quote! {
+            #variant_name => match (#(#built_fields,)*) {
+                (#(Some(#field_bindings),)*) => Some(Self::#variant_ident {
+                    #(#field_members: #field_bindings,)*
+                    #(#ignored_members: Default::default(),)*
+                }),
+                #[allow(unreachable_patterns)]
+                _ => None,
+            }
+        });
+    }
+
+    let properties_fns = quote! {
+        fn prop(&self, name: &str) -> Option<&dyn #bevy_property_path::Property> {
+            match self {
+                #(#prop_arms,)*
+            }
+        }
+
+        fn prop_mut(&mut self, name: &str) -> Option<&mut dyn #bevy_property_path::Property> {
+            match self {
+                #(#prop_arms,)*
+            }
+        }
+
+        fn prop_with_index(&self, index: usize) -> Option<&dyn #bevy_property_path::Property> {
+            match self {
+                #(#prop_with_index_arms,)*
+            }
+        }
+
+        fn prop_with_index_mut(&mut self, index: usize) -> Option<&mut dyn #bevy_property_path::Property> {
+            match self {
+                #(#prop_with_index_arms,)*
+            }
+        }
+
+        fn prop_name(&self, index: usize) -> Option<&str> {
+            match self {
+                #(#prop_name_arms,)*
+            }
+        }
+
+        fn prop_len(&self) -> usize {
+            match self {
+                #(#prop_len_arms,)*
+            }
+        }
+
+        fn variant(&self) -> Option<&str> {
+            Some(match self {
+                #(#variant_arms,)*
+            })
+        }
+    };
+    let apply_props = quote! {
+        match properties.variant() {
+            Some(variant) if Some(variant) != self.variant() => {
+                #[allow(unused_imports)]
+                use #bevy_property_path::{BuildWithClone, BuildWithDefault};
+                let value = match variant {
+                    #(#build_arms,)*
+                    _ => None,
+                };
+                match value {
+                    Some(value) => *self = value,
+                    None => #bevy_property_path::variant_not_applied(std::any::type_name::<Self>(), variant),
+                }
+            }
+            _ => {
+                for (i, prop) in properties.iter_props().enumerate() {
+                    let name = properties.prop_name(i).unwrap();
+                    self.prop_mut(name).map(|p| p.apply(prop));
+                }
+            }
+        }
+    };
+    (properties_fns, apply_props)
+}
+
 #[proc_macro_derive(Property)]
 pub fn derive_property(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -280,6 +432,11 @@ pub fn derive_property(input: TokenStream) -> TokenStream {
                 #bevy_property_path::property_serde::Serializable::Owned(Box::new(#bevy_property_path::property_serde::PropertyValueSerializer::new(self, registry)))
             }
 
+            #[inline]
+            fn serializable_value<'a>(&'a self, _registry: &'a #bevy_property_path::PropertyTypeRegistry) -> #bevy_property_path::property_serde::Serializable<'a> {
+                #bevy_property_path::property_serde::Serializable::Borrowed(self)
+            }
+
             fn property_type(&self) -> #bevy_property_path::PropertyType {
                 #bevy_property_path::PropertyType::Value
             }
@@ -354,8 +511,16 @@ pub fn impl_property(input: TokenStream) -> TokenStream {
 
     let (impl_generics, ty_generics, where_clause) = property_def.generics.split_for_impl();
     let ty = &property_def.type_name;
-    let serialize_fn = if let Some(serialize_fn) = property_def.serialize_fn {
+    // custom serialize functions don't add the type name, so it is added around their value
+    let serialize_value_fn = if let Some(serialize_fn) = &property_def.serialize_fn {
         quote! { #serialize_fn(self) }
+    } else {
+        quote! { #bevy_property_path::property_serde::Serializable::Borrowed(self) }
+    };
+    let serialize_fn = if let Some(serialize_fn) = &property_def.serialize_fn {
+        quote! {
+            #bevy_property_path::property_serde::Serializable::Owned(Box::new(#bevy_property_path::property_serde::CustomValueSerializer::new(self, #serialize_fn(self), registry)))
+        }
     } else {
         quote! {
             #bevy_property_path::property_serde::Serializable::Owned(Box::new(#bevy_property_path::property_serde::PropertyValueSerializer::new(self, registry)))
@@ -412,6 +577,11 @@ pub fn impl_property(input: TokenStream) -> TokenStream {
                 #serialize_fn
             }
 
+            #[inline]
+            fn serializable_value<'a>(&'a self, registry: &'a #bevy_property_path::PropertyTypeRegistry) -> #bevy_property_path::property_serde::Serializable<'a> {
+                #serialize_value_fn
+            }
+
             fn property_type(&self) -> #bevy_property_path::PropertyType {
                 #bevy_property_path::PropertyType::Value
             }
@@ -12,6 +12,8 @@ pub struct DynamicProperties {
     pub prop_names: Vec<Cow<'static, str>>,
     pub prop_indices: HashMap<Cow<'static, str>, usize>,
     pub property_type: PropertyType,
+    /// The active variant if these properties represent an enum. See [Properties::variant].
+    pub variant: Option<String>,
 }
 
 impl DynamicProperties {
@@ -22,6 +24,15 @@ impl DynamicProperties {
             prop_names: Default::default(),
             prop_indices: Default::default(),
             property_type: PropertyType::Map,
+            variant: None,
+        }
+    }
+
+    /// Creates a map of the fields of the given enum variant
+    pub fn map_variant(variant: &str) -> Self {
+        DynamicProperties {
+            variant: Some(variant.to_string()),
+            ..DynamicProperties::map()
         }
     }
 
@@ -32,6 +43,7 @@ impl DynamicProperties {
             prop_names: Default::default(),
             prop_indices: Default::default(),
             property_type: PropertyType::Seq,
+            variant: None,
         }
     }
 
@@ -62,6 +74,18 @@ impl DynamicProperties {
             self.push(prop, Some(name));
         }
     }
+
+    /// Renames the property `from` to `to`. Returns false if there is no property named `from`.
+    pub fn rename(&mut self, from: &str, to: &str) -> bool {
+        let index = match self.prop_indices.remove(from) {
+            Some(index) => index,
+            None => return false,
+        };
+        let name: Cow<'static, str> = Cow::Owned(to.to_string());
+        self.prop_names[index] = name.clone();
+        self.prop_indices.insert(name, index);
+        true
+    }
 }
 
 impl Properties for DynamicProperties {
@@ -113,6 +137,11 @@ impl Properties for DynamicProperties {
             index: 0,
         }
     }
+
+    #[inline]
+    fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
 }
 
 impl Property for DynamicProperties {
@@ -157,6 +186,12 @@ impl Property for DynamicProperties {
             }
             match self.property_type {
                 PropertyType::Map => {
+                    // switching to another variant replaces all fields
+                    if properties.variant().is_some() && properties.variant() != self.variant() {
+                        *self = properties.to_dynamic();
+                        return;
+                    }
+
                     for (i, prop) in properties.iter_props().enumerate() {
                         let name = properties.prop_name(i).unwrap();
                         if let Some(p) = self.prop_mut(name) {
@@ -182,6 +217,10 @@ impl Property for DynamicProperties {
         Some(self)
     }
 
+    fn as_properties_mut(&mut self) -> Option<&mut dyn Properties> {
+        Some(self)
+    }
+
     fn serializable<'a>(&'a self, registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
         Serializable::Owned(Box::new(DynamicPropertiesSerializer::new(self, registry)))
     }
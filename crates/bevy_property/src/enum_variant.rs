@@ -0,0 +1,63 @@
+use crate::Property;
+use std::marker::PhantomData;
+
+/// Creates the fields of an enum variant when `#[derive(Properties)]` enums switch to another variant in
+/// [Property::apply]. Fields that implement `Default` are created with it and then patched with the applied
+/// property. Other fields must implement `Clone` and are cloned from the applied property, so they can't be built if
+/// it is missing or has another type. The enum then keeps its current variant.
+///
+/// The derive calls `(&&VariantField::<T>::new()).build(property)`, which prefers [BuildWithDefault] over
+/// [BuildWithClone] through method resolution, so this only works for concrete field types.
+#[doc(hidden)]
+pub struct VariantField<T>(PhantomData<T>);
+
+impl<T> VariantField<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        VariantField(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait BuildWithDefault<T> {
+    fn build(&self, property: Option<&dyn Property>) -> Option<T>;
+}
+
+impl<T> BuildWithDefault<T> for &VariantField<T>
+where
+    T: Property + Default,
+{
+    fn build(&self, property: Option<&dyn Property>) -> Option<T> {
+        let mut value = T::default();
+        if let Some(property) = property {
+            value.apply(property);
+        }
+        Some(value)
+    }
+}
+
+#[doc(hidden)]
+pub trait BuildWithClone<T> {
+    fn build(&self, property: Option<&dyn Property>) -> Option<T>;
+}
+
+impl<T> BuildWithClone<T> for VariantField<T>
+where
+    T: Property + Clone,
+{
+    fn build(&self, property: Option<&dyn Property>) -> Option<T> {
+        property
+            .and_then(|property| property.any().downcast_ref::<T>())
+            .cloned()
+    }
+}
+
+/// Called by `#[derive(Properties)]` enums when the applied variant doesn't exist or one of its fields can't be built
+#[doc(hidden)]
+pub fn variant_not_applied(type_name: &str, variant: &str) {
+    log::warn!(
+        "{} could not switch to variant {}. The variant doesn't exist or is missing a field without a default value.",
+        type_name,
+        variant
+    );
+}
@@ -1,5 +1,8 @@
-use crate::{property_serde::Serializable, Property, PropertyType, PropertyTypeRegistry};
-use serde::Serialize;
+use crate::{
+    property_serde::{PropertyValueSerializer, Serializable},
+    DeserializeProperty, Property, PropertyType, PropertyTypeRegistry,
+};
+use serde::{Deserialize, Serialize};
 use smallvec::{Array, SmallVec};
 use std::any::Any;
 
@@ -40,7 +43,11 @@ where
         }
     }
 
-    fn serializable<'a>(&'a self, _registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
+    fn serializable<'a>(&'a self, registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
+        Serializable::Owned(Box::new(PropertyValueSerializer::new(self, registry)))
+    }
+
+    fn serializable_value<'a>(&'a self, _registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
         Serializable::Borrowed(self)
     }
 
@@ -48,3 +55,17 @@ where
         PropertyType::Value
     }
 }
+
+impl<T, I> DeserializeProperty for SmallVec<T>
+where
+    T: Clone + Send + Sync + Serialize + 'static + Array<Item = I>,
+    I: Send + Sync + Clone + Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn deserialize(
+        deserializer: &mut dyn erased_serde::Deserializer,
+        _property_type_registry: &PropertyTypeRegistry,
+    ) -> Result<Box<dyn Property>, erased_serde::Error> {
+        let property = <SmallVec<T> as Deserialize>::deserialize(deserializer)?;
+        Ok(Box::new(property))
+    }
+}
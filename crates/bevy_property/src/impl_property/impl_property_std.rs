@@ -24,11 +24,11 @@ where
     }
 
     fn prop_with_index(&self, index: usize) -> Option<&dyn Property> {
-        Some(&self[index])
+        self.get(index).map(|prop| prop as &dyn Property)
     }
 
     fn prop_with_index_mut(&mut self, index: usize) -> Option<&mut dyn Property> {
-        Some(&mut self[index])
+        self.get_mut(index).map(|prop| prop as &mut dyn Property)
     }
 
     fn prop_name(&self, _index: usize) -> Option<&str> {
@@ -94,6 +94,10 @@ where
         Some(self)
     }
 
+    fn as_properties_mut(&mut self) -> Option<&mut dyn Properties> {
+        Some(self)
+    }
+
     fn serializable<'a>(&'a self, registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
         Serializable::Owned(Box::new(SeqSerializer::new(self, registry)))
     }
@@ -196,6 +200,46 @@ impl Property for bool {
     }
 }
 
+impl Property for char {
+    #[inline]
+    fn type_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    #[inline]
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn clone_prop(&self) -> Box<dyn Property> {
+        Box::new(*self)
+    }
+
+    #[inline]
+    fn apply(&mut self, value: &dyn Property) {
+        self.set(value);
+    }
+
+    fn set(&mut self, value: &dyn Property) {
+        let value = value.any();
+        if let Some(prop) = value.downcast_ref::<Self>() {
+            *self = *prop;
+        } else {
+            panic!("prop value is not {}", std::any::type_name::<Self>());
+        }
+    }
+
+    fn serializable<'a>(&'a self, _registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
+        Serializable::Borrowed(self)
+    }
+}
+
 impl Property for usize {
     #[inline]
     fn type_name(&self) -> &str {
@@ -776,6 +820,130 @@ impl Property for i8 {
     }
 }
 
+impl Property for u128 {
+    #[inline]
+    fn type_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    #[inline]
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn clone_prop(&self) -> Box<dyn Property> {
+        Box::new(*self)
+    }
+
+    #[inline]
+    fn apply(&mut self, value: &dyn Property) {
+        self.set(value);
+    }
+
+    fn set(&mut self, value: &dyn Property) {
+        let value = value.any();
+        if let Some(prop) = value.downcast_ref::<Self>() {
+            *self = *prop;
+        } else if let Some(prop) = value.downcast_ref::<u64>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<usize>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<u32>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<u16>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<u8>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<i128>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<i64>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<isize>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<i32>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<i16>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<i8>() {
+            *self = *prop as Self;
+        } else {
+            panic!("prop value is not {}", std::any::type_name::<Self>());
+        }
+    }
+
+    fn serializable<'a>(&'a self, _registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
+        Serializable::Borrowed(self)
+    }
+}
+
+impl Property for i128 {
+    #[inline]
+    fn type_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    #[inline]
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn clone_prop(&self) -> Box<dyn Property> {
+        Box::new(*self)
+    }
+
+    #[inline]
+    fn apply(&mut self, value: &dyn Property) {
+        self.set(value);
+    }
+
+    fn set(&mut self, value: &dyn Property) {
+        let value = value.any();
+        if let Some(prop) = value.downcast_ref::<Self>() {
+            *self = *prop;
+        } else if let Some(prop) = value.downcast_ref::<i64>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<isize>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<i32>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<i16>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<i8>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<u128>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<u64>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<usize>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<u32>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<u16>() {
+            *self = *prop as Self;
+        } else if let Some(prop) = value.downcast_ref::<u8>() {
+            *self = *prop as Self;
+        } else {
+            panic!("prop value is not {}", std::any::type_name::<Self>());
+        }
+    }
+
+    fn serializable<'a>(&'a self, _registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
+        Serializable::Borrowed(self)
+    }
+}
+
 impl Property for f32 {
     #[inline]
     fn type_name(&self) -> &str {
@@ -0,0 +1,63 @@
+use crate::{property_serde, DynamicProperties, Properties, PropertyTypeRegistry};
+
+pub fn serialize_properties(
+    properties: &dyn Properties,
+    property_type_registry: &PropertyTypeRegistry,
+) -> Result<String, serde_json::Error> {
+    let mut buf = Vec::new();
+    let mut serializer = serde_json::Serializer::pretty(&mut buf);
+    property_serde::serialize_properties(properties, property_type_registry, &mut serializer)?;
+    Ok(String::from_utf8(buf).unwrap())
+}
+
+pub fn deserialize_dynamic_properties(
+    json_string: &str,
+    property_type_registry: &PropertyTypeRegistry,
+) -> Result<DynamicProperties, serde_json::Error> {
+    let mut deserializer = serde_json::Deserializer::from_str(json_string);
+    let dynamic_properties =
+        property_serde::deserialize_dynamic_properties(&mut deserializer, property_type_registry)?;
+    deserializer.end()?;
+    Ok(dynamic_properties)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PropertiesVal;
+
+    #[test]
+    fn json_round_trip() {
+        let registry = PropertyTypeRegistry::default();
+        let mut state = DynamicProperties::map_variant("Chasing");
+        state.type_name = "AiState".to_string();
+        state.set("speed", 2.5f32);
+        let mut character = DynamicProperties::map();
+        character.type_name = "Character".to_string();
+        character.set("name", "Ferris".to_string());
+        character.set("health", 1.0f32);
+        character.set("items", vec![3u32, 1, 4]);
+        character.set("state", state);
+
+        let json = serialize_properties(&character, &registry).unwrap();
+        assert!(json.contains("\"variant\": \"Chasing\""));
+        let dynamic_properties = deserialize_dynamic_properties(&json, &registry).unwrap();
+        assert_eq!(dynamic_properties.type_name, "Character");
+        assert_eq!(
+            dynamic_properties.prop_val::<String>("name"),
+            Some(&"Ferris".to_string())
+        );
+        let items = dynamic_properties
+            .prop_val::<DynamicProperties>("items")
+            .unwrap();
+        assert_eq!(items.prop_len(), 3);
+        let state = dynamic_properties
+            .prop_val::<DynamicProperties>("state")
+            .unwrap();
+        assert_eq!(state.variant.as_deref(), Some("Chasing"));
+        assert_eq!(
+            serialize_properties(&dynamic_properties, &registry).unwrap(),
+            json
+        );
+    }
+}
@@ -1,15 +1,22 @@
 pub mod impl_property;
+pub mod json;
 pub mod property_serde;
 pub mod ron;
 
 mod dynamic_properties;
+mod enum_variant;
 mod properties;
 mod property;
+mod property_method;
+mod property_path;
 mod type_registry;
 
 pub use dynamic_properties::*;
+pub use enum_variant::*;
 pub use properties::*;
 pub use property::*;
+pub use property_method::*;
+pub use property_path::*;
 pub use type_registry::*;
 
 pub use bevy_property_derive::*;
@@ -1,4 +1,7 @@
-use crate::{DynamicProperties, Property, PropertyType, PropertyVal};
+use crate::{
+    property_path::{get_path, get_path_mut},
+    DynamicProperties, Property, PropertyPath, PropertyPathError, PropertyType, PropertyVal,
+};
 
 pub trait Properties: Property {
     fn prop(&self, name: &str) -> Option<&dyn Property>;
@@ -8,6 +11,11 @@ pub trait Properties: Property {
     fn prop_name(&self, index: usize) -> Option<&str>;
     fn prop_len(&self) -> usize;
     fn iter_props(&self) -> PropertyIter;
+    /// The name of the active variant if these properties belong to an enum. The props are then the fields of
+    /// that variant, named after their index for tuple variants.
+    fn variant(&self) -> Option<&str> {
+        None
+    }
     fn set_prop(&mut self, name: &str, value: &dyn Property) {
         if let Some(prop) = self.prop_mut(name) {
             prop.set(value);
@@ -15,6 +23,33 @@ pub trait Properties: Property {
             panic!("prop does not exist: {}", name);
         }
     }
+    /// Returns the nested property at the given path
+    fn path(&self, path: &PropertyPath) -> Option<&dyn Property> {
+        get_path(self, path)
+    }
+    fn path_mut(&mut self, path: &PropertyPath) -> Option<&mut dyn Property> {
+        get_path_mut(self, path)
+    }
+    fn set_path(
+        &mut self,
+        path: &PropertyPath,
+        value: &dyn Property,
+    ) -> Result<(), PropertyPathError> {
+        match self.path_mut(path) {
+            Some(prop) if prop.type_name() == value.type_name() => {
+                prop.set(value);
+                Ok(())
+            }
+            Some(prop) => Err(PropertyPathError::TypeMismatch {
+                path: path.to_string(),
+                expected: prop.type_name().to_string(),
+                found: value.type_name().to_string(),
+            }),
+            None => Err(PropertyPathError::MissingProperty {
+                path: path.to_string(),
+            }),
+        }
+    }
     fn to_dynamic(&self) -> DynamicProperties {
         let mut dynamic_props = match self.property_type() {
             PropertyType::Map => {
@@ -38,6 +73,7 @@ pub trait Properties: Property {
         };
 
         dynamic_props.type_name = self.type_name().to_string();
+        dynamic_props.variant = self.variant().map(|variant| variant.to_string());
         dynamic_props
     }
 }
@@ -70,6 +106,7 @@ impl<'a> Iterator for PropertyIter<'a> {
 pub trait PropertiesVal {
     fn prop_val<T: 'static>(&self, name: &str) -> Option<&T>;
     fn set_prop_val<T: 'static>(&mut self, name: &str, value: T);
+    fn path_val<T: 'static>(&self, path: &PropertyPath) -> Option<&T>;
 }
 
 impl<P> PropertiesVal for P
@@ -89,4 +126,9 @@ where
             panic!("prop does not exist or is incorrect type: {}", name);
         }
     }
+
+    #[inline]
+    fn path_val<T: 'static>(&self, path: &PropertyPath) -> Option<&T> {
+        self.path(path).and_then(|p| p.any().downcast_ref::<T>())
+    }
 }
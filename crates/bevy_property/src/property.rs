@@ -23,7 +23,16 @@ pub trait Property: Send + Sync + Any + 'static {
     fn as_properties(&self) -> Option<&dyn Properties> {
         None
     }
+    fn as_properties_mut(&mut self) -> Option<&mut dyn Properties> {
+        None
+    }
     fn serializable<'a>(&'a self, registry: &'a PropertyTypeRegistry) -> Serializable<'a>;
+    /// Returns the value of a [PropertyType::Value] property without its type name, which [Property::serializable]
+    /// may include for self-describing formats. Formats that store type names separately use this instead. The value
+    /// is deserialized by the [DeserializeProperty] impl of the type.
+    fn serializable_value<'a>(&'a self, registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
+        self.serializable(registry)
+    }
 }
 
 pub trait DeserializeProperty {
@@ -0,0 +1,139 @@
+use crate::Property;
+use std::{any::TypeId, sync::Arc};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MethodCallError {
+    #[error("Method is not registered for this type.")]
+    UnknownMethod { type_name: String, method: String },
+    #[error("Method receiver does not exist.")]
+    MissingReceiver { type_name: &'static str },
+    #[error("Method was called on a property of another type.")]
+    ReceiverType {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("Method was called with the wrong number of arguments.")]
+    ArgumentCount { expected: usize, found: usize },
+    #[error("Method argument has the wrong type.")]
+    ArgumentType {
+        index: usize,
+        expected: &'static str,
+        found: String,
+    },
+}
+
+/// The value returned by a [PropertyMethod]. Methods without a return value return `None`.
+pub type MethodResult = Result<Option<Box<dyn Property>>, MethodCallError>;
+
+type MethodFn = dyn Fn(&mut dyn Property, &[&dyn Property]) -> MethodResult + Send + Sync;
+
+/// A method that can be called on properties of one type, with properties as arguments. Methods are registered
+/// with [PropertyTypeRegistry::register_method](crate::PropertyTypeRegistry::register_method).
+#[derive(Clone)]
+pub struct PropertyMethod {
+    pub name: String,
+    pub receiver_type_name: &'static str,
+    pub argument_type_names: Vec<&'static str>,
+    pub return_type_name: Option<&'static str>,
+    receiver_type: TypeId,
+    argument_types: Vec<TypeId>,
+    call_fn: Arc<MethodFn>,
+}
+
+impl PropertyMethod {
+    /// Calls the method after checking the types of `receiver` and `args`
+    pub fn call(&self, receiver: &mut dyn Property, args: &[&dyn Property]) -> MethodResult {
+        if receiver.any().type_id() != self.receiver_type {
+            return Err(MethodCallError::ReceiverType {
+                expected: self.receiver_type_name,
+                found: receiver.type_name().to_string(),
+            });
+        }
+        if args.len() != self.argument_types.len() {
+            return Err(MethodCallError::ArgumentCount {
+                expected: self.argument_types.len(),
+                found: args.len(),
+            });
+        }
+        for (index, arg) in args.iter().enumerate() {
+            if arg.any().type_id() != self.argument_types[index] {
+                return Err(MethodCallError::ArgumentType {
+                    index,
+                    expected: self.argument_type_names[index],
+                    found: arg.type_name().to_string(),
+                });
+            }
+        }
+
+        (self.call_fn)(receiver, args)
+    }
+}
+
+/// Return values of methods that can be registered as a [PropertyMethod]
+pub trait MethodReturn {
+    fn type_name() -> Option<&'static str>;
+    fn into_property(self) -> Option<Box<dyn Property>>;
+}
+
+impl MethodReturn for () {
+    fn type_name() -> Option<&'static str> {
+        None
+    }
+
+    fn into_property(self) -> Option<Box<dyn Property>> {
+        None
+    }
+}
+
+impl<T: Property> MethodReturn for T {
+    fn type_name() -> Option<&'static str> {
+        Some(std::any::type_name::<T>())
+    }
+
+    fn into_property(self) -> Option<Box<dyn Property>> {
+        Some(Box::new(self))
+    }
+}
+
+/// Functions taking `&mut T` and up to four arguments, which are cloned from the argument properties
+pub trait IntoPropertyMethod<T, Args> {
+    fn into_method(self, name: &str) -> PropertyMethod;
+}
+
+macro_rules! impl_into_property_method {
+    ($($arg:ident),*) => {
+        impl<T, R, F, $($arg,)*> IntoPropertyMethod<T, ($($arg,)*)> for F
+        where
+            T: Property,
+            R: MethodReturn,
+            F: Fn(&mut T, $($arg,)*) -> R + Send + Sync + 'static,
+            $($arg: Property + Clone,)*
+        {
+            #[allow(non_snake_case, unused_variables, unused_mut, unused_assignments)]
+            fn into_method(self, name: &str) -> PropertyMethod {
+                PropertyMethod {
+                    name: name.to_string(),
+                    receiver_type_name: std::any::type_name::<T>(),
+                    argument_type_names: vec![$(std::any::type_name::<$arg>(),)*],
+                    return_type_name: R::type_name(),
+                    receiver_type: TypeId::of::<T>(),
+                    argument_types: vec![$(TypeId::of::<$arg>(),)*],
+                    call_fn: Arc::new(move |receiver: &mut dyn Property, args: &[&dyn Property]| {
+                        // the types were checked by PropertyMethod::call
+                        let receiver = receiver.any_mut().downcast_mut::<T>().unwrap();
+                        let mut args = args.iter();
+                        $(let $arg = args.next().unwrap().any().downcast_ref::<$arg>().unwrap().clone();)*
+                        Ok(self(receiver, $($arg,)*).into_property())
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_into_property_method!();
+impl_into_property_method!(A);
+impl_into_property_method!(A, B);
+impl_into_property_method!(A, B, C);
+impl_into_property_method!(A, B, C, D);
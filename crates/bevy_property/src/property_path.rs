@@ -0,0 +1,339 @@
+use crate::{DynamicProperties, Properties, Property, PropertyType};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PropertyPathError {
+    #[error("Property path could not be parsed.")]
+    InvalidPath { path: String },
+    #[error("Property path does not refer to an existing property.")]
+    MissingProperty { path: String },
+    #[error("Property at path has a different type than the value.")]
+    TypeMismatch {
+        path: String,
+        expected: String,
+        found: String,
+    },
+    #[error("Patched component or resource does not exist.")]
+    MissingReceiver { type_name: &'static str },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// A prop of a [PropertyType::Map], accessed with [Properties::prop]
+    Field(String),
+    /// An element of a [PropertyType::Seq], accessed with [Properties::prop_with_index]
+    Index(usize),
+}
+
+impl PathSegment {
+    fn get<'a, P: Properties + ?Sized>(&self, properties: &'a P) -> Option<&'a dyn Property> {
+        match (self, properties.property_type()) {
+            (PathSegment::Field(name), PropertyType::Map) => properties.prop(name),
+            (PathSegment::Index(index), PropertyType::Seq) => properties.prop_with_index(*index),
+            _ => None,
+        }
+    }
+
+    fn get_mut<'a, P: Properties + ?Sized>(
+        &self,
+        properties: &'a mut P,
+    ) -> Option<&'a mut dyn Property> {
+        match (self, properties.property_type()) {
+            (PathSegment::Field(name), PropertyType::Map) => properties.prop_mut(name),
+            (PathSegment::Index(index), PropertyType::Seq) => {
+                properties.prop_with_index_mut(*index)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The path of a nested property, like `transform.translation.x` or `items[3].count`. Fields are separated by `.`
+/// and sequence elements are accessed with `[index]`. Tuple fields are named after their index, like `velocity.0`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PropertyPath {
+    segments: Vec<PathSegment>,
+}
+
+impl PropertyPath {
+    pub fn parse(path: &str) -> Result<Self, PropertyPathError> {
+        let invalid = || PropertyPathError::InvalidPath {
+            path: path.to_string(),
+        };
+        let mut segments = Vec::new();
+        let mut rest = path;
+        loop {
+            if let Some(index_start) = rest.strip_prefix('[') {
+                let end = index_start.find(']').ok_or_else(invalid)?;
+                let index = index_start[..end].parse().map_err(|_| invalid())?;
+                segments.push(PathSegment::Index(index));
+                rest = &index_start[end + 1..];
+            } else {
+                let field_start = if segments.is_empty() {
+                    rest
+                } else {
+                    rest.strip_prefix('.').ok_or_else(invalid)?
+                };
+                let end = field_start
+                    .find(&['.', '[', ']'][..])
+                    .unwrap_or(field_start.len());
+                if end == 0 {
+                    return Err(invalid());
+                }
+                segments.push(PathSegment::Field(field_start[..end].to_string()));
+                rest = &field_start[end..];
+            }
+
+            if rest.is_empty() {
+                return Ok(PropertyPath { segments });
+            }
+        }
+    }
+
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+}
+
+impl FromStr for PropertyPath {
+    type Err = PropertyPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        PropertyPath::parse(path)
+    }
+}
+
+impl fmt::Display for PropertyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) if i == 0 => write!(f, "{}", name)?,
+                PathSegment::Field(name) => write!(f, ".{}", name)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn get_path<'a, P: Properties + ?Sized>(
+    properties: &'a P,
+    path: &PropertyPath,
+) -> Option<&'a dyn Property> {
+    let (first, rest) = path.segments.split_first()?;
+    let mut property = first.get(properties)?;
+    for segment in rest {
+        property = segment.get(property.as_properties()?)?;
+    }
+    Some(property)
+}
+
+pub(crate) fn get_path_mut<'a, P: Properties + ?Sized>(
+    properties: &'a mut P,
+    path: &PropertyPath,
+) -> Option<&'a mut dyn Property> {
+    let (first, rest) = path.segments.split_first()?;
+    let mut property = first.get_mut(properties)?;
+    for segment in rest {
+        property = segment.get_mut(property.as_properties_mut()?)?;
+    }
+    Some(property)
+}
+
+/// Changes to nested properties, addressed by their [PropertyPath]. Unlike applying [DynamicProperties], a patch
+/// can change single elements of sequences. It can be applied to any [Properties] that contain its paths.
+#[derive(Default)]
+pub struct PropertyPatch {
+    changes: Vec<(PropertyPath, Box<dyn Property>)>,
+}
+
+impl PropertyPatch {
+    pub fn new() -> Self {
+        PropertyPatch::default()
+    }
+
+    pub fn with<T: Property>(mut self, path: PropertyPath, value: T) -> Self {
+        self.set(path, value);
+        self
+    }
+
+    pub fn set<T: Property>(&mut self, path: PropertyPath, value: T) {
+        self.set_box(path, Box::new(value));
+    }
+
+    /// Sets the value of the given path, replacing an earlier change of the same path
+    pub fn set_box(&mut self, path: PropertyPath, value: Box<dyn Property>) {
+        match self
+            .changes
+            .iter_mut()
+            .find(|(change_path, _)| *change_path == path)
+        {
+            Some((_, change)) => *change = value,
+            None => self.changes.push((path, value)),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&PropertyPath, &dyn Property)> {
+        self.changes.iter().map(|(path, value)| (path, &**value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Sets each changed property with [Property::set]. If a path doesn't exist or its property has a different type
+    /// than the value, nothing is changed.
+    pub fn apply(&self, properties: &mut dyn Properties) -> Result<(), PropertyPathError> {
+        for (path, value) in self.changes.iter() {
+            let property =
+                properties
+                    .path(path)
+                    .ok_or_else(|| PropertyPathError::MissingProperty {
+                        path: path.to_string(),
+                    })?;
+            if property.type_name() != value.type_name() {
+                return Err(PropertyPathError::TypeMismatch {
+                    path: path.to_string(),
+                    expected: property.type_name().to_string(),
+                    found: value.type_name().to_string(),
+                });
+            }
+        }
+
+        for (path, value) in self.changes.iter() {
+            properties.path_mut(path).unwrap().set(&**value);
+        }
+        Ok(())
+    }
+
+    /// Converts the patch to a map from path strings to values, which can be serialized like other
+    /// [DynamicProperties]
+    pub fn to_dynamic(&self) -> DynamicProperties {
+        let mut dynamic_properties = DynamicProperties::map();
+        dynamic_properties.type_name = std::any::type_name::<Self>().to_string();
+        for (path, value) in self.changes.iter() {
+            dynamic_properties.set_box(&path.to_string(), value.clone_prop());
+        }
+        dynamic_properties
+    }
+
+    /// Reads a patch from a map created by [PropertyPatch::to_dynamic]
+    pub fn from_dynamic(dynamic_properties: &DynamicProperties) -> Result<Self, PropertyPathError> {
+        let mut patch = PropertyPatch::new();
+        for (i, value) in dynamic_properties.iter_props().enumerate() {
+            let path = dynamic_properties.prop_name(i).unwrap_or_default();
+            patch.set_box(path.parse()?, value.clone_prop());
+        }
+        Ok(patch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PropertiesVal;
+
+    fn path(path: &str) -> PropertyPath {
+        path.parse().unwrap()
+    }
+
+    fn character() -> DynamicProperties {
+        let mut item = DynamicProperties::map();
+        item.set("count", 1u32);
+        let mut items = DynamicProperties::seq();
+        for _ in 0..4 {
+            items.push(Box::new(item.to_dynamic()), None);
+        }
+        let mut character = DynamicProperties::map();
+        character.set("items", items);
+        character.set("health", vec![1.0f32, 2.0]);
+        character
+    }
+
+    #[test]
+    fn parse_paths() {
+        assert_eq!(
+            path("items[3].count").segments(),
+            &[
+                PathSegment::Field("items".to_string()),
+                PathSegment::Index(3),
+                PathSegment::Field("count".to_string()),
+            ]
+        );
+        for valid in &["a", "a.b.0", "[2]", "a[0][1].b"] {
+            assert_eq!(path(valid).to_string(), *valid);
+        }
+        for invalid in &[
+            "", "a.", ".a", "a..b", "a[", "a[x]", "a[-1]", "a]b", "a[0]b",
+        ] {
+            assert_eq!(
+                PropertyPath::parse(invalid),
+                Err(PropertyPathError::InvalidPath {
+                    path: invalid.to_string()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn get_and_set_by_path() {
+        let mut character = character();
+        assert_eq!(character.path_val::<u32>(&path("items[3].count")), Some(&1));
+        assert_eq!(character.path_val::<f32>(&path("health[1]")), Some(&2.0));
+        assert!(character.path(&path("items[4].count")).is_none());
+        assert!(character.path(&path("items.count")).is_none());
+        assert!(character.path(&path("health[2]")).is_none());
+
+        character.set_path(&path("items[3].count"), &5u32).unwrap();
+        character.set_path(&path("health[0]"), &0.5f32).unwrap();
+        assert_eq!(character.path_val::<u32>(&path("items[3].count")), Some(&5));
+        assert_eq!(character.path_val::<u32>(&path("items[2].count")), Some(&1));
+        assert_eq!(character.path_val::<f32>(&path("health[0]")), Some(&0.5));
+        assert_eq!(
+            character.set_path(&path("items[3].name"), &5u32),
+            Err(PropertyPathError::MissingProperty {
+                path: "items[3].name".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn apply_patch() {
+        let patch = PropertyPatch::new()
+            .with(path("items[1].count"), 7u32)
+            .with(path("health[1]"), 3.0f32);
+        let patch = PropertyPatch::from_dynamic(&patch.to_dynamic()).unwrap();
+        assert_eq!(patch.len(), 2);
+
+        let mut character = character();
+        patch.apply(&mut character).unwrap();
+        assert_eq!(character.path_val::<u32>(&path("items[1].count")), Some(&7));
+        assert_eq!(character.path_val::<f32>(&path("health[1]")), Some(&3.0));
+
+        // patches with a missing path don't change anything
+        let patch = PropertyPatch::new()
+            .with(path("items[0].count"), 9u32)
+            .with(path("mana"), 1.0f32);
+        assert!(patch.apply(&mut character).is_err());
+        assert_eq!(character.path_val::<u32>(&path("items[0].count")), Some(&1));
+
+        // so do patches with a value of the wrong type
+        let patch = PropertyPatch::new()
+            .with(path("items[0].count"), 9u32)
+            .with(path("health[0]"), "full".to_string());
+        assert_eq!(
+            patch.apply(&mut character),
+            Err(PropertyPathError::TypeMismatch {
+                path: "health[0]".to_string(),
+                expected: "f32".to_string(),
+                found: "alloc::string::String".to_string(),
+            })
+        );
+        assert_eq!(character.path_val::<u32>(&path("items[0].count")), Some(&1));
+    }
+}
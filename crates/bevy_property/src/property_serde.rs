@@ -8,6 +8,7 @@ use serde::{
 
 pub const TYPE_FIELD: &str = "type";
 pub const MAP_FIELD: &str = "map";
+pub const VARIANT_FIELD: &str = "variant";
 pub const SEQ_FIELD: &str = "seq";
 pub const VALUE_FIELD: &str = "value";
 
@@ -25,6 +26,34 @@ impl<'a> Serializable<'a> {
         }
     }
 }
+
+/// Serializes properties with any serde format. The output has the same layout as the properties'
+/// [DynamicProperties], which can be read back with [deserialize_dynamic_properties].
+pub fn serialize_properties<S>(
+    properties: &dyn Properties,
+    registry: &PropertyTypeRegistry,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    properties
+        .serializable(registry)
+        .borrow()
+        .serialize(serializer)
+}
+
+/// Deserializes [DynamicProperties] with any serde format that supports `deserialize_any`, like RON or JSON
+pub fn deserialize_dynamic_properties<'de, D>(
+    deserializer: D,
+    registry: &PropertyTypeRegistry,
+) -> Result<DynamicProperties, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    DynamicPropertiesDeserializer::new(registry).deserialize(deserializer)
+}
+
 pub struct PropertyValueSerializer<'a, T>
 where
     T: Property + Serialize,
@@ -60,6 +89,42 @@ where
     }
 }
 
+/// Serializes the value of a property with a custom serialize function along with its type name
+pub struct CustomValueSerializer<'a> {
+    pub property: &'a dyn Property,
+    pub value: Serializable<'a>,
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> CustomValueSerializer<'a> {
+    pub fn new(
+        property: &'a dyn Property,
+        value: Serializable<'a>,
+        registry: &'a PropertyTypeRegistry,
+    ) -> Self {
+        CustomValueSerializer {
+            property,
+            value,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for CustomValueSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(2))?;
+        state.serialize_entry(
+            TYPE_FIELD,
+            format_type_name(self.registry, self.property.type_name()),
+        )?;
+        state.serialize_entry(VALUE_FIELD, self.value.borrow())?;
+        state.end()
+    }
+}
+
 pub struct DynamicPropertiesSerializer<'a> {
     pub dynamic_properties: &'a DynamicProperties,
     pub registry: &'a PropertyTypeRegistry,
@@ -119,12 +184,16 @@ impl<'a> Serialize for MapSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_map(Some(2))?;
+        let variant = self.properties.variant();
+        let mut state = serializer.serialize_map(Some(if variant.is_some() { 3 } else { 2 }))?;
 
         state.serialize_entry(
             TYPE_FIELD,
             format_type_name(self.registry, self.properties.type_name()),
         )?;
+        if let Some(variant) = variant {
+            state.serialize_entry(VARIANT_FIELD, variant)?;
+        }
         state.serialize_entry(
             MAP_FIELD,
             &MapValueSerializer {
@@ -417,6 +486,13 @@ impl<'a, 'de> Visitor<'de> for AnyPropVisiter<'a> {
         Ok(Box::new(v))
     }
 
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Box::new(v))
+    }
+
     fn visit_i8<E>(self, v: i8) -> Result<Self::Value, E>
     where
         E: de::Error,
@@ -445,6 +521,13 @@ impl<'a, 'de> Visitor<'de> for AnyPropVisiter<'a> {
         Ok(Box::new(v))
     }
 
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Box::new(v))
+    }
+
     fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
     where
         E: de::Error,
@@ -459,6 +542,13 @@ impl<'a, 'de> Visitor<'de> for AnyPropVisiter<'a> {
         Ok(Box::new(v))
     }
 
+    fn visit_char<E>(self, v: char) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Box::new(v))
+    }
+
     fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
     where
         E: de::Error,
@@ -497,11 +587,15 @@ where
     V: MapAccess<'de>,
 {
     let mut type_name: Option<String> = None;
+    let mut variant: Option<String> = None;
     while let Some(key) = map.next_key::<String>()? {
         match key.as_str() {
             TYPE_FIELD => {
                 type_name = Some(map.next_value()?);
             }
+            VARIANT_FIELD => {
+                variant = Some(map.next_value()?);
+            }
             MAP_FIELD => {
                 let type_name = type_name
                     .take()
@@ -509,6 +603,7 @@ where
                 let mut dynamic_properties =
                     map.next_value_seed(MapPropertyDeserializer { registry })?;
                 dynamic_properties.type_name = type_name;
+                dynamic_properties.variant = variant;
                 return Ok(DynamicPropertiesOrProperty::DynamicProperties(
                     dynamic_properties,
                 ));
@@ -1,15 +1,28 @@
 use crate::{
-    property_serde::DynamicPropertiesDeserializer, DynamicProperties, PropertyTypeRegistry,
+    property_serde::{self, DynamicPropertiesSerializer},
+    DynamicProperties, Properties, PropertyTypeRegistry,
 };
 use ron::de::Deserializer;
-use serde::de::DeserializeSeed;
+use serde::Serialize;
+
+pub fn serialize_properties(
+    properties: &dyn Properties,
+    property_type_registry: &PropertyTypeRegistry,
+) -> Result<String, ron::Error> {
+    let dynamic_properties = properties.to_dynamic();
+    let mut buf = Vec::new();
+    // floats must keep their decimal point, otherwise they are deserialized as integers
+    let config = ron::ser::PrettyConfig::default().with_decimal_floats(true);
+    let mut serializer = ron::ser::Serializer::new(&mut buf, Some(config), false)?;
+    DynamicPropertiesSerializer::new(&dynamic_properties, property_type_registry)
+        .serialize(&mut serializer)?;
+    Ok(String::from_utf8(buf).unwrap())
+}
 
 pub fn deserialize_dynamic_properties(
     ron_string: &str,
     property_type_registry: &PropertyTypeRegistry,
 ) -> Result<DynamicProperties, ron::Error> {
-    let mut deserializer = Deserializer::from_str(&ron_string).unwrap();
-    let dynamic_properties_deserializer =
-        DynamicPropertiesDeserializer::new(&property_type_registry);
-    dynamic_properties_deserializer.deserialize(&mut deserializer)
+    let mut deserializer = Deserializer::from_str(ron_string)?;
+    property_serde::deserialize_dynamic_properties(&mut deserializer, property_type_registry)
 }
@@ -1,4 +1,7 @@
-use crate::{DeserializeProperty, Property};
+use crate::{
+    DeserializeProperty, IntoPropertyMethod, MethodCallError, MethodResult, Property,
+    PropertyMethod,
+};
 use bevy_utils::{HashMap, HashSet};
 use std::any::TypeId;
 
@@ -7,6 +10,7 @@ pub struct PropertyTypeRegistry {
     registrations: HashMap<String, PropertyTypeRegistration>,
     short_names: HashMap<String, String>,
     ambigous_names: HashSet<String>,
+    methods: HashMap<&'static str, HashMap<String, PropertyMethod>>,
 }
 
 impl PropertyTypeRegistry {
@@ -18,6 +22,25 @@ impl PropertyTypeRegistry {
         self.add_registration(registration);
     }
 
+    /// Removes the registration with the given full `type_name`. Short names that were ambiguous because of the
+    /// removed type become available again.
+    pub fn unregister(&mut self, type_name: &str) -> Option<PropertyTypeRegistration> {
+        let registration = self.registrations.remove(type_name)?;
+        self.methods.remove(type_name);
+        let registrations = std::mem::take(&mut self.registrations);
+        self.short_names.clear();
+        self.ambigous_names.clear();
+        for (_, registration) in registrations {
+            self.add_registration(registration);
+        }
+
+        Some(registration)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PropertyTypeRegistration> {
+        self.registrations.values()
+    }
+
     fn add_registration(&mut self, registration: PropertyTypeRegistration) {
         let short_name = registration.short_name.to_string();
         if self.short_names.contains_key(&short_name) || self.ambigous_names.contains(&short_name) {
@@ -40,6 +63,61 @@ impl PropertyTypeRegistry {
         }
     }
 
+    /// Registers a method that can be called on properties of type `T` with [PropertyTypeRegistry::call_method].
+    /// Methods are opt-in and looked up by name, so `method` doesn't need to be a method of `T`:
+    ///
+    /// ```ignore
+    /// registry.register_method("reset", Timer::reset);
+    /// registry.register_method("set_duration", |timer: &mut Timer, duration: f32| timer.duration = duration);
+    /// ```
+    pub fn register_method<T, Args, F>(&mut self, name: &str, method: F)
+    where
+        T: Property,
+        F: IntoPropertyMethod<T, Args>,
+    {
+        self.methods
+            .entry(std::any::type_name::<T>())
+            .or_default()
+            .insert(name.to_string(), method.into_method(name));
+    }
+
+    pub fn get_method(&self, type_name: &str, name: &str) -> Option<&PropertyMethod> {
+        let type_name = self
+            .get(type_name)
+            .map(|registration| registration.name)
+            .unwrap_or(type_name);
+        self.methods
+            .get(type_name)
+            .and_then(|methods| methods.get(name))
+    }
+
+    /// Returns the methods registered for the type with the given full name
+    pub fn iter_methods<'a>(&'a self, type_name: &str) -> impl Iterator<Item = &'a PropertyMethod> {
+        self.methods
+            .get(type_name)
+            .into_iter()
+            .flat_map(|methods| methods.values())
+    }
+
+    /// Calls the method `name` on `receiver`. The types of the receiver and the arguments are checked before the
+    /// method is called.
+    pub fn call_method(
+        &self,
+        receiver: &mut dyn Property,
+        name: &str,
+        args: &[&dyn Property],
+    ) -> MethodResult {
+        let method = self
+            .methods
+            .get(receiver.type_name())
+            .and_then(|methods| methods.get(name))
+            .ok_or_else(|| MethodCallError::UnknownMethod {
+                type_name: receiver.type_name().to_string(),
+                method: name.to_string(),
+            })?;
+        method.call(receiver, args)
+    }
+
     pub fn format_type_name(&self, type_name: &str) -> Option<&str> {
         self.get(type_name).map(|registration| {
             if self.short_names.contains_key(&registration.short_name) {
@@ -141,9 +219,88 @@ impl PropertyTypeRegistration {
 
 #[cfg(test)]
 mod test {
-    use crate::PropertyTypeRegistration;
+    use crate::{MethodCallError, PropertyTypeRegistration, PropertyTypeRegistry};
     use std::collections::HashMap;
 
+    #[test]
+    fn test_unregister() {
+        let mut registry = PropertyTypeRegistry::default();
+        let mut f32_registration = PropertyTypeRegistration::of::<Option<f32>>();
+        f32_registration.short_name = "Float".to_string();
+        registry.add_registration(f32_registration);
+        let mut f64_registration = PropertyTypeRegistration::of::<Option<f64>>();
+        f64_registration.short_name = "Float".to_string();
+        registry.add_registration(f64_registration);
+        assert!(registry.get_with_short_name("Float").is_none());
+
+        let removed = std::any::type_name::<Option<f64>>();
+        assert!(registry.unregister(removed).is_some());
+        assert!(registry.get(removed).is_none());
+        assert_eq!(
+            registry.get_with_short_name("Float").unwrap().name,
+            std::any::type_name::<Option<f32>>()
+        );
+    }
+
+    #[test]
+    fn test_call_method() {
+        let mut registry = PropertyTypeRegistry::default();
+        registry.register_method("clear", Vec::<u32>::clear);
+        registry.register_method("get", |items: &mut Vec<u32>, index: usize| items[index]);
+        registry.register_method("insert", |items: &mut Vec<u32>, index: usize, item: u32| {
+            items.insert(index, item)
+        });
+        let method = registry
+            .get_method(std::any::type_name::<Vec<u32>>(), "insert")
+            .unwrap();
+        assert_eq!(method.argument_type_names, vec!["usize", "u32"]);
+        assert_eq!(method.return_type_name, None);
+
+        let mut items = vec![1u32, 2, 3];
+        assert!(registry
+            .call_method(&mut items, "insert", &[&1usize, &5u32])
+            .unwrap()
+            .is_none());
+        let item = registry
+            .call_method(&mut items, "get", &[&1usize])
+            .unwrap()
+            .unwrap();
+        assert_eq!(item.any().downcast_ref::<u32>(), Some(&5));
+
+        assert_eq!(
+            registry.call_method(&mut items, "get", &[&1u32]).err(),
+            Some(MethodCallError::ArgumentType {
+                index: 0,
+                expected: "usize",
+                found: "u32".to_string()
+            })
+        );
+        assert_eq!(
+            registry.call_method(&mut items, "get", &[]).err(),
+            Some(MethodCallError::ArgumentCount {
+                expected: 1,
+                found: 0
+            })
+        );
+        assert_eq!(
+            registry.call_method(&mut 1u32, "get", &[&1usize]).err(),
+            Some(MethodCallError::UnknownMethod {
+                type_name: "u32".to_string(),
+                method: "get".to_string()
+            })
+        );
+        assert_eq!(
+            method.call(&mut 1u32, &[&1usize, &5u32]).err(),
+            Some(MethodCallError::ReceiverType {
+                expected: std::any::type_name::<Vec<u32>>(),
+                found: "u32".to_string()
+            })
+        );
+
+        registry.call_method(&mut items, "clear", &[]).unwrap();
+        assert!(items.is_empty());
+    }
+
     #[test]
     fn test_get_short_name() {
         assert_eq!(
@@ -142,7 +142,7 @@ impl<'a> UnsafeClone for DrawContext<'a> {
             render_resource_context: self.render_resource_context.unsafe_clone(),
             vertex_buffer_descriptors: self.vertex_buffer_descriptors.unsafe_clone(),
             shared_buffers: self.shared_buffers.unsafe_clone(),
-            current_pipeline: self.current_pipeline,
+            current_pipeline: self.current_pipeline.clone(),
         }
     }
 }
@@ -250,7 +250,7 @@ impl<'a> DrawContext<'a> {
     pub fn set_pipeline(
         &mut self,
         draw: &mut Draw,
-        pipeline_handle: Handle<PipelineDescriptor>,
+        pipeline_handle: &Handle<PipelineDescriptor>,
         specialization: &PipelineSpecialization,
     ) -> Result<(), DrawError> {
         let specialized_pipeline = if let Some(specialized_pipeline) = self
@@ -263,20 +263,21 @@ impl<'a> DrawContext<'a> {
                 &**self.render_resource_context,
                 &mut self.pipelines,
                 &mut self.shaders,
-                pipeline_handle,
+                pipeline_handle.clone_weak(),
                 &self.vertex_buffer_descriptors,
                 specialization,
             )
         };
 
-        draw.set_pipeline(specialized_pipeline);
+        draw.set_pipeline(specialized_pipeline.clone_weak());
         self.current_pipeline = Some(specialized_pipeline);
         Ok(())
     }
 
     pub fn get_pipeline_descriptor(&self) -> Result<&PipelineDescriptor, DrawError> {
         self.current_pipeline
-            .and_then(|handle| self.pipelines.get(&handle))
+            .as_ref()
+            .and_then(|handle| self.pipelines.get(handle))
             .ok_or(DrawError::NoPipelineSet)
     }
 
@@ -293,10 +294,13 @@ impl<'a> DrawContext<'a> {
         draw: &mut Draw,
         render_resource_bindings: &mut [&mut RenderResourceBindings],
     ) -> Result<(), DrawError> {
-        let pipeline = self.current_pipeline.ok_or(DrawError::NoPipelineSet)?;
+        let pipeline = self
+            .current_pipeline
+            .as_ref()
+            .ok_or(DrawError::NoPipelineSet)?;
         let pipeline_descriptor = self
             .pipelines
-            .get(&pipeline)
+            .get(pipeline)
             .ok_or(DrawError::NonExistentPipeline)?;
         let layout = pipeline_descriptor
             .get_layout()
@@ -323,10 +327,13 @@ impl<'a> DrawContext<'a> {
         index: u32,
         bind_group: &BindGroup,
     ) -> Result<(), DrawError> {
-        let pipeline = self.current_pipeline.ok_or(DrawError::NoPipelineSet)?;
+        let pipeline = self
+            .current_pipeline
+            .as_ref()
+            .ok_or(DrawError::NoPipelineSet)?;
         let pipeline_descriptor = self
             .pipelines
-            .get(&pipeline)
+            .get(pipeline)
             .ok_or(DrawError::NonExistentPipeline)?;
         let layout = pipeline_descriptor
             .get_layout()
@@ -343,10 +350,13 @@ impl<'a> DrawContext<'a> {
         render_resource_bindings: &[&RenderResourceBindings],
     ) -> Result<Option<Range<u32>>, DrawError> {
         let mut indices = None;
-        let pipeline = self.current_pipeline.ok_or(DrawError::NoPipelineSet)?;
+        let pipeline = self
+            .current_pipeline
+            .as_ref()
+            .ok_or(DrawError::NoPipelineSet)?;
         let pipeline_descriptor = self
             .pipelines
-            .get(&pipeline)
+            .get(pipeline)
             .ok_or(DrawError::NonExistentPipeline)?;
         let layout = pipeline_descriptor
             .get_layout()
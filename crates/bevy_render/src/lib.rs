@@ -31,11 +31,14 @@ use crate::prelude::*;
 use base::{MainPass, Msaa};
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
+#[cfg(any(feature = "png", feature = "hdr"))]
+use bevy_asset::LoadAndSave;
 use bevy_ecs::{IntoQuerySystem, IntoThreadLocalSystem};
 use bevy_type_registry::RegisterType;
 use camera::{
     ActiveCameras, Camera, OrthographicProjection, PerspectiveProjection, VisibleEntities,
 };
+use mesh::{BinaryMeshLoader, BinaryMeshSaver};
 use pipeline::{
     DynamicBinding, PipelineCompiler, PipelineDescriptor, PipelineSpecialization,
     PrimitiveTopology, ShaderSpecialization, VertexBufferDescriptors,
@@ -50,7 +53,7 @@ use std::ops::Range;
 use texture::HdrTextureLoader;
 #[cfg(feature = "png")]
 use texture::ImageTextureLoader;
-use texture::TextureResourceSystemState;
+use texture::{BinaryTextureLoader, BinaryTextureSaver, TextureResourceSystemState};
 
 /// The names of "render" App stages
 pub mod stage {
@@ -82,11 +85,16 @@ impl Plugin for RenderPlugin {
     fn build(&self, app: &mut AppBuilder) {
         #[cfg(feature = "png")]
         {
-            app.add_asset_loader::<Texture, ImageTextureLoader>();
+            type ImageTextureProcessor =
+                LoadAndSave<Texture, ImageTextureLoader, BinaryTextureSaver>;
+            app.add_asset_loader::<Texture, ImageTextureLoader>()
+                .add_asset_processor::<ImageTextureProcessor>();
         }
         #[cfg(feature = "hdr")]
         {
-            app.add_asset_loader::<Texture, HdrTextureLoader>();
+            type HdrTextureProcessor = LoadAndSave<Texture, HdrTextureLoader, BinaryTextureSaver>;
+            app.add_asset_loader::<Texture, HdrTextureLoader>()
+                .add_asset_processor::<HdrTextureProcessor>();
         }
 
         app.add_stage_after(bevy_asset::stage::ASSET_EVENTS, stage::RENDER_RESOURCE)
@@ -98,6 +106,10 @@ impl Plugin for RenderPlugin {
             .add_asset::<Texture>()
             .add_asset::<Shader>()
             .add_asset::<PipelineDescriptor>()
+            .add_asset_loader::<Mesh, BinaryMeshLoader>()
+            .add_asset_loader::<Texture, BinaryTextureLoader>()
+            .add_asset_saver::<Mesh, BinaryMeshSaver>()
+            .add_asset_saver::<Texture, BinaryTextureSaver>()
             .register_component::<Camera>()
             .register_component::<Draw>()
             .register_component::<RenderPipelines>()
@@ -0,0 +1,70 @@
+use super::Mesh;
+use anyhow::Result;
+use bevy_asset::{AssetLoader, AssetSaver, LoaderSettings};
+use std::path::Path;
+
+/// Loads meshes that were written by a [BinaryMeshSaver]
+#[derive(Clone, Default)]
+pub struct BinaryMeshLoader;
+
+impl AssetLoader<Mesh> for BinaryMeshLoader {
+    fn from_bytes(
+        &self,
+        _asset_path: &Path,
+        bytes: Vec<u8>,
+        _settings: &LoaderSettings,
+    ) -> Result<Mesh> {
+        Ok(bincode::deserialize(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["mesh"];
+        EXTENSIONS
+    }
+}
+
+/// Saves meshes in a binary format that loads faster than source formats like GLTF. This is used to process meshes.
+#[derive(Clone, Default)]
+pub struct BinaryMeshSaver;
+
+impl AssetSaver<Mesh> for BinaryMeshSaver {
+    fn save(&self, mesh: &Mesh) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(mesh)?)
+    }
+
+    fn extension(&self) -> &str {
+        "mesh"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mesh::VertexAttribute, pipeline::PrimitiveTopology};
+
+    #[test]
+    fn save_and_load() {
+        let mesh = Mesh {
+            primitive_topology: PrimitiveTopology::TriangleList,
+            attributes: vec![
+                VertexAttribute::position(vec![[0., 1., 2.], [3., 4., 5.], [6., 7., 8.]]),
+                VertexAttribute::uv(vec![[0., 1.], [2., 3.], [4., 5.]]),
+            ],
+            indices: Some(vec![0, 1, 2]),
+        };
+
+        let bytes = BinaryMeshSaver.save(&mesh).unwrap();
+        let loaded_mesh = BinaryMeshLoader
+            .from_bytes(Path::new("a.mesh"), bytes, &LoaderSettings::default())
+            .unwrap();
+        assert_eq!(loaded_mesh.primitive_topology, mesh.primitive_topology);
+        assert_eq!(loaded_mesh.indices, mesh.indices);
+        for (loaded_attribute, attribute) in loaded_mesh.attributes.iter().zip(&mesh.attributes) {
+            assert_eq!(loaded_attribute.name, attribute.name);
+            assert_eq!(
+                loaded_attribute.values.get_bytes(),
+                attribute.values.get_bytes()
+            );
+        }
+    }
+}
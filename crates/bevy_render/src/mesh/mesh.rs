@@ -12,12 +12,13 @@ use bevy_core::AsBytes;
 use bevy_ecs::{Local, Query, Res, ResMut};
 use bevy_math::*;
 use bevy_utils::HashSet;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use thiserror::Error;
 
 pub const VERTEX_BUFFER_ASSET_INDEX: usize = 0;
 pub const INDEX_BUFFER_ASSET_INDEX: usize = 1;
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum VertexAttributeValues {
     Float(Vec<f32>),
     Float2(Vec<[f32; 2]>),
@@ -61,7 +62,7 @@ impl From<&VertexAttributeValues> for VertexFormat {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VertexAttribute {
     pub name: Cow<'static, str>,
     pub values: VertexAttributeValues,
@@ -106,7 +107,7 @@ pub enum MeshToVertexBufferError {
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Mesh {
     pub primitive_topology: PrimitiveTopology,
     pub attributes: Vec<VertexAttribute>,
@@ -470,7 +471,7 @@ pub mod shape {
 
 fn remove_current_mesh_resources(
     render_resource_context: &dyn RenderResourceContext,
-    handle: Handle<Mesh>,
+    handle: &Handle<Mesh>,
 ) {
     if let Some(RenderResourceId::Buffer(buffer)) =
         render_resource_context.get_asset_resource(handle, VERTEX_BUFFER_ASSET_INDEX)
@@ -515,18 +516,19 @@ pub fn mesh_resource_provider_system(
     for event in state.mesh_event_reader.iter(&mesh_events) {
         match event {
             AssetEvent::Created { handle } => {
-                changed_meshes.insert(*handle);
+                changed_meshes.insert(handle.clone_weak());
             }
             AssetEvent::Modified { handle } => {
-                changed_meshes.insert(*handle);
-                remove_current_mesh_resources(render_resource_context, *handle);
+                changed_meshes.insert(handle.clone_weak());
+                remove_current_mesh_resources(render_resource_context, handle);
             }
             AssetEvent::Removed { handle } => {
-                remove_current_mesh_resources(render_resource_context, *handle);
+                remove_current_mesh_resources(render_resource_context, handle);
                 // if mesh was modified and removed in the same update, ignore the modification
                 // events are ordered so future modification events are ok
                 changed_meshes.remove(handle);
             }
+            AssetEvent::Failed { .. } => {}
         }
     }
 
@@ -554,12 +556,12 @@ pub fn mesh_resource_provider_system(
             );
 
             render_resource_context.set_asset_resource(
-                *changed_mesh_handle,
+                changed_mesh_handle,
                 RenderResourceId::Buffer(vertex_buffer),
                 VERTEX_BUFFER_ASSET_INDEX,
             );
             render_resource_context.set_asset_resource(
-                *changed_mesh_handle,
+                changed_mesh_handle,
                 RenderResourceId::Buffer(index_buffer),
                 INDEX_BUFFER_ASSET_INDEX,
             );
@@ -575,13 +577,13 @@ pub fn mesh_resource_provider_system(
         }
 
         if let Some(RenderResourceId::Buffer(vertex_buffer)) =
-            render_resource_context.get_asset_resource(*handle, VERTEX_BUFFER_ASSET_INDEX)
+            render_resource_context.get_asset_resource(handle, VERTEX_BUFFER_ASSET_INDEX)
         {
             render_pipelines.bindings.set_vertex_buffer(
                 "Vertex",
                 vertex_buffer,
                 render_resource_context
-                    .get_asset_resource(*handle, INDEX_BUFFER_ASSET_INDEX)
+                    .get_asset_resource(handle, INDEX_BUFFER_ASSET_INDEX)
                     .and_then(|r| {
                         if let RenderResourceId::Buffer(buffer) = r {
                             Some(buffer)
@@ -1,6 +1,8 @@
+mod binary_mesh;
 #[allow(clippy::module_inception)]
 mod mesh;
 mod vertex;
 
+pub use binary_mesh::*;
 pub use mesh::*;
 pub use vertex::*;
@@ -55,7 +55,7 @@ impl RenderPipelines {
         RenderPipelines {
             pipelines: handles
                 .into_iter()
-                .map(|pipeline| RenderPipeline::new(*pipeline))
+                .map(|pipeline| RenderPipeline::new(pipeline.clone()))
                 .collect::<Vec<RenderPipeline>>(),
             ..Default::default()
         }
@@ -90,7 +90,7 @@ pub fn draw_render_pipelines_system(
             draw_context
                 .set_pipeline(
                     &mut draw,
-                    render_pipeline.pipeline,
+                    &render_pipeline.pipeline,
                     &render_pipeline.specialization,
                 )
                 .unwrap();
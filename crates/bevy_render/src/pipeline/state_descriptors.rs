@@ -47,7 +47,7 @@ impl StencilStateFaceDescriptor {
     };
 }
 
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum CompareFunction {
     Never = 0,
     Less = 1,
@@ -57,7 +57,7 @@ impl Node for TextureCopyNode {
 
                         let texture_resource = render_context
                             .resources()
-                            .get_asset_resource(*handle, TEXTURE_ASSET_INDEX)
+                            .get_asset_resource(handle, TEXTURE_ASSET_INDEX)
                             .unwrap();
 
                         render_context.copy_buffer_to_texture(
@@ -72,7 +72,7 @@ impl Node for TextureCopyNode {
                         render_context.resources().remove_buffer(texture_buffer);
                     }
                 }
-                AssetEvent::Removed { .. } => {}
+                AssetEvent::Removed { .. } | AssetEvent::Failed { .. } => {}
             }
         }
     }
@@ -259,17 +259,17 @@ pub struct AssetRenderResourceBindings {
 }
 
 impl AssetRenderResourceBindings {
-    pub fn get<T>(&self, handle: Handle<T>) -> Option<&RenderResourceBindings> {
+    pub fn get<T>(&self, handle: &Handle<T>) -> Option<&RenderResourceBindings> {
         self.bindings.get(&HandleUntyped::from(handle))
     }
 
-    pub fn get_or_insert_mut<T>(&mut self, handle: Handle<T>) -> &mut RenderResourceBindings {
+    pub fn get_or_insert_mut<T>(&mut self, handle: &Handle<T>) -> &mut RenderResourceBindings {
         self.bindings
             .entry(HandleUntyped::from(handle))
             .or_insert_with(RenderResourceBindings::default)
     }
 
-    pub fn get_mut<T>(&mut self, handle: Handle<T>) -> Option<&mut RenderResourceBindings> {
+    pub fn get_mut<T>(&mut self, handle: &Handle<T>) -> Option<&mut RenderResourceBindings> {
         self.bindings.get_mut(&HandleUntyped::from(handle))
     }
 }
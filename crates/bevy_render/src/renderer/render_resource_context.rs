@@ -63,21 +63,29 @@ pub trait RenderResourceContext: Downcast + Send + Sync + 'static {
 }
 
 impl dyn RenderResourceContext {
-    pub fn set_asset_resource<T>(&self, handle: Handle<T>, resource: RenderResourceId, index: usize)
-    where
+    pub fn set_asset_resource<T>(
+        &self,
+        handle: &Handle<T>,
+        resource: RenderResourceId,
+        index: usize,
+    ) where
         T: 'static,
     {
         self.set_asset_resource_untyped(handle.into(), resource, index);
     }
 
-    pub fn get_asset_resource<T>(&self, handle: Handle<T>, index: usize) -> Option<RenderResourceId>
+    pub fn get_asset_resource<T>(
+        &self,
+        handle: &Handle<T>,
+        index: usize,
+    ) -> Option<RenderResourceId>
     where
         T: 'static,
     {
         self.get_asset_resource_untyped(handle.into(), index)
     }
 
-    pub fn remove_asset_resource<T>(&self, handle: Handle<T>, index: usize)
+    pub fn remove_asset_resource<T>(&self, handle: &Handle<T>, index: usize)
     where
         T: 'static,
     {
@@ -0,0 +1,42 @@
+use super::Texture;
+use anyhow::Result;
+use bevy_asset::{AssetLoader, AssetSaver, LoaderSettings};
+use std::path::Path;
+
+/// Loads textures that were written by a [BinaryTextureSaver]
+#[derive(Clone, Default)]
+pub struct BinaryTextureLoader;
+
+impl AssetLoader<Texture> for BinaryTextureLoader {
+    fn from_bytes(
+        &self,
+        _asset_path: &Path,
+        bytes: Vec<u8>,
+        _settings: &LoaderSettings,
+    ) -> Result<Texture> {
+        Ok(bincode::deserialize(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["texture"];
+        EXTENSIONS
+    }
+}
+
+/// Saves textures with their decoded pixel data, so loading them doesn't require decoding formats like PNG. This is
+/// used to process textures.
+///
+/// The pixel data is not compressed, so processed textures are usually much larger than their source files. Loading
+/// them trades disk space for decoding time.
+#[derive(Clone, Default)]
+pub struct BinaryTextureSaver;
+
+impl AssetSaver<Texture> for BinaryTextureSaver {
+    fn save(&self, texture: &Texture) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(texture)?)
+    }
+
+    fn extension(&self) -> &str {
+        "texture"
+    }
+}
@@ -1,6 +1,6 @@
 use super::{Texture, TextureFormat};
 use anyhow::Result;
-use bevy_asset::AssetLoader;
+use bevy_asset::{AssetLoader, LoaderSettings};
 use bevy_math::Vec2;
 use std::path::Path;
 
@@ -9,7 +9,12 @@ use std::path::Path;
 pub struct HdrTextureLoader;
 
 impl AssetLoader<Texture> for HdrTextureLoader {
-    fn from_bytes(&self, _asset_path: &Path, bytes: Vec<u8>) -> Result<Texture> {
+    fn from_bytes(
+        &self,
+        _asset_path: &Path,
+        bytes: Vec<u8>,
+        _settings: &LoaderSettings,
+    ) -> Result<Texture> {
         let format = TextureFormat::Rgba32Float;
         debug_assert_eq!(
             format.pixel_size(),
@@ -1,7 +1,8 @@
-use super::{Texture, TextureFormat};
+use super::{AddressMode, FilterMode, SamplerDescriptor, Texture, TextureFormat};
 use anyhow::Result;
-use bevy_asset::AssetLoader;
+use bevy_asset::{AssetLoader, LoaderSettings};
 use bevy_math::Vec2;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// Loader for images that can be read by the `image` crate.
@@ -10,10 +11,38 @@ use std::path::Path;
 #[derive(Clone, Default)]
 pub struct ImageTextureLoader;
 
+/// Settings for [ImageTextureLoader], which are read from the meta file of an image. Sprite sheets usually use
+/// [FilterMode::Nearest] to keep their pixels sharp.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageTextureSettings {
+    pub address_mode: AddressMode,
+    pub mag_filter: FilterMode,
+    pub min_filter: FilterMode,
+}
+
+impl Default for ImageTextureSettings {
+    fn default() -> Self {
+        let sampler = SamplerDescriptor::default();
+        ImageTextureSettings {
+            address_mode: sampler.address_mode_u,
+            mag_filter: sampler.mag_filter,
+            min_filter: sampler.min_filter,
+        }
+    }
+}
+
 impl AssetLoader<Texture> for ImageTextureLoader {
-    fn from_bytes(&self, asset_path: &Path, bytes: Vec<u8>) -> Result<Texture> {
+    fn from_bytes(
+        &self,
+        asset_path: &Path,
+        bytes: Vec<u8>,
+        settings: &LoaderSettings,
+    ) -> Result<Texture> {
         use bevy_core::AsBytes;
 
+        let settings = settings.get::<ImageTextureSettings>()?;
+
         // Find the image type we expect. A file with the extension "png" should
         // probably load as a PNG.
 
@@ -143,11 +172,16 @@ impl AssetLoader<Texture> for ImageTextureLoader {
             }
         }
 
-        Ok(Texture::new(
-            Vec2::new(width as f32, height as f32),
-            data,
-            format,
-        ))
+        let mut texture = Texture::new(Vec2::new(width as f32, height as f32), data, format);
+        texture.sampler = SamplerDescriptor {
+            address_mode_u: settings.address_mode,
+            address_mode_v: settings.address_mode,
+            address_mode_w: settings.address_mode,
+            mag_filter: settings.mag_filter,
+            min_filter: settings.min_filter,
+            ..Default::default()
+        };
+        Ok(texture)
     }
 
     fn extensions(&self) -> &[&str] {
@@ -1,3 +1,4 @@
+mod binary_texture;
 #[cfg(feature = "hdr")]
 mod hdr_texture_loader;
 #[cfg(feature = "png")]
@@ -8,6 +9,7 @@ mod texture;
 mod texture_descriptor;
 mod texture_dimension;
 
+pub use binary_texture::*;
 #[cfg(feature = "hdr")]
 pub use hdr_texture_loader::*;
 #[cfg(feature = "png")]
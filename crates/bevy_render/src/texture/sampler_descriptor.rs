@@ -1,9 +1,10 @@
 use super::Texture;
 use crate::pipeline::CompareFunction;
+use serde::{Deserialize, Serialize};
 use std::num::NonZeroU8;
 
 /// Describes a sampler
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct SamplerDescriptor {
     pub address_mode_u: AddressMode,
     pub address_mode_v: AddressMode,
@@ -35,24 +36,13 @@ impl Default for SamplerDescriptor {
 }
 
 impl From<&Texture> for SamplerDescriptor {
-    fn from(_texture: &Texture) -> Self {
-        SamplerDescriptor {
-            address_mode_u: AddressMode::ClampToEdge,
-            address_mode_v: AddressMode::ClampToEdge,
-            address_mode_w: AddressMode::ClampToEdge,
-            mag_filter: FilterMode::Nearest,
-            min_filter: FilterMode::Linear,
-            mipmap_filter: FilterMode::Nearest,
-            lod_min_clamp: 0.0,
-            lod_max_clamp: std::f32::MAX,
-            compare_function: None,
-            anisotropy_clamp: None,
-        }
+    fn from(texture: &Texture) -> Self {
+        texture.sampler
     }
 }
 
 /// How edges should be handled in texture addressing.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum AddressMode {
     ClampToEdge = 0,
     Repeat = 1,
@@ -66,7 +56,7 @@ impl Default for AddressMode {
 }
 
 /// Texel mixing mode when sampling between texels.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum FilterMode {
     Nearest = 0,
     Linear = 1,
@@ -7,15 +7,18 @@ use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{Res, ResMut};
 use bevy_math::Vec2;
 use bevy_utils::HashSet;
+use serde::{Deserialize, Serialize};
 
 pub const TEXTURE_ASSET_INDEX: usize = 0;
 pub const SAMPLER_ASSET_INDEX: usize = 1;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Texture {
     pub data: Vec<u8>,
     pub size: Vec2,
     pub format: TextureFormat,
+    /// Describes how the texture is sampled
+    pub sampler: SamplerDescriptor,
 }
 
 impl Default for Texture {
@@ -24,6 +27,7 @@ impl Default for Texture {
             data: Default::default(),
             size: Default::default(),
             format: TextureFormat::Rgba8UnormSrgb,
+            sampler: Default::default(),
         }
     }
 }
@@ -35,7 +39,12 @@ impl Texture {
             data.len(),
             "Pixel data, size and format have to match",
         );
-        Self { data, size, format }
+        Self {
+            data,
+            size,
+            format,
+            sampler: Default::default(),
+        }
     }
 
     pub fn new_fill(size: Vec2, pixel: &[u8], format: TextureFormat) -> Self {
@@ -82,18 +91,19 @@ impl Texture {
         for event in state.event_reader.iter(&texture_events) {
             match event {
                 AssetEvent::Created { handle } => {
-                    changed_textures.insert(*handle);
+                    changed_textures.insert(handle.clone_weak());
                 }
                 AssetEvent::Modified { handle } => {
-                    changed_textures.insert(*handle);
-                    Self::remove_current_texture_resources(render_resource_context, *handle);
+                    changed_textures.insert(handle.clone_weak());
+                    Self::remove_current_texture_resources(render_resource_context, handle);
                 }
                 AssetEvent::Removed { handle } => {
-                    Self::remove_current_texture_resources(render_resource_context, *handle);
+                    Self::remove_current_texture_resources(render_resource_context, handle);
                     // if texture was modified and removed in the same update, ignore the modification
                     // events are ordered so future modification events are ok
                     changed_textures.remove(handle);
                 }
+                AssetEvent::Failed { .. } => {}
             }
         }
 
@@ -106,12 +116,12 @@ impl Texture {
                 let sampler_resource = render_resource_context.create_sampler(&sampler_descriptor);
 
                 render_resource_context.set_asset_resource(
-                    *texture_handle,
+                    texture_handle,
                     RenderResourceId::Texture(texture_resource),
                     TEXTURE_ASSET_INDEX,
                 );
                 render_resource_context.set_asset_resource(
-                    *texture_handle,
+                    texture_handle,
                     RenderResourceId::Sampler(sampler_resource),
                     SAMPLER_ASSET_INDEX,
                 );
@@ -121,7 +131,7 @@ impl Texture {
 
     fn remove_current_texture_resources(
         render_resource_context: &dyn RenderResourceContext,
-        handle: Handle<Texture>,
+        handle: &Handle<Texture>,
     ) {
         if let Some(RenderResourceId::Texture(resource)) =
             render_resource_context.get_asset_resource(handle, TEXTURE_ASSET_INDEX)
@@ -145,7 +155,7 @@ pub struct TextureResourceSystemState {
 
 impl RenderResource for Option<Handle<Texture>> {
     fn resource_type(&self) -> Option<RenderResourceType> {
-        self.map(|_texture| RenderResourceType::Texture)
+        self.as_ref().map(|_texture| RenderResourceType::Texture)
     }
 
     fn write_buffer_bytes(&self, _buffer: &mut [u8]) {}
@@ -155,7 +165,7 @@ impl RenderResource for Option<Handle<Texture>> {
     }
 
     fn texture(&self) -> Option<Handle<Texture>> {
-        *self
+        self.as_ref().map(|texture| texture.clone_weak())
     }
 }
 
@@ -171,6 +181,6 @@ impl RenderResource for Handle<Texture> {
     }
 
     fn texture(&self) -> Option<Handle<Texture>> {
-        Some(*self)
+        Some(self.clone_weak())
     }
 }
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 // NOTE: These are currently just copies of the wgpu types, but they might change in the future
 
 /// Dimensions of a particular texture view.
@@ -44,7 +46,7 @@ pub struct PixelInfo {
 ///
 /// If there is a conversion in the format (such as srgb -> linear), The conversion listed is for
 /// loading from texture in a shader. When writing to the texture, the opposite conversion takes place.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum TextureFormat {
     // Normal 8 bit formats
     R8Unorm = 0,
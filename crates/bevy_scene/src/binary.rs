@@ -0,0 +1,285 @@
+use crate::{Entity, Scene};
+use bevy_property::{DynamicProperties, Property, PropertyType, PropertyTypeRegistry};
+use bevy_utils::HashMap;
+use bincode::{ErrorKind, Options};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The binary form of a [Scene]. Type and property names are only stored once, in `names`, and referred to by
+/// their index. Values are encoded with bincode.
+#[derive(Serialize, Deserialize)]
+struct BinaryScene {
+    names: Vec<String>,
+    entities: Vec<BinaryEntity>,
+    resources: Vec<BinaryProperty>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryEntity {
+    entity: u32,
+    components: Vec<BinaryProperty>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum BinaryProperty {
+    Map {
+        type_name: u32,
+        variant: Option<u32>,
+        props: Vec<(u32, BinaryProperty)>,
+    },
+    Seq {
+        type_name: u32,
+        props: Vec<BinaryProperty>,
+    },
+    Value {
+        type_name: u32,
+        value: Vec<u8>,
+    },
+}
+
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+pub(crate) fn serialize_binary(
+    scene: &Scene,
+    registry: &PropertyTypeRegistry,
+) -> Result<Vec<u8>, bincode::Error> {
+    let mut serializer = BinarySerializer {
+        registry,
+        names: Vec::new(),
+        name_indices: HashMap::default(),
+    };
+    let entities = scene
+        .entities
+        .iter()
+        .map(|entity| {
+            Ok(BinaryEntity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .iter()
+                    .map(|component| serializer.serialize_property(component))
+                    .collect::<Result<_, bincode::Error>>()?,
+            })
+        })
+        .collect::<Result<_, bincode::Error>>()?;
+    let resources = scene
+        .resources
+        .iter()
+        .map(|resource| serializer.serialize_property(resource))
+        .collect::<Result<_, bincode::Error>>()?;
+
+    bincode_options().serialize(&BinaryScene {
+        names: serializer.names,
+        entities,
+        resources,
+    })
+}
+
+struct BinarySerializer<'a> {
+    registry: &'a PropertyTypeRegistry,
+    names: Vec<String>,
+    name_indices: HashMap<String, u32>,
+}
+
+impl<'a> BinarySerializer<'a> {
+    fn name_index(&mut self, name: &str) -> u32 {
+        if let Some(index) = self.name_indices.get(name) {
+            return *index;
+        }
+
+        let index = self.names.len() as u32;
+        self.names.push(name.to_string());
+        self.name_indices.insert(name.to_string(), index);
+        index
+    }
+
+    fn type_name_index(&mut self, type_name: &str) -> u32 {
+        let type_name = self
+            .registry
+            .format_type_name(type_name)
+            .unwrap_or(type_name)
+            .to_string();
+        self.name_index(&type_name)
+    }
+
+    fn serialize_property(
+        &mut self,
+        property: &dyn Property,
+    ) -> Result<BinaryProperty, bincode::Error> {
+        let type_name = self.type_name_index(property.type_name());
+        let properties = property.as_properties();
+        Ok(match (property.property_type(), properties) {
+            (PropertyType::Map, Some(properties)) => BinaryProperty::Map {
+                type_name,
+                variant: properties.variant().map(|variant| self.name_index(variant)),
+                props: properties
+                    .iter_props()
+                    .enumerate()
+                    .map(|(index, prop)| {
+                        let name = self.name_index(properties.prop_name(index).unwrap());
+                        Ok((name, self.serialize_property(prop)?))
+                    })
+                    .collect::<Result<_, bincode::Error>>()?,
+            },
+            (PropertyType::Seq, Some(properties)) => BinaryProperty::Seq {
+                type_name,
+                props: properties
+                    .iter_props()
+                    .map(|prop| self.serialize_property(prop))
+                    .collect::<Result<_, bincode::Error>>()?,
+            },
+            _ => BinaryProperty::Value {
+                type_name,
+                value: bincode_options()
+                    .serialize(property.serializable_value(self.registry).borrow())?,
+            },
+        })
+    }
+}
+
+pub(crate) fn deserialize_binary(
+    bytes: &[u8],
+    registry: &PropertyTypeRegistry,
+) -> Result<Scene, bincode::Error> {
+    let binary_scene: BinaryScene = bincode_options().deserialize(bytes)?;
+    let deserializer = BinaryDeserializer {
+        registry,
+        names: &binary_scene.names,
+    };
+    let entities = binary_scene
+        .entities
+        .into_iter()
+        .map(|entity| {
+            Ok(Entity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .into_iter()
+                    .map(|component| deserializer.deserialize_properties(component))
+                    .collect::<Result<_, bincode::Error>>()?,
+            })
+        })
+        .collect::<Result<_, bincode::Error>>()?;
+    let resources = binary_scene
+        .resources
+        .into_iter()
+        .map(|resource| deserializer.deserialize_properties(resource))
+        .collect::<Result<_, bincode::Error>>()?;
+
+    Ok(Scene {
+        entities,
+        resources,
+    })
+}
+
+struct BinaryDeserializer<'a> {
+    registry: &'a PropertyTypeRegistry,
+    names: &'a [String],
+}
+
+impl<'a> BinaryDeserializer<'a> {
+    fn name(&self, index: u32) -> Result<&'a str, bincode::Error> {
+        self.names
+            .get(index as usize)
+            .map(|name| name.as_str())
+            .ok_or_else(|| custom_error(format!("Name index {} is out of range", index)))
+    }
+
+    fn deserialize_properties(
+        &self,
+        property: BinaryProperty,
+    ) -> Result<DynamicProperties, bincode::Error> {
+        Ok(match property {
+            BinaryProperty::Map {
+                type_name,
+                variant,
+                props,
+            } => {
+                let mut dynamic_properties = DynamicProperties::map();
+                dynamic_properties.type_name = self.name(type_name)?.to_string();
+                if let Some(variant) = variant {
+                    dynamic_properties.variant = Some(self.name(variant)?.to_string());
+                }
+                for (name, prop) in props {
+                    dynamic_properties.set_box(self.name(name)?, self.deserialize_property(prop)?);
+                }
+                dynamic_properties
+            }
+            BinaryProperty::Seq { type_name, props } => {
+                let mut dynamic_properties = DynamicProperties::seq();
+                dynamic_properties.type_name = self.name(type_name)?.to_string();
+                for prop in props {
+                    dynamic_properties.push(self.deserialize_property(prop)?, None);
+                }
+                dynamic_properties
+            }
+            BinaryProperty::Value { type_name, .. } => {
+                return Err(custom_error(format!(
+                    "Expected properties, found a value of type {}",
+                    self.name(type_name)?
+                )))
+            }
+        })
+    }
+
+    fn deserialize_property(
+        &self,
+        property: BinaryProperty,
+    ) -> Result<Box<dyn Property>, bincode::Error> {
+        match property {
+            BinaryProperty::Value { type_name, value } => {
+                let type_name = self.name(type_name)?;
+                match self.registry.get(type_name) {
+                    Some(registration) => {
+                        let mut deserializer =
+                            bincode::Deserializer::from_slice(&value, bincode_options());
+                        registration.deserialize(&mut deserializer, self.registry)
+                    }
+                    None => deserialize_primitive(type_name, &value).ok_or_else(|| {
+                        custom_error(format!("TypeRegistration is missing for {}", type_name))
+                    })?,
+                }
+            }
+            properties => Ok(Box::new(self.deserialize_properties(properties)?)),
+        }
+    }
+}
+
+/// Primitive properties don't have a registration, so they are deserialized by their type name, as returned by
+/// [std::any::type_name]
+fn deserialize_primitive(
+    type_name: &str,
+    bytes: &[u8],
+) -> Option<Result<Box<dyn Property>, bincode::Error>> {
+    fn deserialize<T: Property + DeserializeOwned>(
+        bytes: &[u8],
+    ) -> Result<Box<dyn Property>, bincode::Error> {
+        Ok(Box::new(bincode_options().deserialize::<T>(bytes)?))
+    }
+
+    Some(match type_name {
+        "bool" => deserialize::<bool>(bytes),
+        "char" => deserialize::<char>(bytes),
+        "u8" => deserialize::<u8>(bytes),
+        "u16" => deserialize::<u16>(bytes),
+        "u32" => deserialize::<u32>(bytes),
+        "u64" => deserialize::<u64>(bytes),
+        "u128" => deserialize::<u128>(bytes),
+        "usize" => deserialize::<usize>(bytes),
+        "i8" => deserialize::<i8>(bytes),
+        "i16" => deserialize::<i16>(bytes),
+        "i32" => deserialize::<i32>(bytes),
+        "i64" => deserialize::<i64>(bytes),
+        "i128" => deserialize::<i128>(bytes),
+        "isize" => deserialize::<isize>(bytes),
+        "f32" => deserialize::<f32>(bytes),
+        "f64" => deserialize::<f64>(bytes),
+        "alloc::string::String" => deserialize::<String>(bytes),
+        _ => return None,
+    })
+}
+
+fn custom_error(message: String) -> bincode::Error {
+    Box::new(ErrorKind::Custom(message))
+}
@@ -1,19 +1,25 @@
+mod binary;
 mod loaded_scenes;
+mod save_game;
 mod scene;
+mod scene_diff;
 mod scene_spawner;
 pub mod serde;
 
 pub use loaded_scenes::*;
+pub use save_game::*;
 pub use scene::*;
+pub use scene_diff::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
-    pub use crate::{Scene, SceneSpawner};
+    pub use crate::{Persistent, SaveFilter, SaveGame, Scene, SceneOverrides, SceneSpawner};
 }
 
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
+use bevy_asset::{AddAsset, AssetPlugin};
 use bevy_ecs::IntoThreadLocalSystem;
+use bevy_type_registry::{RegisterType, TypeRegistryPlugin};
 
 #[derive(Default)]
 pub struct ScenePlugin;
@@ -24,8 +30,17 @@ impl Plugin for ScenePlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_asset::<Scene>()
             .add_asset_loader::<Scene, SceneLoader>()
+            .add_asset_saver::<Scene, SceneSaver>()
+            .register_component::<Persistent>()
             .init_resource::<SceneSpawner>()
             .add_stage_after(stage::EVENT_UPDATE, SCENE_STAGE)
             .add_system_to_stage(SCENE_STAGE, scene_spawner_system.thread_local_system());
     }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![
+            PluginId::of::<TypeRegistryPlugin>(),
+            PluginId::of::<AssetPlugin>(),
+        ]
+    }
 }
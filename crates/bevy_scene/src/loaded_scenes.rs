@@ -1,13 +1,16 @@
-use crate::{serde::SceneDeserializer, Scene};
+use crate::Scene;
 use anyhow::Result;
-use bevy_asset::AssetLoader;
+use bevy_asset::{AssetLoader, AssetSaver, LoaderSettings};
 use bevy_ecs::{FromResources, Resources};
 use bevy_property::PropertyTypeRegistry;
 use bevy_type_registry::TypeRegistry;
 use parking_lot::RwLock;
-use serde::de::DeserializeSeed;
 use std::{path::Path, sync::Arc};
 
+/// The extension of scenes in the binary format of [Scene::serialize_binary]
+pub const BINARY_SCENE_EXTENSION: &str = "bscn";
+
+/// Loads scenes in the RON format (`.scn`) or the binary format (`.bscn`), depending on their extension
 pub struct SceneLoader {
     property_type_registry: Arc<RwLock<PropertyTypeRegistry>>,
 }
@@ -22,18 +25,73 @@ impl FromResources for SceneLoader {
 }
 
 impl AssetLoader<Scene> for SceneLoader {
-    fn from_bytes(&self, _asset_path: &Path, bytes: Vec<u8>) -> Result<Scene> {
+    fn from_bytes(
+        &self,
+        asset_path: &Path,
+        bytes: Vec<u8>,
+        _settings: &LoaderSettings,
+    ) -> Result<Scene> {
         let registry = self.property_type_registry.read();
+        if asset_path.extension() == Some(BINARY_SCENE_EXTENSION.as_ref()) {
+            return Ok(Scene::deserialize_binary(&bytes, &registry)?);
+        }
+
         let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
-        let scene_deserializer = SceneDeserializer {
-            property_type_registry: &registry,
-        };
-        let scene = scene_deserializer.deserialize(&mut deserializer)?;
-        Ok(scene)
+        Ok(Scene::deserialize(&mut deserializer, &registry)?)
     }
 
     fn extensions(&self) -> &[&str] {
-        static EXTENSIONS: &[&str] = &["scn"];
+        static EXTENSIONS: &[&str] = &["scn", BINARY_SCENE_EXTENSION];
         EXTENSIONS
     }
 }
+
+/// Saves scenes in the RON format that [SceneLoader] loads
+pub struct SceneSaver {
+    property_type_registry: Arc<RwLock<PropertyTypeRegistry>>,
+}
+
+impl FromResources for SceneSaver {
+    fn from_resources(resources: &Resources) -> Self {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        SceneSaver {
+            property_type_registry: type_registry.property.clone(),
+        }
+    }
+}
+
+impl AssetSaver<Scene> for SceneSaver {
+    fn save(&self, scene: &Scene) -> Result<Vec<u8>> {
+        let registry = self.property_type_registry.read();
+        Ok(scene.serialize_ron(&registry)?.into_bytes())
+    }
+
+    fn extension(&self) -> &str {
+        "scn"
+    }
+}
+
+/// Saves scenes in the binary format that [SceneLoader] loads from `.bscn` files
+pub struct BinarySceneSaver {
+    property_type_registry: Arc<RwLock<PropertyTypeRegistry>>,
+}
+
+impl FromResources for BinarySceneSaver {
+    fn from_resources(resources: &Resources) -> Self {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        BinarySceneSaver {
+            property_type_registry: type_registry.property.clone(),
+        }
+    }
+}
+
+impl AssetSaver<Scene> for BinarySceneSaver {
+    fn save(&self, scene: &Scene) -> Result<Vec<u8>> {
+        let registry = self.property_type_registry.read();
+        Ok(scene.serialize_binary(&registry)?)
+    }
+
+    fn extension(&self) -> &str {
+        BINARY_SCENE_EXTENSION
+    }
+}
@@ -0,0 +1,613 @@
+use crate::{
+    scene_spawner::write_scene,
+    serde::{SceneDeserializer, SceneSerializer},
+    serialize_ron, Entity, Scene, SceneSpawnError,
+};
+use bevy_asset::AssetServer;
+use bevy_ecs::{EntityMap, Resources, World};
+use bevy_property::{
+    Properties, PropertiesVal, Property, PropertyTypeRegistration, PropertyTypeRegistry,
+};
+use bevy_transform::prelude::{Children, Parent};
+use bevy_type_registry::{ComponentRegistration, ComponentRegistry, TypeRegistry};
+use bevy_utils::HashSet;
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Serialize,
+};
+use std::path::Path;
+use thiserror::Error;
+
+/// Marks entities that are written to save games with [SaveFilter::PersistentEntities]
+#[derive(Properties, Default, Debug, Clone, Copy)]
+pub struct Persistent;
+
+/// Selects what is written to a save game. Registered resources are always saved.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SaveFilter {
+    /// Saves all registered components of the entities with the [Persistent] component
+    PersistentEntities,
+    /// Saves the components in the allow-list of the [ComponentRegistry], see
+    /// [RegisterType::register_persistent_component](bevy_type_registry::RegisterType::register_persistent_component).
+    /// Loading replaces these components on the entities they were saved from, which keep their other components.
+    /// Saved entities that no longer exist are spawned as new entities.
+    PersistentComponents,
+}
+
+#[derive(Error, Debug)]
+pub enum SaveGameError {
+    #[error("Encountered an io error while reading or writing the save game.")]
+    Io(#[from] std::io::Error),
+    #[error("Save game could not be serialized or deserialized.")]
+    Ron(#[from] ron::Error),
+    #[error("Save game was written by a newer version.")]
+    UnsupportedVersion { version: u32, current: u32 },
+    #[error("Save game could not be spawned.")]
+    SpawnError(#[from] SceneSpawnError),
+}
+
+type Migration = Box<dyn Fn(&mut Scene) + Send + Sync>;
+
+/// Saves and loads the state of the world as a [Scene]. Save games are versioned: when a save game of an older
+/// version is loaded, the migrations of every newer version are applied to it in order.
+pub struct SaveGame {
+    filter: SaveFilter,
+    version: u32,
+    migrations: Vec<(u32, Migration)>,
+}
+
+impl SaveGame {
+    pub fn new(filter: SaveFilter) -> Self {
+        Self {
+            filter,
+            version: 0,
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Sets the version that is written to new save games
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Adds a migration that upgrades save games that are older than `version`
+    pub fn with_migration(
+        mut self,
+        version: u32,
+        migration: impl Fn(&mut Scene) + Send + Sync + 'static,
+    ) -> Self {
+        self.migrations.push((version, Box::new(migration)));
+        self.migrations.sort_by_key(|(version, _)| *version);
+        self
+    }
+
+    /// Adds a migration that renames the field `from` of the component or resource `type_name` to `to` in save games
+    /// that are older than `version`
+    pub fn rename_field(self, version: u32, type_name: &str, from: &str, to: &str) -> Self {
+        let short_name = PropertyTypeRegistration::get_short_name(type_name);
+        let (from, to) = (from.to_string(), to.to_string());
+        self.with_migration(version, move |scene| {
+            let components = scene
+                .entities
+                .iter_mut()
+                .flat_map(|entity| entity.components.iter_mut())
+                .chain(scene.resources.iter_mut());
+            for properties in components {
+                if PropertyTypeRegistration::get_short_name(&properties.type_name) == short_name {
+                    properties.rename(&from, &to);
+                }
+            }
+        })
+    }
+
+    /// Captures the entities and components that match the filter and the registered resources. [Parent] and
+    /// [Children] links to entities that aren't saved are left out, so children of unsaved entities are loaded as
+    /// roots.
+    pub fn to_scene(&self, world: &World, resources: &Resources) -> Scene {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let component_registry = type_registry.component.read();
+        let mut scene = Scene::default();
+        for archetype in world.archetypes() {
+            if self.filter == SaveFilter::PersistentEntities && !archetype.has::<Persistent>() {
+                continue;
+            }
+
+            let registrations = archetype
+                .types()
+                .iter()
+                .filter_map(|type_info| component_registry.get(&type_info.id()))
+                .filter(|registration| self.saves_component(&component_registry, registration))
+                .collect::<Vec<_>>();
+            if registrations.is_empty() {
+                continue;
+            }
+
+            for (index, entity) in archetype.iter_entities().enumerate() {
+                scene.entities.push(Entity {
+                    entity: entity.id(),
+                    components: registrations
+                        .iter()
+                        .map(|registration| {
+                            registration
+                                .get_component_properties(archetype, index)
+                                .to_dynamic()
+                        })
+                        .collect(),
+                });
+            }
+        }
+        remove_unsaved_hierarchy_links(&mut scene);
+
+        let mut scene = scene.with_resources(resources, &type_registry.resource.read());
+        if let Some(asset_server) = resources.get::<AssetServer>() {
+            scene.store_asset_paths(&asset_server);
+        }
+        scene
+    }
+
+    fn saves_component(
+        &self,
+        component_registry: &ComponentRegistry,
+        registration: &ComponentRegistration,
+    ) -> bool {
+        match self.filter {
+            SaveFilter::PersistentEntities => true,
+            SaveFilter::PersistentComponents => component_registry.is_persistent(&registration.ty),
+        }
+    }
+
+    /// Serializes the scene to RON, together with the current version
+    pub fn serialize(
+        &self,
+        scene: &Scene,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<String, SaveGameError> {
+        Ok(serialize_ron(SaveGameSerializer {
+            version: self.version,
+            scene: SceneSerializer::new(scene, registry),
+        })?)
+    }
+
+    /// Deserializes a save game that was written by [SaveGame::serialize] and migrates it to the current version
+    pub fn deserialize(
+        &self,
+        bytes: &[u8],
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Scene, SaveGameError> {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        let (version, mut scene) =
+            SaveGameDeserializer { registry }.deserialize(&mut deserializer)?;
+        if version > self.version {
+            return Err(SaveGameError::UnsupportedVersion {
+                version,
+                current: self.version,
+            });
+        }
+
+        for (_, migration) in self
+            .migrations
+            .iter()
+            .filter(|(migration_version, _)| *migration_version > version)
+        {
+            migration(&mut scene);
+        }
+        Ok(scene)
+    }
+
+    /// Writes the state of the world that matches the filter to the file at `path`
+    pub fn save(
+        &self,
+        world: &World,
+        resources: &Resources,
+        path: impl AsRef<Path>,
+    ) -> Result<(), SaveGameError> {
+        let scene = self.to_scene(world, resources);
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let ron = self.serialize(&scene, &type_registry.property.read())?;
+        std::fs::write(path, ron)?;
+        Ok(())
+    }
+
+    /// Loads the save game at `path`. The entities or components that match the filter are removed before the saved
+    /// ones are written, and the saved resources are applied to the existing resources.
+    pub fn load(
+        &self,
+        world: &mut World,
+        resources: &Resources,
+        path: impl AsRef<Path>,
+    ) -> Result<(), SaveGameError> {
+        let bytes = std::fs::read(path)?;
+        let scene = {
+            let type_registry = resources.get::<TypeRegistry>().unwrap();
+            let registry = type_registry.property.read();
+            self.deserialize(&bytes, &registry)?
+        };
+
+        self.clear(world, resources);
+        let mut entity_map = EntityMap::default();
+        if self.filter == SaveFilter::PersistentComponents {
+            // the persistent components are written back to the entities that still have their other components
+            for scene_entity in scene.entities.iter() {
+                let entity = bevy_ecs::Entity::new(scene_entity.entity);
+                if world.contains(entity) {
+                    entity_map.insert(entity, entity);
+                }
+            }
+        }
+        write_scene(&scene, world, resources, entity_map)?;
+        Ok(())
+    }
+
+    /// Removes what would be written to a save game: the entities with the [Persistent] component, or the persistent
+    /// components. Entities that only have persistent components are despawned.
+    pub fn clear(&self, world: &mut World, resources: &Resources) {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let component_registry = type_registry.component.read();
+        let mut despawned = Vec::new();
+        let mut removed = Vec::new();
+        for archetype in world.archetypes() {
+            match self.filter {
+                SaveFilter::PersistentEntities => {
+                    if archetype.has::<Persistent>() {
+                        despawned.extend(archetype.iter_entities().copied());
+                    }
+                }
+                SaveFilter::PersistentComponents => {
+                    let persistent = archetype
+                        .types()
+                        .iter()
+                        .filter(|type_info| component_registry.is_persistent(&type_info.id()))
+                        .map(|type_info| component_registry.get(&type_info.id()).unwrap())
+                        .collect::<Vec<_>>();
+                    if persistent.is_empty() {
+                        continue;
+                    } else if persistent.len() == archetype.types().len() {
+                        despawned.extend(archetype.iter_entities().copied());
+                    } else {
+                        for entity in archetype.iter_entities() {
+                            removed.push((*entity, persistent.clone()));
+                        }
+                    }
+                }
+            }
+        }
+
+        for entity in despawned {
+            let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
+        }
+        for (entity, registrations) in removed {
+            for registration in registrations {
+                registration.remove_component_from_entity(world, entity);
+            }
+        }
+    }
+}
+
+/// Removes the [Parent] and [Children] links to entities that aren't in the scene, since they couldn't be mapped to
+/// spawned entities
+fn remove_unsaved_hierarchy_links(scene: &mut Scene) {
+    let saved = scene
+        .entities
+        .iter()
+        .map(|entity| entity.entity)
+        .collect::<HashSet<_>>();
+    let is_saved = |entity: &bevy_ecs::Entity| saved.contains(&entity.id());
+    for entity in scene.entities.iter_mut() {
+        for component in entity.components.iter_mut() {
+            if component.type_name == std::any::type_name::<Children>() {
+                let mut children = Children::default();
+                children.apply(component);
+                children.retain(|child| is_saved(child));
+                *component = children.to_dynamic();
+            }
+        }
+        entity.components.retain(|component| {
+            if component.type_name == std::any::type_name::<Parent>() {
+                matches!(component.prop_val::<bevy_ecs::Entity>("0"), Some(parent) if is_saved(parent))
+            } else {
+                true
+            }
+        });
+    }
+}
+
+struct SaveGameSerializer<'a> {
+    version: u32,
+    scene: SceneSerializer<'a>,
+}
+
+impl<'a> Serialize for SaveGameSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(SAVE_GAME_STRUCT, 2)?;
+        state.serialize_field(SAVE_GAME_FIELD_VERSION, &self.version)?;
+        state.serialize_field(SAVE_GAME_FIELD_SCENE, &self.scene)?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SaveGameField {
+    Version,
+    Scene,
+}
+
+const SAVE_GAME_STRUCT: &str = "SaveGame";
+const SAVE_GAME_FIELD_VERSION: &str = "version";
+const SAVE_GAME_FIELD_SCENE: &str = "scene";
+
+struct SaveGameDeserializer<'a> {
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SaveGameDeserializer<'a> {
+    type Value = (u32, Scene);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SAVE_GAME_STRUCT,
+            &[SAVE_GAME_FIELD_VERSION, SAVE_GAME_FIELD_SCENE],
+            SaveGameVisiter {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct SaveGameVisiter<'a> {
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SaveGameVisiter<'a> {
+    type Value = (u32, Scene);
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("save game")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut scene = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SaveGameField::Version => {
+                    if version.is_some() {
+                        return Err(Error::duplicate_field(SAVE_GAME_FIELD_VERSION));
+                    }
+                    version = Some(map.next_value::<u32>()?);
+                }
+                SaveGameField::Scene => {
+                    if scene.is_some() {
+                        return Err(Error::duplicate_field(SAVE_GAME_FIELD_SCENE));
+                    }
+                    scene = Some(map.next_value_seed(SceneDeserializer {
+                        property_type_registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        Ok((
+            version.ok_or_else(|| Error::missing_field(SAVE_GAME_FIELD_VERSION))?,
+            scene.ok_or_else(|| Error::missing_field(SAVE_GAME_FIELD_SCENE))?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScenePlugin;
+    use bevy_app::{App, TestApp};
+    use bevy_asset::AssetPlugin;
+    use bevy_property::DynamicProperties;
+    use bevy_transform::TransformPlugin;
+    use bevy_type_registry::{RegisterType, TypeRegistryPlugin};
+    use std::path::PathBuf;
+
+    #[derive(Properties, Default, Debug, Clone, PartialEq)]
+    struct Player {
+        name: String,
+        health: u32,
+    }
+
+    #[derive(Properties, Default, Debug, Clone, PartialEq)]
+    struct Camera {
+        zoom: f32,
+    }
+
+    #[derive(Properties, Default, Debug, Clone, PartialEq)]
+    struct Score {
+        points: u32,
+    }
+
+    fn save_game_app() -> TestApp {
+        App::build()
+            .add_plugin(TypeRegistryPlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(ScenePlugin)
+            .register_persistent_component::<Player>()
+            .add_plugin(TransformPlugin)
+            .register_component::<Camera>()
+            .register_resource::<Score>()
+            .init_resource::<Score>()
+            .test_app()
+    }
+
+    fn player(name: &str, health: u32) -> Player {
+        Player {
+            name: name.to_string(),
+            health,
+        }
+    }
+
+    fn players(app: &TestApp) -> Vec<Player> {
+        app.world().query::<&Player>().iter().cloned().collect()
+    }
+
+    fn save_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "bevy_save_game_{}_{}.ron",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn save_and_load() {
+        for filter in [
+            SaveFilter::PersistentEntities,
+            SaveFilter::PersistentComponents,
+        ]
+        .iter()
+        {
+            let mut app = save_game_app();
+            let world = &mut app.app.world;
+            world.spawn((player("ferris", 10), Persistent));
+            world.spawn((Camera { zoom: 2.0 },));
+            app.resources().get_mut::<Score>().unwrap().points = 7;
+
+            let save_game = SaveGame::new(*filter);
+            let path = save_path(&format!("{:?}", filter));
+            save_game.save(app.world(), app.resources(), &path).unwrap();
+            let ron = std::fs::read_to_string(&path).unwrap();
+            assert!(ron.contains("Player"));
+            assert!(!ron.contains("Camera"));
+
+            // the saved player replaces the current one, while other entities are kept
+            let world = &mut app.app.world;
+            let player_entity = world
+                .query::<(bevy_ecs::Entity, &Player)>()
+                .iter()
+                .next()
+                .unwrap()
+                .0;
+            world.despawn(player_entity).unwrap();
+            world.spawn((player("ferris", 1), Persistent));
+            app.resources().get_mut::<Score>().unwrap().points = 0;
+            save_game
+                .load(&mut app.app.world, &app.app.resources, &path)
+                .unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(players(&app), vec![player("ferris", 10)]);
+            assert_eq!(app.world().query::<&Camera>().iter().count(), 1);
+            assert_eq!(app.resource::<Score>().points, 7);
+        }
+    }
+
+    #[test]
+    fn other_components_are_kept_when_loading_persistent_components() {
+        let mut app = save_game_app();
+        app.app
+            .world
+            .spawn((player("ferris", 10), Camera { zoom: 2.0 }));
+        let save_game = SaveGame::new(SaveFilter::PersistentComponents);
+        let path = save_path("other_components");
+        save_game.save(app.world(), app.resources(), &path).unwrap();
+        save_game
+            .load(&mut app.app.world, &app.app.resources, &path)
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // the player is loaded onto the entity that still has the camera
+        let entities = app
+            .world()
+            .query::<(&Player, &Camera)>()
+            .iter()
+            .map(|(player, camera)| (player.clone(), camera.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(entities, vec![(player("ferris", 10), Camera { zoom: 2.0 })]);
+        assert_eq!(app.world().query::<&Player>().iter().count(), 1);
+        assert_eq!(app.world().query::<&Camera>().iter().count(), 1);
+    }
+
+    #[test]
+    fn hierarchy_links_to_unsaved_entities_are_removed() {
+        let mut app = save_game_app();
+        let world = &mut app.app.world;
+        // a persistent child of a camera that isn't saved
+        let camera = world.spawn((Camera { zoom: 2.0 },));
+        let orphan = world.spawn((player("orphan", 1), Persistent, Parent(camera)));
+        world.insert_one(camera, Children::with(&[orphan])).unwrap();
+        // a persistent child of a persistent parent
+        let child = world.spawn((player("child", 2), Persistent));
+        let parent = world.spawn((player("parent", 3), Persistent, Children::with(&[child])));
+        world.insert_one(child, Parent(parent)).unwrap();
+
+        let save_game = SaveGame::new(SaveFilter::PersistentEntities);
+        let path = save_path("hierarchy");
+        save_game.save(app.world(), app.resources(), &path).unwrap();
+        save_game
+            .load(&mut app.app.world, &app.app.resources, &path)
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let world = app.world();
+        let find = |name: &str| {
+            world
+                .query::<(bevy_ecs::Entity, &Player)>()
+                .iter()
+                .find(|(_, player)| player.name == name)
+                .unwrap()
+                .0
+        };
+        let (orphan, child, parent) = (find("orphan"), find("child"), find("parent"));
+        assert!(world.get::<Parent>(orphan).is_err());
+        assert_eq!(world.get::<Parent>(child).unwrap().0, parent);
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().0.as_slice(),
+            &[child]
+        );
+    }
+
+    #[test]
+    fn renamed_fields_are_migrated() {
+        let app = save_game_app();
+        let type_registry = app.resource::<TypeRegistry>();
+        let registry = type_registry.property.read();
+
+        // version 1 called the health of players "hp"
+        let mut old_player = DynamicProperties::map();
+        old_player.type_name = std::any::type_name::<Player>().to_string();
+        old_player.set("name", "ferris".to_string());
+        old_player.set("hp", 3u32);
+        let mut scene = Scene::default();
+        scene.entities.push(Entity {
+            entity: 0,
+            components: vec![old_player],
+        });
+        let ron = SaveGame::new(SaveFilter::PersistentComponents)
+            .with_version(1)
+            .serialize(&scene, &registry)
+            .unwrap();
+
+        let save_game = SaveGame::new(SaveFilter::PersistentComponents)
+            .with_version(2)
+            .rename_field(2, "Player", "hp", "health");
+        let scene = save_game.deserialize(ron.as_bytes(), &registry).unwrap();
+        let player = &scene.entities[0].components[0];
+        assert!(player.prop("hp").is_none());
+        assert!(player.prop("health").is_some());
+
+        // save games of newer versions can't be loaded
+        assert!(matches!(
+            SaveGame::new(SaveFilter::PersistentComponents).deserialize(ron.as_bytes(), &registry),
+            Err(SaveGameError::UnsupportedVersion {
+                version: 1,
+                current: 0
+            })
+        ));
+    }
+}
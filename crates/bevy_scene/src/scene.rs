@@ -1,13 +1,22 @@
-use crate::serde::SceneSerializer;
+use crate::{
+    binary,
+    scene_spawner::{contains_asset_paths, write_scene},
+    serde::{SceneDeserializer, SceneSerializer},
+    SceneSpawnError,
+};
 use anyhow::Result;
-use bevy_ecs::World;
-use bevy_property::{DynamicProperties, PropertyTypeRegistry};
-use bevy_type_registry::ComponentRegistry;
-use serde::Serialize;
+use bevy_asset::{AssetPath, AssetServer, HandleId};
+use bevy_ecs::{EntityMap, Resources, World};
+use bevy_property::{DynamicProperties, Properties, PropertyType, PropertyTypeRegistry};
+use bevy_type_registry::{ComponentRegistry, ResourceRegistry};
+use serde::{de::DeserializeSeed, Serialize};
 
 #[derive(Default)]
 pub struct Scene {
     pub entities: Vec<Entity>,
+    /// Resources that are applied to the app's resources when the scene is spawned. The resources must already exist
+    /// in the app.
+    pub resources: Vec<DynamicProperties>,
 }
 
 pub struct Entity {
@@ -15,6 +24,32 @@ pub struct Entity {
     pub components: Vec<DynamicProperties>,
 }
 
+impl Clone for Scene {
+    fn clone(&self) -> Self {
+        Scene {
+            entities: self.entities.clone(),
+            resources: self
+                .resources
+                .iter()
+                .map(|resource| resource.to_dynamic())
+                .collect(),
+        }
+    }
+}
+
+impl Clone for Entity {
+    fn clone(&self) -> Self {
+        Entity {
+            entity: self.entity,
+            components: self
+                .components
+                .iter()
+                .map(|component| component.to_dynamic())
+                .collect(),
+        }
+    }
+}
+
 impl Scene {
     pub fn from_world(world: &World, component_registry: &ComponentRegistry) -> Self {
         let mut scene = Scene::default();
@@ -43,10 +78,135 @@ impl Scene {
         scene
     }
 
-    // TODO: move to AssetSaver when it is implemented
+    /// Adds the current value of every resource in the [ResourceRegistry] to the scene
+    pub fn with_resources(
+        mut self,
+        resources: &Resources,
+        resource_registry: &ResourceRegistry,
+    ) -> Self {
+        let mut registrations = resource_registry.registrations.values().collect::<Vec<_>>();
+        registrations.sort_by_key(|registration| registration.long_name);
+        self.resources.extend(
+            registrations
+                .iter()
+                .filter_map(|registration| registration.get_resource_properties(resources)),
+        );
+        self
+    }
+
+    /// Spawns the entities of the scene and applies its resources. Unlike [SceneSpawner](crate::SceneSpawner), the
+    /// spawned entities aren't tracked, so they aren't updated when the scene changes. Returns the map from the
+    /// entities of the scene to the spawned entities.
+    pub fn write_to_world(
+        &self,
+        world: &mut World,
+        resources: &Resources,
+    ) -> Result<EntityMap, SceneSpawnError> {
+        write_scene(self, world, resources, EntityMap::default())
+    }
+
+    /// Replaces the ids of handles to assets that were loaded from a file with the paths of the assets. Handle ids
+    /// are only valid while the app is running, so scenes that are saved should refer to assets by their path. The
+    /// assets are loaded once the scene is spawned.
+    pub fn store_asset_paths(&mut self, asset_server: &AssetServer) {
+        for entity in self.entities.iter_mut() {
+            for component in entity.components.iter_mut() {
+                store_asset_paths(component, asset_server);
+            }
+        }
+        for resource in self.resources.iter_mut() {
+            store_asset_paths(resource, asset_server);
+        }
+    }
+
+    /// Serializes the scene to RON. To write scenes to assets, use [AssetServer::save](bevy_asset::AssetServer::save),
+    /// which uses the [SceneSaver](crate::SceneSaver).
     pub fn serialize_ron(&self, registry: &PropertyTypeRegistry) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serializes the scene with any serde format, in the same layout as [Scene::serialize_ron]
+    pub fn serialize<S>(
+        &self,
+        registry: &PropertyTypeRegistry,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        SceneSerializer::new(self, registry).serialize(serializer)
+    }
+
+    /// Deserializes a scene with any serde format that supports `deserialize_any`, like RON or JSON
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Scene, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        SceneDeserializer {
+            property_type_registry: registry,
+        }
+        .deserialize(deserializer)
+    }
+
+    /// Serializes the scene to JSON, which can be read with [Scene::deserialize_json]
+    pub fn serialize_json(
+        &self,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<String, serde_json::Error> {
+        let mut buf = Vec::new();
+        let mut serializer = serde_json::Serializer::pretty(&mut buf);
+        self.serialize(registry, &mut serializer)?;
+        Ok(String::from_utf8(buf).unwrap())
+    }
+
+    pub fn deserialize_json(
+        json: &str,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Scene, serde_json::Error> {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let scene = Scene::deserialize(&mut deserializer, registry)?;
+        deserializer.end()?;
+        Ok(scene)
+    }
+
+    /// Serializes the scene to a compact binary format, which loads faster than RON. Type and property names are
+    /// stored once per scene. The [BinarySceneSaver](crate::BinarySceneSaver) writes scenes in this format.
+    pub fn serialize_binary(
+        &self,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Vec<u8>, bincode::Error> {
+        binary::serialize_binary(self, registry)
+    }
+
+    /// Reads a scene that was written with [Scene::serialize_binary]
+    pub fn deserialize_binary(
+        bytes: &[u8],
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Scene, bincode::Error> {
+        binary::deserialize_binary(bytes, registry)
+    }
+}
+
+fn store_asset_paths(properties: &mut DynamicProperties, asset_server: &AssetServer) {
+    for prop in properties.props.iter_mut() {
+        if let Some(handle_id) = prop.any().downcast_ref::<HandleId>() {
+            if let Some(path) = asset_server.get_handle_path(*handle_id) {
+                *prop = Box::new(AssetPath(path));
+            }
+        } else if let Some(properties) = prop.any_mut().downcast_mut::<DynamicProperties>() {
+            store_asset_paths(properties, asset_server);
+        } else if prop.property_type() == PropertyType::Seq {
+            // sequences like Vec<Handle<T>> are only converted to dynamic properties if they contain handles
+            let mut properties = prop.as_properties().unwrap().to_dynamic();
+            store_asset_paths(&mut properties, asset_server);
+            if contains_asset_paths(&properties) {
+                *prop = Box::new(properties);
+            }
+        }
+    }
 }
 
 pub fn serialize_ron<S>(serialize: S) -> Result<String, ron::Error>
@@ -62,3 +222,454 @@ where
     serialize.serialize(&mut ron_serializer)?;
     Ok(String::from_utf8(buf).unwrap())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SceneLoader, ScenePlugin, SceneSpawner};
+    use bevy_app::{App, TestApp};
+    use bevy_asset::{
+        AddAsset, AssetLoader, AssetPlugin, Assets, Handle, LoaderSettings, MemoryAssetIo,
+    };
+    use bevy_ecs::FromResources;
+    use bevy_property::{DynamicProperties, Properties, PropertiesVal, Property};
+    use bevy_type_registry::{RegisterType, TypeRegistry, TypeRegistryPlugin};
+    use std::path::Path;
+
+    struct Text;
+
+    #[derive(Default)]
+    struct TextLoader;
+
+    impl AssetLoader<Text> for TextLoader {
+        fn from_bytes(
+            &self,
+            _asset_path: &Path,
+            _bytes: Vec<u8>,
+            _settings: &LoaderSettings,
+        ) -> Result<Text> {
+            Ok(Text)
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[derive(Properties, Default)]
+    struct Label {
+        text: Handle<Text>,
+    }
+
+    #[derive(Properties, Default)]
+    struct Labels {
+        texts: Vec<Handle<Text>>,
+    }
+
+    #[derive(Properties, Default)]
+    struct Character {
+        name: String,
+        health: f32,
+        alive: bool,
+        nickname: Option<String>,
+        inventory: Inventory,
+    }
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct Score {
+        points: u32,
+        level: String,
+    }
+
+    #[derive(Properties, Default)]
+    struct Inventory {
+        items: Vec<u32>,
+        gold: u64,
+    }
+
+    #[derive(Properties, Debug, PartialEq)]
+    enum AiState {
+        Idle,
+        Chasing {
+            target: bevy_ecs::Entity,
+            speed: f32,
+        },
+        Fleeing(f32, #[property(ignore)] u32),
+    }
+
+    #[allow(clippy::derivable_impls)]
+    impl Default for AiState {
+        fn default() -> Self {
+            AiState::Idle
+        }
+    }
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct Velocity(f32, f32);
+
+    #[derive(Properties, Default)]
+    struct Primitives {
+        bool: bool,
+        char: char,
+        u8: u8,
+        u16: u16,
+        u32: u32,
+        u64: u64,
+        u128: u128,
+        usize: usize,
+        i8: i8,
+        i16: i16,
+        i32: i32,
+        i64: i64,
+        i128: i128,
+        isize: isize,
+        f32: f32,
+        f64: f64,
+        string: String,
+    }
+
+    fn scene_app() -> TestApp {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.txt", Vec::new());
+        App::build()
+            .add_resource(AssetServer::new(asset_io))
+            .add_plugin(TypeRegistryPlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(ScenePlugin)
+            .add_asset::<Text>()
+            .add_asset_loader::<Text, TextLoader>()
+            .register_component::<Label>()
+            .register_component::<Labels>()
+            .register_component::<Character>()
+            .register_component::<AiState>()
+            .register_component::<Velocity>()
+            .register_component::<Primitives>()
+            .register_property::<Option<String>>()
+            .register_resource::<Score>()
+            .init_resource::<Score>()
+            .test_app()
+    }
+
+    #[test]
+    fn handles_are_saved_as_asset_paths() {
+        let app = scene_app();
+        let (text, other_text) = {
+            let asset_server = app.resource::<AssetServer>();
+            (
+                asset_server.load::<Text, _>("a.txt").unwrap(),
+                asset_server.load::<Text, _>("b.txt").unwrap(),
+            )
+        };
+        let mut world = World::new();
+        world.spawn((
+            Label { text: text.clone() },
+            Labels {
+                texts: vec![text, other_text],
+            },
+        ));
+        let type_registry = app.resource::<TypeRegistry>();
+        let mut scene = Scene::from_world(&world, &type_registry.component.read());
+        scene.store_asset_paths(&app.resource::<AssetServer>());
+        let ron = scene.serialize_ron(&type_registry.property.read()).unwrap();
+        assert!(ron.contains("\"a.txt\""));
+        assert!(ron.contains("\"b.txt\""));
+
+        // the scene is loaded in a new app, which loads the asset when the scene is spawned
+        let mut app = scene_app();
+        let scene = SceneLoader::from_resources(app.resources())
+            .from_bytes(
+                Path::new("a.scn"),
+                ron.into_bytes(),
+                &LoaderSettings::default(),
+            )
+            .unwrap();
+        let scene = app
+            .resources()
+            .get_mut::<Assets<Scene>>()
+            .unwrap()
+            .add(scene);
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner
+            .spawn_sync(&mut app.app.world, &app.app.resources, scene)
+            .unwrap();
+
+        let text_id = HandleId::from_path(Path::new("a.txt"));
+        let other_text_id = HandleId::from_path(Path::new("b.txt"));
+        let label = app.world().query::<&Label>().iter().next().unwrap().text.id;
+        assert_eq!(label, text_id);
+        let labels = app
+            .world()
+            .query::<&Labels>()
+            .iter()
+            .next()
+            .unwrap()
+            .texts
+            .iter()
+            .map(|text| text.id)
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec![text_id, other_text_id]);
+        let asset_server = app.resource::<AssetServer>();
+        assert!(asset_server.get_load_state(text_id).is_some());
+        assert!(asset_server.get_load_state(other_text_id).is_some());
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let app = scene_app();
+        let text = app
+            .resource::<AssetServer>()
+            .load::<Text, _>("a.txt")
+            .unwrap();
+        let mut world = World::new();
+        world.spawn((
+            Character {
+                name: "Ferris".to_string(),
+                health: 0.75,
+                alive: true,
+                nickname: Some("crab".to_string()),
+                inventory: Inventory {
+                    items: vec![3, 1, 4],
+                    gold: 1 << 40,
+                },
+            },
+            Label { text },
+        ));
+        world.spawn((Character::default(),));
+        world.spawn((Primitives {
+            bool: true,
+            char: 'λ',
+            u8: u8::MAX,
+            u16: u16::MAX,
+            u32: u32::MAX,
+            u64: u64::MAX,
+            u128: u128::MAX,
+            usize: usize::MAX,
+            i8: i8::MIN,
+            i16: i16::MIN,
+            i32: i32::MIN,
+            i64: i64::MIN,
+            i128: i128::MIN,
+            isize: isize::MIN,
+            f32: 0.5,
+            f64: -0.25,
+            string: "primitives".to_string(),
+        },));
+        let type_registry = app.resource::<TypeRegistry>();
+        let mut scene = Scene::from_world(&world, &type_registry.component.read());
+        scene.store_asset_paths(&app.resource::<AssetServer>());
+
+        let registry = type_registry.property.read();
+        let ron = scene.serialize_ron(&registry).unwrap();
+        let bytes = scene.serialize_binary(&registry).unwrap();
+        assert!(bytes.len() < ron.len() / 2);
+        let binary_scene = Scene::deserialize_binary(&bytes, &registry).unwrap();
+        assert_eq!(binary_scene.serialize_ron(&registry).unwrap(), ron);
+
+        // the loader picks the format by extension
+        let loader = SceneLoader::from_resources(app.resources());
+        for (path, bytes) in [("a.scn", ron.clone().into_bytes()), ("a.bscn", bytes)] {
+            let scene = loader
+                .from_bytes(Path::new(path), bytes, &LoaderSettings::default())
+                .unwrap();
+            assert_eq!(scene.serialize_ron(&registry).unwrap(), ron);
+        }
+    }
+
+    #[test]
+    fn resources_are_saved_and_applied() {
+        let app = scene_app();
+        *app.resources().get_mut::<Score>().unwrap() = Score {
+            points: 42,
+            level: "caves".to_string(),
+        };
+        let mut world = World::new();
+        world.spawn((Character::default(),));
+        let type_registry = TypeRegistry::clone(&app.resource::<TypeRegistry>());
+        let scene = Scene::from_world(&world, &type_registry.component.read())
+            .with_resources(app.resources(), &type_registry.resource.read());
+        assert_eq!(scene.resources.len(), 1);
+
+        let registry = type_registry.property.read();
+        let ron = scene.serialize_ron(&registry).unwrap();
+        assert!(ron.contains("resources"));
+        let bytes = scene.serialize_binary(&registry).unwrap();
+        let binary_scene = Scene::deserialize_binary(&bytes, &registry).unwrap();
+        assert_eq!(binary_scene.serialize_ron(&registry).unwrap(), ron);
+
+        // the scene is loaded in a new app, whose resource is replaced when the scene is spawned
+        let mut app = scene_app();
+        let scene = SceneLoader::from_resources(app.resources())
+            .from_bytes(
+                Path::new("a.scn"),
+                ron.into_bytes(),
+                &LoaderSettings::default(),
+            )
+            .unwrap();
+        assert_eq!(scene.entities.len(), 1);
+        let scene = app
+            .resources()
+            .get_mut::<Assets<Scene>>()
+            .unwrap()
+            .add(scene);
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner
+            .spawn_sync(&mut app.app.world, &app.app.resources, scene)
+            .unwrap();
+        assert_eq!(
+            *app.resource::<Score>(),
+            Score {
+                points: 42,
+                level: "caves".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn enums_and_tuple_structs_are_properties() {
+        let mut state = AiState::Chasing {
+            target: bevy_ecs::Entity::new(7),
+            speed: 1.0,
+        };
+        assert_eq!(state.variant(), Some("Chasing"));
+        assert_eq!(state.prop_val::<f32>("speed"), Some(&1.0));
+
+        // patches of the active variant only change the given fields
+        let mut patch = DynamicProperties::map_variant("Chasing");
+        patch.set("speed", 2.0f32);
+        state.apply(&patch);
+        assert_eq!(
+            state,
+            AiState::Chasing {
+                target: bevy_ecs::Entity::new(7),
+                speed: 2.0,
+            }
+        );
+
+        // patches of another variant switch to it
+        let mut patch = DynamicProperties::map_variant("Fleeing");
+        patch.set("0", 3.0f32);
+        state.apply(&patch);
+        assert_eq!(state, AiState::Fleeing(3.0, 0));
+        state.apply(&AiState::Idle);
+        assert_eq!(state, AiState::Idle);
+        state.apply(&AiState::Chasing {
+            target: bevy_ecs::Entity::new(8),
+            speed: 4.0,
+        });
+        assert_eq!(
+            state.prop_val::<bevy_ecs::Entity>("target").unwrap().id(),
+            8
+        );
+
+        // variants that can't be built leave the value unchanged, like Chasing without its Entity target
+        state.apply(&AiState::Idle);
+        let mut patch = DynamicProperties::map_variant("Chasing");
+        patch.set("speed", 5.0f32);
+        state.apply(&patch);
+        assert_eq!(state, AiState::Idle);
+        let mut patch = DynamicProperties::map_variant("Sleeping");
+        patch.set("0", 5.0f32);
+        state.set(&patch);
+        assert_eq!(state, AiState::Idle);
+
+        let mut velocity = Velocity(1.0, 2.0);
+        let mut patch = DynamicProperties::map();
+        patch.set("1", 5.0f32);
+        velocity.apply(&patch);
+        assert_eq!(velocity, Velocity(1.0, 5.0));
+    }
+
+    #[test]
+    fn enums_are_saved_with_their_variant() {
+        let app = scene_app();
+        let mut world = World::new();
+        let target = world.spawn((Velocity(1.0, 2.0),));
+        world.spawn((AiState::Chasing { target, speed: 2.5 },));
+        world.spawn((AiState::Fleeing(0.5, 3),));
+        let type_registry = TypeRegistry::clone(&app.resource::<TypeRegistry>());
+        let scene = Scene::from_world(&world, &type_registry.component.read());
+
+        let registry = type_registry.property.read();
+        let ron = scene.serialize_ron(&registry).unwrap();
+        assert!(ron.contains("\"variant\": \"Chasing\""));
+        let bytes = scene.serialize_binary(&registry).unwrap();
+        let binary_scene = Scene::deserialize_binary(&bytes, &registry).unwrap();
+        assert_eq!(binary_scene.serialize_ron(&registry).unwrap(), ron);
+
+        let mut app = scene_app();
+        let scene = SceneLoader::from_resources(app.resources())
+            .from_bytes(
+                Path::new("a.scn"),
+                ron.into_bytes(),
+                &LoaderSettings::default(),
+            )
+            .unwrap();
+        let scene = app
+            .resources()
+            .get_mut::<Assets<Scene>>()
+            .unwrap()
+            .add(scene);
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner
+            .spawn_sync(&mut app.app.world, &app.app.resources, scene)
+            .unwrap();
+
+        let mut states = app
+            .world()
+            .query::<&AiState>()
+            .iter()
+            .map(|state| match *state {
+                AiState::Chasing { speed, .. } => ("Chasing", speed),
+                AiState::Fleeing(speed, ignored) => {
+                    assert_eq!(ignored, 0);
+                    ("Fleeing", speed)
+                }
+                AiState::Idle => ("Idle", 0.0),
+            })
+            .collect::<Vec<_>>();
+        states.sort_by(|a, b| a.0.cmp(b.0));
+        assert_eq!(states, vec![("Chasing", 2.5), ("Fleeing", 0.5)]);
+        let velocity = app
+            .world()
+            .query::<&Velocity>()
+            .iter()
+            .next()
+            .map(|v| (v.0, v.1));
+        assert_eq!(velocity, Some((1.0, 2.0)));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let app = scene_app();
+        *app.resources().get_mut::<Score>().unwrap() = Score {
+            points: 7,
+            level: "forest".to_string(),
+        };
+        let mut world = World::new();
+        let target = world.spawn((Velocity(1.0, 2.0),));
+        world.spawn((
+            Character {
+                name: "Ferris".to_string(),
+                health: 0.75,
+                alive: true,
+                nickname: None,
+                inventory: Inventory {
+                    items: vec![3, 1, 4],
+                    gold: 1 << 40,
+                },
+            },
+            AiState::Chasing { target, speed: 2.5 },
+        ));
+        let type_registry = TypeRegistry::clone(&app.resource::<TypeRegistry>());
+        let scene = Scene::from_world(&world, &type_registry.component.read())
+            .with_resources(app.resources(), &type_registry.resource.read());
+
+        let registry = type_registry.property.read();
+        let ron = scene.serialize_ron(&registry).unwrap();
+        let json = scene.serialize_json(&registry).unwrap();
+        assert!(json.contains("\"variant\": \"Chasing\""));
+        let json_scene = Scene::deserialize_json(&json, &registry).unwrap();
+        assert_eq!(json_scene.serialize_ron(&registry).unwrap(), ron);
+        assert!(Scene::deserialize_json("{\"entities\": 1}", &registry).is_err());
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{Entity, Scene};
+use bevy_property::{DynamicProperties, Properties, Property, PropertyType, PropertyTypeRegistry};
+use bevy_utils::{HashMap, HashSet};
+
+/// The changes to a single entity that is part of both versions of a scene
+pub struct ChangedEntity {
+    pub entity: u32,
+    /// Components that were added or changed. Patches of changed components only contain the changed properties.
+    pub patches: Vec<DynamicProperties>,
+    /// The type names of the components that were removed
+    pub removed_components: Vec<String>,
+}
+
+/// The changes between two versions of a [Scene]. Entities are matched by their id and components by their type name.
+/// [SceneSpawner](crate::SceneSpawner) uses the diff to update spawned instances when a scene is reloaded, which
+/// keeps the properties that didn't change in the file as they are at runtime.
+#[derive(Default)]
+pub struct SceneDiff {
+    /// Entities that are only part of the new version
+    pub added_entities: Vec<u32>,
+    /// Entities that are only part of the old version
+    pub removed_entities: Vec<u32>,
+    pub changed_entities: Vec<ChangedEntity>,
+    /// Resources that were added or changed, only containing the changed properties. Removed resources are ignored,
+    /// since a scene can't remove resources from the app.
+    pub changed_resources: Vec<DynamicProperties>,
+}
+
+impl SceneDiff {
+    pub fn new(old: &Scene, new: &Scene, registry: &PropertyTypeRegistry) -> Self {
+        let mut diff = SceneDiff::default();
+        let old_entities = old
+            .entities
+            .iter()
+            .map(|entity| (entity.entity, entity))
+            .collect::<HashMap<_, _>>();
+        for new_entity in new.entities.iter() {
+            match old_entities.get(&new_entity.entity) {
+                Some(old_entity) => {
+                    if let Some(changed) = diff_entity(old_entity, new_entity, registry) {
+                        diff.changed_entities.push(changed);
+                    }
+                }
+                None => diff.added_entities.push(new_entity.entity),
+            }
+        }
+
+        let new_ids = new
+            .entities
+            .iter()
+            .map(|entity| entity.entity)
+            .collect::<HashSet<_>>();
+        diff.removed_entities = old
+            .entities
+            .iter()
+            .map(|entity| entity.entity)
+            .filter(|id| !new_ids.contains(id))
+            .collect();
+        diff.changed_resources = diff_components(&old.resources, &new.resources, registry);
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_entities.is_empty()
+            && self.removed_entities.is_empty()
+            && self.changed_entities.is_empty()
+            && self.changed_resources.is_empty()
+    }
+}
+
+fn diff_entity(
+    old: &Entity,
+    new: &Entity,
+    registry: &PropertyTypeRegistry,
+) -> Option<ChangedEntity> {
+    let patches = diff_components(&old.components, &new.components, registry);
+    let removed_components = old
+        .components
+        .iter()
+        .filter(|old_component| find_component(&new.components, old_component).is_none())
+        .map(|old_component| old_component.type_name.clone())
+        .collect::<Vec<_>>();
+    if patches.is_empty() && removed_components.is_empty() {
+        return None;
+    }
+
+    Some(ChangedEntity {
+        entity: new.entity,
+        patches,
+        removed_components,
+    })
+}
+
+fn find_component<'a>(
+    components: &'a [DynamicProperties],
+    component: &DynamicProperties,
+) -> Option<&'a DynamicProperties> {
+    components
+        .iter()
+        .find(|other| other.type_name == component.type_name)
+}
+
+fn diff_components(
+    old: &[DynamicProperties],
+    new: &[DynamicProperties],
+    registry: &PropertyTypeRegistry,
+) -> Vec<DynamicProperties> {
+    new.iter()
+        .filter_map(|new_component| match find_component(old, new_component) {
+            Some(old_component) => diff_properties(old_component, new_component, registry),
+            None => Some(new_component.to_dynamic()),
+        })
+        .collect()
+}
+
+/// Returns a patch with the properties of `new` that differ from `old`, or `None` if they are equal. Nested maps are
+/// patched recursively, while sequences and values are replaced as a whole. Enums that switched to another variant are
+/// replaced as well.
+fn diff_properties(
+    old: &dyn Property,
+    new: &dyn Property,
+    registry: &PropertyTypeRegistry,
+) -> Option<DynamicProperties> {
+    match (
+        new.property_type(),
+        old.as_properties(),
+        new.as_properties(),
+    ) {
+        (PropertyType::Map, Some(old_properties), Some(new_properties))
+            if old.type_name() == new.type_name()
+                && old_properties.variant() == new_properties.variant() =>
+        {
+            let mut patch = DynamicProperties::map();
+            patch.type_name = new.type_name().to_string();
+            patch.variant = new_properties.variant().map(|variant| variant.to_string());
+            for (index, new_prop) in new_properties.iter_props().enumerate() {
+                let name = new_properties.prop_name(index).unwrap();
+                let changed = match old_properties.prop(name) {
+                    Some(old_prop) => match new_prop.property_type() {
+                        PropertyType::Map => diff_properties(old_prop, new_prop, registry)
+                            .map(|prop_patch| Box::new(prop_patch) as Box<dyn Property>),
+                        _ if values_equal(old_prop, new_prop, registry) => None,
+                        _ => Some(new_prop.clone_prop()),
+                    },
+                    None => Some(new_prop.clone_prop()),
+                };
+                if let Some(changed) = changed {
+                    patch.set_box(name, changed);
+                }
+            }
+
+            if patch.props.is_empty() {
+                None
+            } else {
+                Some(patch)
+            }
+        }
+        _ if values_equal(old, new, registry) => None,
+        _ => new
+            .as_properties()
+            .map(|properties| properties.to_dynamic()),
+    }
+}
+
+/// Compares the serialized values, since properties don't implement `PartialEq`
+fn values_equal(a: &dyn Property, b: &dyn Property, registry: &PropertyTypeRegistry) -> bool {
+    if a.type_name() != b.type_name() {
+        return false;
+    }
+
+    match (
+        bincode::serialize(a.serializable(registry).borrow()),
+        bincode::serialize(b.serializable(registry).borrow()),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_property::PropertiesVal;
+
+    fn component(type_name: &str, props: &[(&str, Box<dyn Property>)]) -> DynamicProperties {
+        let mut component = DynamicProperties::map();
+        component.type_name = type_name.to_string();
+        for (name, prop) in props {
+            component.set_box(name, prop.clone_prop());
+        }
+        component
+    }
+
+    fn entity(entity: u32, components: Vec<DynamicProperties>) -> Entity {
+        Entity { entity, components }
+    }
+
+    #[test]
+    fn diff_contains_only_changes() {
+        let position =
+            |x: f32, y: f32| component("Position", &[("x", Box::new(x)), ("y", Box::new(y))]);
+        let character = |name: &str, x: f32| {
+            component(
+                "Character",
+                &[
+                    ("name", Box::new(name.to_string())),
+                    ("home", Box::new(position(x, 0.0))),
+                ],
+            )
+        };
+        let old = Scene {
+            entities: vec![
+                entity(0, vec![character("a", 1.0), position(0.0, 0.0)]),
+                entity(1, vec![position(1.0, 1.0)]),
+                entity(2, vec![position(2.0, 2.0)]),
+            ],
+            resources: vec![position(0.0, 0.0)],
+        };
+        let new = Scene {
+            entities: vec![
+                entity(0, vec![character("a", 5.0)]),
+                entity(1, vec![position(1.0, 1.0)]),
+                entity(3, vec![position(3.0, 3.0)]),
+            ],
+            resources: vec![position(0.0, 0.0)],
+        };
+
+        let registry = PropertyTypeRegistry::default();
+        let diff = SceneDiff::new(&old, &new, &registry);
+        assert_eq!(diff.added_entities, vec![3]);
+        assert_eq!(diff.removed_entities, vec![2]);
+        assert!(diff.changed_resources.is_empty());
+        assert_eq!(diff.changed_entities.len(), 1);
+        let changed = &diff.changed_entities[0];
+        assert_eq!(changed.entity, 0);
+        assert_eq!(changed.removed_components, vec!["Position".to_string()]);
+        assert_eq!(changed.patches.len(), 1);
+        let patch = &changed.patches[0];
+        assert_eq!(patch.type_name, "Character");
+        assert!(patch.prop("name").is_none());
+        let home = patch
+            .prop("home")
+            .unwrap()
+            .any()
+            .downcast_ref::<DynamicProperties>()
+            .unwrap();
+        assert_eq!(home.prop_val::<f32>("x"), Some(&5.0));
+        assert!(home.prop("y").is_none());
+
+        assert!(SceneDiff::new(&new, &new, &registry).is_empty());
+    }
+
+    #[test]
+    fn variant_changes_replace_the_component() {
+        let state = |variant: &str, speed: f32| {
+            let mut state = component("AiState", &[("speed", Box::new(speed))]);
+            state.variant = Some(variant.to_string());
+            state
+        };
+        let scene = |state: DynamicProperties| Scene {
+            entities: vec![entity(0, vec![state])],
+            resources: Vec::new(),
+        };
+
+        let registry = PropertyTypeRegistry::default();
+        let diff = SceneDiff::new(
+            &scene(state("Chasing", 1.0)),
+            &scene(state("Chasing", 2.0)),
+            &registry,
+        );
+        let patch = &diff.changed_entities[0].patches[0];
+        assert_eq!(patch.variant.as_deref(), Some("Chasing"));
+
+        let diff = SceneDiff::new(
+            &scene(state("Chasing", 1.0)),
+            &scene(state("Fleeing", 1.0)),
+            &registry,
+        );
+        let patch = &diff.changed_entities[0].patches[0];
+        assert_eq!(patch.variant.as_deref(), Some("Fleeing"));
+        assert_eq!(patch.prop_val::<f32>("speed"), Some(&1.0));
+    }
+}
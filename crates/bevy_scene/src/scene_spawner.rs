@@ -1,21 +1,69 @@
-use crate::Scene;
+use crate::{Scene, SceneDiff};
 use bevy_app::prelude::*;
-use bevy_asset::{AssetEvent, Assets, Handle};
-use bevy_ecs::{Resources, World};
-use bevy_type_registry::TypeRegistry;
+use bevy_asset::{AssetEvent, AssetPath, AssetServer, AssetServerError, Assets, Handle};
+use bevy_ecs::{Entity, EntityMap, MapEntitiesError, Resources, World};
+use bevy_property::{DynamicProperties, Properties, PropertyType};
+use bevy_transform::prelude::{Children, Parent, PreviousParent};
+use bevy_type_registry::{
+    ComponentRegistration, ComponentRegistry, ResourceRegistry, TypeRegistry,
+};
 use bevy_utils::HashMap;
+use std::{any::TypeId, path::PathBuf};
 use thiserror::Error;
 use uuid::Uuid;
 
+/// Changes to the components of a scene that only apply to a single spawned instance. Each patch is applied to the
+/// component with the patch's type name on the given scene entity, after the components of the scene were added. A
+/// patch only needs to contain the properties it changes; if the scene entity doesn't have the component yet, it is
+/// added. Patches for entities that aren't part of the scene are ignored.
+#[derive(Default)]
+pub struct SceneOverrides {
+    patches: Vec<(u32, DynamicProperties)>,
+}
+
+impl SceneOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the properties of a component on the scene entity with the id `entity`
+    pub fn with(mut self, entity: u32, patch: DynamicProperties) -> Self {
+        self.patches.push((entity, patch));
+        self
+    }
+
+    fn get(&self, entity: u32) -> impl Iterator<Item = &DynamicProperties> {
+        self.patches
+            .iter()
+            .filter(move |(patch_entity, _)| *patch_entity == entity)
+            .map(|(_, patch)| patch)
+    }
+}
+
 struct InstanceInfo {
-    entity_map: HashMap<u32, bevy_ecs::Entity>,
+    /// Maps the entities of the scene to the spawned entities
+    entity_map: EntityMap,
+    /// The entity the roots of the scene are added to as children
+    parent: Option<Entity>,
+    overrides: SceneOverrides,
+}
+
+impl InstanceInfo {
+    fn new(parent: Option<Entity>, overrides: SceneOverrides) -> Self {
+        Self {
+            entity_map: EntityMap::default(),
+            parent,
+            overrides,
+        }
+    }
 }
 
+/// Identifies a single spawned instance of a scene
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct InstanceId(Uuid);
+pub struct InstanceId(Uuid);
 
 impl InstanceId {
-    pub fn new() -> Self {
+    fn new() -> Self {
         InstanceId(Uuid::new_v4())
     }
 }
@@ -24,58 +72,171 @@ impl InstanceId {
 pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
+    scene_versions: HashMap<Handle<Scene>, Scene>,
     scene_asset_event_reader: EventReader<AssetEvent<Scene>>,
-    scenes_to_instance: Vec<Handle<Scene>>,
+    scenes_to_instance: Vec<(Handle<Scene>, InstanceId, InstanceInfo)>,
     scenes_to_despawn: Vec<Handle<Scene>>,
+    instances_to_despawn: Vec<InstanceId>,
 }
 
 #[derive(Error, Debug)]
 pub enum SceneSpawnError {
     #[error("Scene contains an unregistered component.")]
     UnregisteredComponent { type_name: String },
+    #[error("Scene contains an unregistered resource.")]
+    UnregisteredResource { type_name: String },
+    #[error("Scene contains a resource that was not added to the app.")]
+    MissingResource { type_name: String },
+    #[error("Scene refers to a type by a short name that is shared by several registered types.")]
+    AmbiguousTypeName { type_name: String },
     #[error("Scene does not exist. Perhaps it is still loading?")]
     NonExistentScene { handle: Handle<Scene> },
+    #[error("Scene refers to an asset that could not be loaded.")]
+    AssetLoadError {
+        path: PathBuf,
+        source: AssetServerError,
+    },
+    #[error("Scene component refers to an entity that is not part of the scene.")]
+    MapEntitiesError(#[from] MapEntitiesError),
+    #[error("Scene instance's parent entity does not exist. Perhaps it was despawned?")]
+    ParentNotFound { parent: Entity },
 }
 
 impl SceneSpawner {
-    pub fn spawn(&mut self, scene_handle: Handle<Scene>) {
-        self.scenes_to_instance.push(scene_handle);
+    pub fn spawn(&mut self, scene_handle: Handle<Scene>) -> InstanceId {
+        self.queue_instance(
+            scene_handle,
+            InstanceInfo::new(None, SceneOverrides::default()),
+        )
+    }
+
+    /// Spawns the scene with its root entities added to the children of `parent`
+    pub fn spawn_as_child(&mut self, scene_handle: Handle<Scene>, parent: Entity) -> InstanceId {
+        self.spawn_as_child_with_overrides(scene_handle, parent, SceneOverrides::default())
+    }
+
+    /// Spawns the scene with its root entities added to the children of `parent`, applying `overrides` to this
+    /// instance only. The overrides are applied again when the scene is reloaded.
+    pub fn spawn_as_child_with_overrides(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        parent: Entity,
+        overrides: SceneOverrides,
+    ) -> InstanceId {
+        self.queue_instance(scene_handle, InstanceInfo::new(Some(parent), overrides))
+    }
+
+    fn queue_instance(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        instance_info: InstanceInfo,
+    ) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.scenes_to_instance
+            .push((scene_handle, instance_id, instance_info));
+        instance_id
     }
 
     pub fn despawn(&mut self, scene_handle: Handle<Scene>) {
         self.scenes_to_despawn.push(scene_handle);
     }
 
+    /// Despawns the entities of a single instance, leaving other instances of the same scene untouched
+    pub fn despawn_instance(&mut self, instance_id: InstanceId) {
+        self.instances_to_despawn.push(instance_id);
+    }
+
     pub fn despawn_sync(
         &mut self,
         world: &mut World,
         scene_handle: Handle<Scene>,
     ) -> Result<(), SceneSpawnError> {
-        if let Some(instance_ids) = self.spawned_scenes.get(&scene_handle) {
+        if let Some(instance_ids) = self.spawned_scenes.remove(&scene_handle) {
             for instance_id in instance_ids {
-                if let Some(instance) = self.spawned_instances.get(&instance_id) {
-                    for entity in instance.entity_map.values() {
-                        let _ = world.despawn(*entity); // Ignore the result, despawn only cares if it exists.
-                    }
+                if let Some(instance) = self.spawned_instances.remove(&instance_id) {
+                    Self::despawn_instance_internal(world, &instance);
                 }
             }
+        }
+        self.scene_versions.remove(&scene_handle);
+        Ok(())
+    }
 
-            self.spawned_scenes.remove(&scene_handle);
+    pub fn despawn_instance_sync(
+        &mut self,
+        world: &mut World,
+        instance_id: InstanceId,
+    ) -> Result<(), SceneSpawnError> {
+        self.scenes_to_instance
+            .retain(|(_, queued_id, _)| *queued_id != instance_id);
+        if let Some(instance) = self.spawned_instances.remove(&instance_id) {
+            Self::despawn_instance_internal(world, &instance);
+            for instance_ids in self.spawned_scenes.values_mut() {
+                instance_ids.retain(|id| *id != instance_id);
+            }
+            self.spawned_scenes
+                .retain(|_, instance_ids| !instance_ids.is_empty());
+            let spawned_scenes = &self.spawned_scenes;
+            self.scene_versions
+                .retain(|scene_handle, _| spawned_scenes.contains_key(scene_handle));
         }
         Ok(())
     }
 
+    fn despawn_instance_internal(world: &mut World, instance_info: &InstanceInfo) {
+        if let Some(parent) = instance_info.parent {
+            if let Ok(mut children) = world.get_mut::<Children>(parent) {
+                children.retain(|child| {
+                    !instance_info
+                        .entity_map
+                        .values()
+                        .any(|entity| entity == *child)
+                });
+            }
+        }
+
+        for entity in instance_info.entity_map.values() {
+            let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
+        }
+    }
+
     pub fn spawn_sync(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handle: Handle<Scene>,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<InstanceId, SceneSpawnError> {
         let instance_id = InstanceId::new();
-        let mut instance_info = InstanceInfo {
-            entity_map: HashMap::default(),
-        };
-        Self::spawn_internal(world, resources, scene_handle, &mut instance_info)?;
+        let instance_info = InstanceInfo::new(None, SceneOverrides::default());
+        self.spawn_instance_sync(world, resources, scene_handle, instance_id, instance_info)?;
+        Ok(instance_id)
+    }
+
+    fn spawn_instance_sync(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        scene_handle: Handle<Scene>,
+        instance_id: InstanceId,
+        mut instance_info: InstanceInfo,
+    ) -> Result<(), SceneSpawnError> {
+        {
+            let scenes = resources.get::<Assets<Scene>>().unwrap();
+            let scene =
+                scenes
+                    .get(&scene_handle)
+                    .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                        handle: scene_handle.clone_weak(),
+                    })?;
+            if let Err(err) = Self::spawn_internal(world, resources, scene, &mut instance_info) {
+                Self::despawn_instance_internal(world, &instance_info);
+                return Err(err);
+            }
+            // the version of the scene that is spawned, which is compared to the new version when it changes
+            self.scene_versions
+                .entry(scene_handle.clone_weak())
+                .or_insert_with(|| scene.clone());
+        }
         self.spawned_instances.insert(instance_id, instance_info);
         let spawned = self
             .spawned_scenes
@@ -88,90 +249,474 @@ impl SceneSpawner {
     fn spawn_internal(
         world: &mut World,
         resources: &Resources,
-        scene_handle: Handle<Scene>,
+        scene: &Scene,
         instance_info: &mut InstanceInfo,
     ) -> Result<(), SceneSpawnError> {
         let type_registry = resources.get::<TypeRegistry>().unwrap();
         let component_registry = type_registry.component.read();
-        let scenes = resources.get::<Assets<Scene>>().unwrap();
-        let scene = scenes
-            .get(&scene_handle)
-            .ok_or_else(|| SceneSpawnError::NonExistentScene {
-                handle: scene_handle,
-            })?;
+        let resource_registry = type_registry.resource.read();
+        let asset_server = resources.get::<AssetServer>().unwrap();
+        let mut writer = SceneWriter::new(
+            resources,
+            &component_registry,
+            &resource_registry,
+            &asset_server,
+        );
+
+        for resource in scene.resources.iter() {
+            writer.write_resource(resource)?;
+        }
 
         for scene_entity in scene.entities.iter() {
-            let entity = *instance_info
+            let entity = instance_info
                 .entity_map
-                .entry(scene_entity.entity)
-                .or_insert_with(|| world.reserve_entity());
-            for component in scene_entity.components.iter() {
-                let component_registration = component_registry
-                    .get_with_name(&component.type_name)
-                    .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
-                        type_name: component.type_name.to_string(),
-                    })?;
-                if world.has_component_type(entity, component_registration.ty) {
-                    if component.type_name != "Camera" {
-                        component_registration.apply_component_to_entity(world, entity, component);
+                .get_or_reserve(Entity::new(scene_entity.entity), world);
+            let components = scene_entity
+                .components
+                .iter()
+                .chain(instance_info.overrides.get(scene_entity.entity));
+            for component in components {
+                writer.write_component(world, entity, component)?;
+            }
+        }
+
+        let spawned = instance_info.entity_map.values().collect::<Vec<_>>();
+        writer.finish(world, instance_info, &spawned)
+    }
+
+    /// Updates an instance of the old version of a scene to `scene` by only writing the changes in `diff`
+    fn update_internal(
+        world: &mut World,
+        resources: &Resources,
+        scene: &Scene,
+        diff: &SceneDiff,
+        instance_info: &mut InstanceInfo,
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let component_registry = type_registry.component.read();
+        let resource_registry = type_registry.resource.read();
+        let asset_server = resources.get::<AssetServer>().unwrap();
+        let mut writer = SceneWriter::new(
+            resources,
+            &component_registry,
+            &resource_registry,
+            &asset_server,
+        );
+        let scene_entities = scene
+            .entities
+            .iter()
+            .map(|scene_entity| (scene_entity.entity, scene_entity))
+            .collect::<HashMap<_, _>>();
+
+        for resource in diff.changed_resources.iter() {
+            writer.write_resource(resource)?;
+        }
+
+        for removed in diff.removed_entities.iter() {
+            let scene_entity = Entity::new(*removed);
+            if let Ok(entity) = instance_info.entity_map.get(scene_entity) {
+                remove_from_parent(world, entity);
+                let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
+                instance_info.entity_map.remove(scene_entity);
+            }
+        }
+
+        let mut added = Vec::new();
+        let mut added_scene_entities = Vec::new();
+        for scene_entity in diff.added_entities.iter().map(|id| scene_entities[id]) {
+            let entity = instance_info
+                .entity_map
+                .get_or_reserve(Entity::new(scene_entity.entity), world);
+            let components = scene_entity
+                .components
+                .iter()
+                .chain(instance_info.overrides.get(scene_entity.entity));
+            for component in components {
+                writer.write_component(world, entity, component)?;
+            }
+            added.push(entity);
+            added_scene_entities.push(Entity::new(scene_entity.entity));
+        }
+
+        for changed in diff.changed_entities.iter() {
+            // entities that were despawned at runtime stay despawned
+            let entity = match instance_info.entity_map.get(Entity::new(changed.entity)) {
+                Ok(entity) if world.contains(entity) => entity,
+                _ => continue,
+            };
+            for type_name in changed.removed_components.iter() {
+                let registration = writer.component_registration(type_name)?;
+                if world.has_component_type(entity, registration.ty) {
+                    registration.remove_component_from_entity(world, entity);
+                }
+            }
+
+            let scene_entity = scene_entities[&changed.entity];
+            for patch in changed.patches.iter() {
+                let registration = writer.component_registration(&patch.type_name)?;
+                let ty = registration.ty;
+                // references to entities can only be mapped for whole components, and components that were removed
+                // at runtime are added back with all of their properties
+                let component =
+                    if registration.maps_entities() || !world.has_component_type(entity, ty) {
+                        scene_entity
+                            .components
+                            .iter()
+                            .find(|component| component.type_name == patch.type_name)
+                            .unwrap_or(patch)
+                    } else {
+                        patch
+                    };
+                writer.write_component(world, entity, component)?;
+
+                // overrides of the instance take precedence over the changes
+                for component in instance_info.overrides.get(changed.entity) {
+                    if writer.component_registration(&component.type_name)?.ty == ty {
+                        writer.write_component(world, entity, component)?;
                     }
-                } else {
-                    component_registration
-                        .add_component_to_entity(world, resources, entity, component);
                 }
             }
         }
-        Ok(())
+
+        let result = writer.finish(world, instance_info, &added);
+        if result.is_err() {
+            // the entities that were added by this update are despawned, the rest of the instance is kept
+            for entity in added {
+                let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
+            }
+            for scene_entity in added_scene_entities {
+                instance_info.entity_map.remove(scene_entity);
+            }
+        }
+        result
     }
 
+    /// Brings the spawned instances of the given scenes up to date with the current version of the scene. Only the
+    /// changes since the previous version are written, so properties, components and entities that were added or
+    /// changed at runtime are kept unless the scene changed them too. Instances that fail to update are logged and
+    /// skipped.
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handles: &[Handle<Scene>],
-    ) -> Result<(), SceneSpawnError> {
+    ) {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let scenes = resources.get::<Assets<Scene>>().unwrap();
         for scene_handle in scene_handles {
+            let scene = match scenes.get(scene_handle) {
+                Some(scene) => scene,
+                None => continue,
+            };
+            let diff = self
+                .scene_versions
+                .get(scene_handle)
+                .map(|old_scene| SceneDiff::new(old_scene, scene, &type_registry.property.read()));
             if let Some(spawned_instances) = self.spawned_scenes.get(scene_handle) {
                 for instance_id in spawned_instances.iter() {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_internal(world, resources, *scene_handle, instance_info)?;
+                        let result = match diff {
+                            Some(ref diff) => {
+                                Self::update_internal(world, resources, scene, diff, instance_info)
+                            }
+                            None => Self::spawn_internal(world, resources, scene, instance_info),
+                        };
+                        if let Err(err) = result {
+                            log::error!(
+                                "failed to update scene instance {:?}: {}",
+                                instance_id,
+                                err
+                            );
+                        }
                     }
                 }
             }
+            self.scene_versions
+                .insert(scene_handle.clone_weak(), scene.clone());
         }
-        Ok(())
     }
 
-    pub fn despawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
+    /// Despawns the queued scenes and instances. Failures are logged and don't affect the other queued scenes.
+    pub fn despawn_queued_scenes(&mut self, world: &mut World) {
         let scenes_to_despawn = std::mem::take(&mut self.scenes_to_despawn);
-
         for scene_handle in scenes_to_despawn {
-            self.despawn_sync(world, scene_handle)?;
+            if let Err(err) = self.despawn_sync(world, scene_handle) {
+                log::error!("failed to despawn scene: {}", err);
+            }
+        }
+
+        let instances_to_despawn = std::mem::take(&mut self.instances_to_despawn);
+        for instance_id in instances_to_despawn {
+            if let Err(err) = self.despawn_instance_sync(world, instance_id) {
+                log::error!(
+                    "failed to despawn scene instance {:?}: {}",
+                    instance_id,
+                    err
+                );
+            }
+        }
+    }
+
+    /// Spawns the queued instances whose scenes are loaded. Instances that fail to spawn are logged and dropped,
+    /// without leaving any of their entities in the world.
+    pub fn spawn_queued_scenes(&mut self, world: &mut World, resources: &Resources) {
+        let scenes_to_spawn = std::mem::take(&mut self.scenes_to_instance);
+
+        for (scene_handle, instance_id, instance_info) in scenes_to_spawn {
+            // keep the instance queued until the scene is loaded
+            let scene_exists = resources
+                .get::<Assets<Scene>>()
+                .unwrap()
+                .contains(&scene_handle);
+            if scene_exists {
+                if let Err(err) = self.spawn_instance_sync(
+                    world,
+                    resources,
+                    scene_handle,
+                    instance_id,
+                    instance_info,
+                ) {
+                    log::error!("failed to spawn scene instance {:?}: {}", instance_id, err);
+                }
+            } else {
+                self.scenes_to_instance
+                    .push((scene_handle, instance_id, instance_info));
+            }
+        }
+    }
+}
+
+/// Writes the components and resources of a scene to the world
+struct SceneWriter<'a> {
+    resources: &'a Resources,
+    component_registry: &'a ComponentRegistry,
+    resource_registry: &'a ResourceRegistry,
+    asset_server: &'a AssetServer,
+    /// The entities whose components were written, by the type of components that refer to other entities
+    mapped_entities: HashMap<TypeId, Vec<Entity>>,
+}
+
+impl<'a> SceneWriter<'a> {
+    fn new(
+        resources: &'a Resources,
+        component_registry: &'a ComponentRegistry,
+        resource_registry: &'a ResourceRegistry,
+        asset_server: &'a AssetServer,
+    ) -> Self {
+        Self {
+            resources,
+            component_registry,
+            resource_registry,
+            asset_server,
+            mapped_entities: HashMap::default(),
+        }
+    }
+
+    fn component_registration(
+        &self,
+        type_name: &str,
+    ) -> Result<&'a ComponentRegistration, SceneSpawnError> {
+        self.component_registry
+            .get_with_name(type_name)
+            .ok_or_else(|| {
+                if self.component_registry.names.is_ambiguous(type_name) {
+                    SceneSpawnError::AmbiguousTypeName {
+                        type_name: type_name.to_string(),
+                    }
+                } else {
+                    SceneSpawnError::UnregisteredComponent {
+                        type_name: type_name.to_string(),
+                    }
+                }
+            })
+    }
+
+    fn write_resource(&self, resource: &DynamicProperties) -> Result<(), SceneSpawnError> {
+        let loaded_resource;
+        let resource = if contains_asset_paths(resource) {
+            let mut resource = resource.to_dynamic();
+            load_asset_paths(&mut resource, self.asset_server)?;
+            loaded_resource = resource;
+            &loaded_resource
+        } else {
+            resource
+        };
+        let resource_registration = self
+            .resource_registry
+            .get_with_name(&resource.type_name)
+            .ok_or_else(|| {
+                if self
+                    .resource_registry
+                    .names
+                    .is_ambiguous(&resource.type_name)
+                {
+                    SceneSpawnError::AmbiguousTypeName {
+                        type_name: resource.type_name.to_string(),
+                    }
+                } else {
+                    SceneSpawnError::UnregisteredResource {
+                        type_name: resource.type_name.to_string(),
+                    }
+                }
+            })?;
+        if !resource_registration.apply_resource(self.resources, resource) {
+            return Err(SceneSpawnError::MissingResource {
+                type_name: resource.type_name.to_string(),
+            });
         }
         Ok(())
     }
 
-    pub fn spawn_queued_scenes(
+    /// Applies `component` to the entity's component of the same type, or adds it if the entity doesn't have one
+    fn write_component(
         &mut self,
         world: &mut World,
-        resources: &Resources,
+        entity: Entity,
+        component: &DynamicProperties,
     ) -> Result<(), SceneSpawnError> {
-        let scenes_to_spawn = std::mem::take(&mut self.scenes_to_instance);
+        let loaded_component;
+        let component = if contains_asset_paths(component) {
+            let mut component = component.to_dynamic();
+            load_asset_paths(&mut component, self.asset_server)?;
+            loaded_component = component;
+            &loaded_component
+        } else {
+            component
+        };
+        let component_registration = self.component_registration(&component.type_name)?;
+        if world.has_component_type(entity, component_registration.ty) {
+            component_registration.apply_component_to_entity(world, entity, component);
+        } else {
+            component_registration.add_component_to_entity(
+                world,
+                self.resources,
+                entity,
+                component,
+            );
+        }
+        if component_registration.maps_entities() {
+            self.mapped_entities
+                .entry(component_registration.ty)
+                .or_default()
+                .push(entity);
+        }
+        Ok(())
+    }
 
-        for scene_handle in scenes_to_spawn {
-            match self.spawn_sync(world, resources, scene_handle) {
-                Ok(_) => {}
-                Err(SceneSpawnError::NonExistentScene { .. }) => {
-                    self.scenes_to_instance.push(scene_handle)
+    /// Maps the entities of the written components and adds the roots among `spawned` to the instance's parent
+    fn finish(
+        self,
+        world: &mut World,
+        instance_info: &InstanceInfo,
+        spawned: &[Entity],
+    ) -> Result<(), SceneSpawnError> {
+        // the components that were just written still refer to the entities of the scene
+        for (ty, entities) in self.mapped_entities.iter() {
+            self.component_registry.get(ty).unwrap().map_entities(
+                world,
+                &instance_info.entity_map,
+                entities,
+            )?;
+        }
+
+        if let Some(parent) = instance_info.parent {
+            for root in spawned.iter().copied() {
+                if world.get::<Parent>(root).is_err() {
+                    add_child(world, parent, root)?;
                 }
-                Err(err) => return Err(err),
             }
         }
-
         Ok(())
     }
 }
 
+/// Writes the scene to the world without keeping track of the spawned entities. The entities of the scene that are
+/// in `entity_map` are written to the entities they are mapped to instead of new entities.
+pub(crate) fn write_scene(
+    scene: &Scene,
+    world: &mut World,
+    resources: &Resources,
+    entity_map: EntityMap,
+) -> Result<EntityMap, SceneSpawnError> {
+    let mut instance_info = InstanceInfo::new(None, SceneOverrides::default());
+    instance_info.entity_map = entity_map;
+    SceneSpawner::spawn_internal(world, resources, scene, &mut instance_info)?;
+    Ok(instance_info.entity_map)
+}
+
+fn add_child(world: &mut World, parent: Entity, child: Entity) -> Result<(), SceneSpawnError> {
+    if !world.contains(parent) {
+        return Err(SceneSpawnError::ParentNotFound { parent });
+    }
+
+    // the child was just written by the scene writer, so it exists
+    world
+        .insert(child, (Parent(parent), PreviousParent(Some(parent))))
+        .unwrap();
+    let mut added = false;
+    if let Ok(mut children) = world.get_mut::<Children>(parent) {
+        children.push(child);
+        added = true;
+    }
+
+    if !added {
+        world
+            .insert_one(parent, Children::with(&[child]))
+            .map_err(|_| SceneSpawnError::ParentNotFound { parent })?;
+    }
+    Ok(())
+}
+
+fn remove_from_parent(world: &mut World, child: Entity) {
+    let parent = match world.get::<Parent>(child) {
+        Ok(parent) => parent.0,
+        Err(_) => return,
+    };
+    if let Ok(mut children) = world.get_mut::<Children>(parent) {
+        children.retain(|entity| *entity != child);
+    }
+}
+
+pub(crate) fn contains_asset_paths(properties: &dyn Properties) -> bool {
+    properties.iter_props().any(|prop| {
+        prop.any().is::<AssetPath>()
+            || matches!(
+                prop.as_properties(),
+                Some(properties) if contains_asset_paths(properties)
+            )
+    })
+}
+
+/// Replaces the asset paths that were stored with [Scene::store_asset_paths] with the ids of the assets, loading
+/// the assets that aren't loaded yet
+fn load_asset_paths(
+    properties: &mut DynamicProperties,
+    asset_server: &AssetServer,
+) -> Result<(), SceneSpawnError> {
+    for prop in properties.props.iter_mut() {
+        if let Some(AssetPath(path)) = prop.any().downcast_ref::<AssetPath>() {
+            let handle_id = asset_server.get_or_load_untyped(path).map_err(|source| {
+                SceneSpawnError::AssetLoadError {
+                    path: path.clone(),
+                    source,
+                }
+            })?;
+            *prop = Box::new(handle_id);
+        } else if let Some(properties) = prop.any_mut().downcast_mut::<DynamicProperties>() {
+            load_asset_paths(properties, asset_server)?;
+        } else if prop.property_type() == PropertyType::Seq
+            && contains_asset_paths(prop.as_properties().unwrap())
+        {
+            // sequences like Vec<AssetPath> are converted to dynamic properties to replace their elements
+            let mut properties = prop.as_properties().unwrap().to_dynamic();
+            load_asset_paths(&mut properties, asset_server)?;
+            *prop = Box::new(properties);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
     let mut scene_spawner = resources.get_mut::<SceneSpawner>().unwrap();
     let scene_asset_events = resources.get::<Events<AssetEvent<Scene>>>().unwrap();
@@ -183,14 +728,315 @@ pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
     {
         if let AssetEvent::Modified { handle } = event {
             if scene_spawner.spawned_scenes.contains_key(handle) {
-                updated_spawned_scenes.push(*handle);
+                updated_spawned_scenes.push(handle.clone_weak());
             }
         }
     }
 
-    scene_spawner.despawn_queued_scenes(world).unwrap();
-    scene_spawner.spawn_queued_scenes(world, resources).unwrap();
-    scene_spawner
-        .update_spawned_scenes(world, resources, &updated_spawned_scenes)
-        .unwrap();
+    scene_spawner.despawn_queued_scenes(world);
+    scene_spawner.spawn_queued_scenes(world, resources);
+    scene_spawner.update_spawned_scenes(world, resources, &updated_spawned_scenes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScenePlugin;
+    use bevy_app::{App, TestApp};
+    use bevy_asset::AssetPlugin;
+    use bevy_ecs::{FromResources, MapEntities};
+    use bevy_type_registry::{RegisterType, TypeRegistryPlugin};
+
+    #[derive(Properties, Debug, Clone, Copy, PartialEq)]
+    struct Target(Entity);
+
+    impl FromResources for Target {
+        fn from_resources(_resources: &Resources) -> Self {
+            Target(Entity::new(u32::MAX))
+        }
+    }
+
+    #[derive(Properties, Default, Debug, Clone, PartialEq)]
+    struct Level {
+        value: u32,
+        name: String,
+    }
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            self.0 = entity_map.get(self.0)?;
+            Ok(())
+        }
+    }
+
+    fn scene_app() -> TestApp {
+        App::build()
+            .add_plugin(TypeRegistryPlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(ScenePlugin)
+            .register_component::<Level>()
+            .register_resource::<Level>()
+            .register_component_with_mapped_entities::<Target>()
+            .register_component_with_mapped_entities::<Parent>()
+            .test_app()
+    }
+
+    fn scene_from_world(app: &TestApp, scene_world: &World) -> Scene {
+        Scene::from_world(
+            scene_world,
+            &app.resource::<TypeRegistry>().component.read(),
+        )
+    }
+
+    fn add_scene(app: &TestApp, scene_world: &World) -> Handle<Scene> {
+        let scene = scene_from_world(app, scene_world);
+        app.resources()
+            .get_mut::<Assets<Scene>>()
+            .unwrap()
+            .add(scene)
+    }
+
+    #[test]
+    fn spawned_entities_are_mapped() {
+        let mut app = scene_app();
+
+        let mut scene_world = World::new();
+        let a = scene_world.reserve_entity();
+        let b = scene_world.reserve_entity();
+        scene_world.insert_one(a, Target(b)).unwrap();
+        scene_world.insert_one(b, Target(a)).unwrap();
+        let scene = add_scene(&app, &scene_world);
+
+        let mut scene_spawner = SceneSpawner::default();
+        for _ in 0..2 {
+            scene_spawner
+                .spawn_sync(&mut app.app.world, &app.app.resources, scene.clone())
+                .unwrap();
+        }
+
+        let targets = app
+            .world()
+            .query::<(Entity, &Target)>()
+            .iter()
+            .map(|(entity, target)| (entity, *target))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(targets.len(), 4);
+        for (entity, Target(target)) in targets.iter() {
+            assert_ne!(entity, target);
+            assert_eq!(targets[target], Target(*entity));
+        }
+    }
+
+    #[test]
+    fn failed_spawns_dont_stop_the_system() {
+        let mut app = scene_app();
+
+        // the target isn't part of the scene, so it can't be mapped when the scene is spawned
+        let mut scene_world = World::new();
+        let outside = scene_world.spawn(());
+        scene_world.spawn((Level::default(), Target(outside)));
+        let mut broken = scene_from_world(&app, &scene_world);
+        broken
+            .entities
+            .retain(|entity| entity.entity != outside.id());
+        let mut scene_world = World::new();
+        scene_world.spawn((Level {
+            value: 1,
+            name: "valid".to_string(),
+        },));
+        let valid = scene_from_world(&app, &scene_world);
+        {
+            let mut scenes = app.resources().get_mut::<Assets<Scene>>().unwrap();
+            let broken = scenes.add(broken);
+            let valid = scenes.add(valid);
+            let mut scene_spawner = app.resources().get_mut::<SceneSpawner>().unwrap();
+            scene_spawner.spawn(broken);
+            scene_spawner.spawn(valid);
+        }
+
+        scene_spawner_system(&mut app.app.world, &mut app.app.resources);
+        let levels = app
+            .world()
+            .query::<&Level>()
+            .iter()
+            .map(|level| level.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(levels, vec!["valid".to_string()]);
+        assert_eq!(app.world().query::<&Target>().iter().count(), 0);
+    }
+
+    #[test]
+    fn resource_errors_dont_stop_the_system() {
+        let mut app = scene_app();
+
+        let mut unregistered = DynamicProperties::map();
+        unregistered.type_name = "Unregistered".to_string();
+        let mut missing = DynamicProperties::map();
+        missing.type_name = std::any::type_name::<Level>().to_string();
+        let mut scene_world = World::new();
+        scene_world.spawn((Level::default(),));
+        let scene = scene_from_world(&app, &scene_world);
+        {
+            let mut scenes = app.resources().get_mut::<Assets<Scene>>().unwrap();
+            let mut scene_spawner = app.resources().get_mut::<SceneSpawner>().unwrap();
+            for resource in [unregistered, missing].iter() {
+                let mut scene = scene.clone();
+                scene.resources.push(resource.to_dynamic());
+                scene_spawner.spawn(scenes.add(scene));
+            }
+            scene_spawner.spawn(scenes.add(scene));
+        }
+
+        // only the scene without resources is spawned, since Level was not added as a resource
+        scene_spawner_system(&mut app.app.world, &mut app.app.resources);
+        assert_eq!(app.world().query::<&Level>().iter().count(), 1);
+    }
+
+    #[test]
+    fn spawn_as_child_with_overrides() {
+        let mut app = scene_app();
+
+        let mut scene_world = World::new();
+        let root = scene_world.spawn((Level {
+            value: 1,
+            name: "root".to_string(),
+        },));
+        let child = scene_world.spawn((Level::default(), Parent(root)));
+        let scene = add_scene(&app, &scene_world);
+
+        let first_parent = app.app.world.spawn(());
+        let second_parent = app.app.world.spawn(());
+        let mut patch = DynamicProperties::map();
+        patch.type_name = std::any::type_name::<Level>().to_string();
+        patch.set("value", 5u32);
+        let mut scene_spawner = SceneSpawner::default();
+        let first = scene_spawner.spawn_as_child(scene.clone(), first_parent);
+        scene_spawner.spawn_as_child_with_overrides(
+            scene.clone(),
+            second_parent,
+            SceneOverrides::new().with(root.id(), patch),
+        );
+        scene_spawner.spawn_queued_scenes(&mut app.app.world, &app.app.resources);
+        // reloading the scene keeps the parents and the overrides
+        scene_spawner.update_spawned_scenes(&mut app.app.world, &app.app.resources, &[scene]);
+
+        let world = app.world();
+        let spawned_root = |parent: Entity| {
+            let children = world.get::<Children>(parent).unwrap();
+            assert_eq!(children.len(), 1);
+            children[0]
+        };
+        let first_root = spawned_root(first_parent);
+        let second_root = spawned_root(second_parent);
+        assert_eq!(world.get::<Parent>(first_root).unwrap().0, first_parent);
+        assert_eq!(world.get::<Level>(first_root).unwrap().value, 1);
+        let second_level = world.get::<Level>(second_root).unwrap();
+        assert_eq!(second_level.value, 5);
+        assert_eq!(second_level.name, "root");
+        let children_of = |root: Entity| {
+            world
+                .query::<(Entity, &Parent)>()
+                .iter()
+                .filter(|(_, parent)| parent.0 == root)
+                .map(|(entity, _)| entity)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(children_of(first_root).len(), 1);
+        assert_eq!(children_of(second_root).len(), 1);
+        assert_ne!(children_of(first_root)[0], child);
+
+        scene_spawner
+            .despawn_instance_sync(&mut app.app.world, first)
+            .unwrap();
+        let world = app.world();
+        assert_eq!(world.query::<&Level>().iter().count(), 2);
+        assert!(world.get::<Level>(first_root).is_err());
+        assert!(world.get::<Children>(first_parent).unwrap().is_empty());
+        assert_eq!(world.get::<Level>(second_root).unwrap().value, 5);
+    }
+
+    #[test]
+    fn despawned_parents_dont_panic() {
+        let mut app = scene_app();
+
+        let mut scene_world = World::new();
+        scene_world.spawn((Level::default(),));
+        let scene = add_scene(&app, &scene_world);
+
+        // the parent is despawned before the queued instance is spawned
+        let parent = app.app.world.spawn(());
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner.spawn_as_child(scene.clone(), parent);
+        app.app.world.despawn(parent).unwrap();
+        scene_spawner.spawn_queued_scenes(&mut app.app.world, &app.app.resources);
+        assert_eq!(app.world().query::<&Level>().iter().count(), 0);
+
+        // the parent is despawned before the spawned instance is updated with a new root entity
+        let parent = app.app.world.spawn(());
+        scene_spawner.spawn_as_child(scene.clone(), parent);
+        scene_spawner.spawn_queued_scenes(&mut app.app.world, &app.app.resources);
+        assert_eq!(app.world().query::<&Level>().iter().count(), 1);
+        app.app.world.despawn(parent).unwrap();
+        scene_world.spawn((Level::default(),));
+        let new_scene = scene_from_world(&app, &scene_world);
+        app.resources()
+            .get_mut::<Assets<Scene>>()
+            .unwrap()
+            .set(&scene, new_scene);
+        scene_spawner.update_spawned_scenes(&mut app.app.world, &app.app.resources, &[scene]);
+        assert_eq!(app.world().query::<&Level>().iter().count(), 1);
+    }
+
+    #[test]
+    fn hot_reload_only_writes_changes() {
+        let mut app = scene_app();
+
+        let level = |value: u32, name: &str| Level {
+            value,
+            name: name.to_string(),
+        };
+        let mut scene_world = World::new();
+        let kept = scene_world.spawn((level(1, "kept"),));
+        let removed = scene_world.spawn((level(2, "removed"),));
+        let scene = add_scene(&app, &scene_world);
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner
+            .spawn_sync(&mut app.app.world, &app.app.resources, scene.clone())
+            .unwrap();
+        let spawned_kept = app
+            .world()
+            .query::<(Entity, &Level)>()
+            .iter()
+            .find(|(_, level)| level.name == "kept")
+            .unwrap()
+            .0;
+        app.app.world.get_mut::<Level>(spawned_kept).unwrap().name =
+            "changed at runtime".to_string();
+
+        // the new version changes the value of one entity, removes the other and adds a third one
+        scene_world.get_mut::<Level>(kept).unwrap().value = 10;
+        scene_world.despawn(removed).unwrap();
+        scene_world.spawn((level(3, "added"),));
+        let new_scene = scene_from_world(&app, &scene_world);
+        app.resources()
+            .get_mut::<Assets<Scene>>()
+            .unwrap()
+            .set(&scene, new_scene);
+        scene_spawner.update_spawned_scenes(&mut app.app.world, &app.app.resources, &[scene]);
+
+        let mut levels = app
+            .world()
+            .query::<(Entity, &Level)>()
+            .iter()
+            .map(|(entity, level)| (entity, level.clone()))
+            .collect::<Vec<_>>();
+        levels.sort_by_key(|(_, level)| level.value);
+        assert_eq!(
+            levels,
+            vec![
+                (levels[0].0, level(3, "added")),
+                (spawned_kept, level(10, "changed at runtime"))
+            ]
+        );
+    }
 }
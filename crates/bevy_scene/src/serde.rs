@@ -21,13 +21,46 @@ impl<'a> SceneSerializer<'a> {
     }
 }
 
+/// Scenes without resources are serialized as a list of entities. Scenes with resources are serialized as a struct
+/// with a `resources` and an `entities` field.
 impl<'a> Serialize for SceneSerializer<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in self.scene.entities.iter() {
+        let entities = EntitiesSerializer {
+            entities: &self.scene.entities,
+            registry: self.registry,
+        };
+        if self.scene.resources.is_empty() {
+            return entities.serialize(serializer);
+        }
+
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        state.serialize_field(
+            SCENE_FIELD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.scene.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(SCENE_FIELD_ENTITIES, &entities)?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [Entity],
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities.iter() {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -92,12 +125,96 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        let mut scene = Scene::default();
-        scene.entities = deserializer.deserialize_seq(SceneEntitySeqVisiter {
+        deserializer.deserialize_any(SceneVisiter {
             property_type_registry: self.property_type_registry,
-        })?;
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Resources,
+    Entities,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_RESOURCES: &str = "resources";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+
+struct SceneVisiter<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
 
-        Ok(scene)
+impl<'a, 'de> Visitor<'de> for SceneVisiter<'a> {
+    type Value = Scene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of entities or scene struct")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entities = SceneEntitySeqVisiter {
+            property_type_registry: self.property_type_registry,
+        }
+        .visit_seq(seq)?;
+        Ok(Scene {
+            entities,
+            resources: Vec::new(),
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.property_type_registry,
+                    })?);
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntityVecDeserializer {
+                        property_type_registry: self.property_type_registry,
+                    })?);
+                }
+            }
+        }
+
+        Ok(Scene {
+            entities: entities.ok_or_else(|| Error::missing_field(SCENE_FIELD_ENTITIES))?,
+            resources: resources.unwrap_or_default(),
+        })
+    }
+}
+
+struct SceneEntityVecDeserializer<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntityVecDeserializer<'a> {
+    type Value = Vec<Entity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisiter {
+            property_type_registry: self.property_type_registry,
+        })
     }
 }
 
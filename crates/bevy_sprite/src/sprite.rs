@@ -44,8 +44,8 @@ pub fn sprite_system(
             SpriteResizeMode::Manual => continue,
             SpriteResizeMode::Automatic => {
                 let material = materials.get(&handle).unwrap();
-                if let Some(texture_handle) = material.texture {
-                    if let Some(texture) = textures.get(&texture_handle) {
+                if let Some(texture_handle) = &material.texture {
+                    if let Some(texture) = textures.get(texture_handle) {
                         sprite.size = texture.size;
                     }
                 }
@@ -1,3 +1,4 @@
+use crate::Task;
 use std::{
     future::Future,
     mem,
@@ -53,6 +54,18 @@ impl TaskPool {
         1
     }
 
+    /// Spawns a static future onto the thread pool. As there are no other threads, the future is run to completion
+    /// before this returns.
+    pub fn spawn<T>(&self, future: impl Future<Output = T> + 'static) -> Task<T>
+    where
+        T: 'static,
+    {
+        let executor = async_executor::LocalExecutor::new();
+        let task = executor.spawn(future);
+        while executor.try_tick() {}
+        Task::new(task)
+    }
+
     /// Allows spawning non-`static futures on the thread pool. The function takes a callback,
     /// passing a scope object into it. The scope object provided to the callback can be used
     /// to spawn tasks. This function will await the completion of all tasks before returning.
@@ -1,3 +1,4 @@
+use futures_lite::future;
 use std::{
     future::Future,
     pin::Pin,
@@ -16,6 +17,11 @@ use std::{
 pub struct Task<T>(async_executor::Task<T>);
 
 impl<T> Task<T> {
+    /// Creates a new task from a given async_executor::Task
+    pub(crate) fn new(task: async_executor::Task<T>) -> Self {
+        Self(task)
+    }
+
     /// Detaches the task to let it keep running in the background. See `async_executor::Task::detach`
     pub fn detach(self) {
         self.0.detach();
@@ -33,6 +39,14 @@ impl<T> Task<T> {
     pub async fn cancel(self) -> Option<T> {
         self.0.cancel().await
     }
+
+    /// Polls the task once without blocking. Returns the task's output if it has completed, or
+    /// [`None`] if it is still running.
+    ///
+    /// Once this has returned the output, the task must not be polled again.
+    pub fn poll_once(&mut self) -> Option<T> {
+        future::block_on(future::poll_once(self))
+    }
 }
 
 impl<T> Future for Task<T> {
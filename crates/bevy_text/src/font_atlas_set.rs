@@ -117,7 +117,7 @@ impl FontAtlasSet {
                     .find_map(|atlas| {
                         atlas
                             .get_char_index(character)
-                            .map(|char_index| (char_index, atlas.texture_atlas))
+                            .map(|char_index| (char_index, atlas.texture_atlas.clone_weak()))
                     })
                     .map(|(char_index, texture_atlas)| GlyphAtlasInfo {
                         texture_atlas,
@@ -1,13 +1,18 @@
 use crate::Font;
 use anyhow::Result;
-use bevy_asset::AssetLoader;
+use bevy_asset::{AssetLoader, LoaderSettings};
 use std::path::Path;
 
 #[derive(Default)]
 pub struct FontLoader;
 
 impl AssetLoader<Font> for FontLoader {
-    fn from_bytes(&self, _asset_path: &Path, bytes: Vec<u8>) -> Result<Font> {
+    fn from_bytes(
+        &self,
+        _asset_path: &Path,
+        bytes: Vec<u8>,
+        _settings: &LoaderSettings,
+    ) -> Result<Font> {
         Ok(Font::try_from_bytes(bytes)?)
     }
 
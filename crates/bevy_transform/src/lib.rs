@@ -25,9 +25,11 @@ pub struct TransformPlugin;
 
 impl Plugin for TransformPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.register_component::<Children>()
-            .register_component::<Parent>()
+        app.register_component_with_mapped_entities::<Children>()
+            .register_component_with_mapped_entities::<Parent>()
             .register_component::<Transform>()
+            // the entities of Children are stored in a SmallVec, which must be registered to load it from scenes
+            .register_property::<smallvec::SmallVec<[Entity; 8]>>()
             // add transform systems to startup so the first update is "correct"
             .add_startup_systems(transform_systems())
             .add_systems_to_stage(stage::POST_UPDATE, transform_systems());
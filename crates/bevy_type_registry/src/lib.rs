@@ -1,10 +1,15 @@
 mod register_type;
+#[cfg(feature = "dynamic_plugins")]
+mod reload;
 mod type_registry;
 
 pub use register_type::*;
+#[cfg(feature = "dynamic_plugins")]
+pub use reload::*;
 pub use type_registry::*;
 
 use bevy_app::prelude::*;
+use bevy_ecs::Entity;
 use bevy_property::DynamicProperties;
 
 #[derive(Default)]
@@ -13,6 +18,10 @@ pub struct TypeRegistryPlugin;
 impl Plugin for TypeRegistryPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_resource::<TypeRegistry>()
-            .register_property::<DynamicProperties>();
+            .register_property::<DynamicProperties>()
+            .register_property::<Entity>();
+
+        #[cfg(feature = "dynamic_plugins")]
+        app.add_plugin_reload_hook(ComponentReloadHook::default());
     }
 }
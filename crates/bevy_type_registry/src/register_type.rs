@@ -1,18 +1,38 @@
 use crate::TypeRegistry;
 use bevy_app::AppBuilder;
-use bevy_ecs::{Component, FromResources};
-use bevy_property::{DeserializeProperty, Properties, Property};
+use bevy_ecs::{Component, FromResources, MapEntities, Resource};
+use bevy_property::{DeserializeProperty, IntoPropertyMethod, Properties, Property};
 
 pub trait RegisterType {
     fn register_component<T>(&mut self) -> &mut Self
     where
         T: Properties + DeserializeProperty + Component + FromResources;
+    /// Registers a component with [Entity](bevy_ecs::Entity) fields, which are mapped to the spawned entities when
+    /// the component is spawned as part of a scene
+    fn register_component_with_mapped_entities<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Component + FromResources + MapEntities;
+    /// Registers a component and adds it to the allow-list of persistent components in the
+    /// [ComponentRegistry](crate::ComponentRegistry)
+    fn register_persistent_component<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Component + FromResources;
+    /// Registers a resource, which allows it to be stored in scenes
+    fn register_resource<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Resource + FromResources;
     fn register_properties<T>(&mut self) -> &mut Self
     where
         T: Properties + DeserializeProperty + FromResources;
     fn register_property<T>(&mut self) -> &mut Self
     where
         T: Property + DeserializeProperty;
+    /// Registers a method that can be called by name on properties of type `T`. See
+    /// [PropertyTypeRegistry::register_method](bevy_property::PropertyTypeRegistry::register_method).
+    fn register_method<T, Args, F>(&mut self, name: &str, method: F) -> &mut Self
+    where
+        T: Property,
+        F: IntoPropertyMethod<T, Args>;
 }
 
 impl RegisterType for AppBuilder {
@@ -28,6 +48,48 @@ impl RegisterType for AppBuilder {
         self
     }
 
+    fn register_component_with_mapped_entities<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Component + FromResources + MapEntities,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry
+                .component
+                .write()
+                .register_with_mapped_entities::<T>();
+            type_registry.property.write().register::<T>();
+        }
+        self
+    }
+
+    fn register_persistent_component<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Component + FromResources,
+    {
+        self.register_component::<T>();
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry
+                .component
+                .write()
+                .set_persistent(std::any::TypeId::of::<T>());
+        }
+        self
+    }
+
+    fn register_resource<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Resource + FromResources,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry.resource.write().register::<T>();
+            type_registry.property.write().register::<T>();
+        }
+        self
+    }
+
     fn register_properties<T>(&mut self) -> &mut Self
     where
         T: Properties + DeserializeProperty + Component + FromResources,
@@ -49,4 +111,16 @@ impl RegisterType for AppBuilder {
         }
         self
     }
+
+    fn register_method<T, Args, F>(&mut self, name: &str, method: F) -> &mut Self
+    where
+        T: Property,
+        F: IntoPropertyMethod<T, Args>,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry.property.write().register_method(name, method);
+        }
+        self
+    }
 }
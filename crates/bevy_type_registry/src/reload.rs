@@ -0,0 +1,227 @@
+use crate::TypeRegistry;
+use bevy_app::PluginReloadHook;
+use bevy_ecs::{Entity, Resources, World};
+use bevy_property::ron::{deserialize_dynamic_properties, serialize_properties};
+use bevy_utils::{HashMap, HashSet};
+use std::any::TypeId;
+
+/// The types a dynamic plugin registered while it was built
+struct PluginTypes {
+    components: Vec<TypeId>,
+    properties: Vec<String>,
+}
+
+struct PreservedComponent {
+    entity: Entity,
+    type_name: String,
+    ron: String,
+}
+
+/// Preserves the registered components of a dynamic plugin across a reload. Before the plugin is unloaded, its
+/// components are serialized, removed from their entities and unregistered. After the new version is built, the
+/// components are deserialized using the new registrations and added back to their entities. Fields that were added
+/// in the new version are initialized using [FromResources](bevy_ecs::FromResources).
+///
+/// This hook is added by [TypeRegistryPlugin](crate::TypeRegistryPlugin).
+#[derive(Default)]
+pub struct ComponentReloadHook {
+    registered: Option<(HashSet<TypeId>, HashSet<String>)>,
+    plugin_types: HashMap<String, PluginTypes>,
+    preserved: HashMap<String, Vec<PreservedComponent>>,
+}
+
+impl PluginReloadHook for ComponentReloadHook {
+    fn build_started(&mut self, _world: &mut World, resources: &mut Resources) {
+        let type_registry = match resources.get::<TypeRegistry>() {
+            Some(type_registry) => type_registry,
+            None => return,
+        };
+        let components = type_registry
+            .component
+            .read()
+            .registrations
+            .keys()
+            .cloned()
+            .collect();
+        let properties = type_registry
+            .property
+            .read()
+            .iter()
+            .map(|registration| registration.name.to_string())
+            .collect();
+        self.registered = Some((components, properties));
+    }
+
+    fn build_finished(&mut self, plugin: &str, _world: &mut World, resources: &mut Resources) {
+        let (components, properties) = match self.registered.take() {
+            Some(registered) => registered,
+            None => return,
+        };
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let plugin_types = PluginTypes {
+            components: type_registry
+                .component
+                .read()
+                .registrations
+                .keys()
+                .filter(|type_id| !components.contains(type_id))
+                .cloned()
+                .collect(),
+            properties: type_registry
+                .property
+                .read()
+                .iter()
+                .filter(|registration| !properties.contains(registration.name))
+                .map(|registration| registration.name.to_string())
+                .collect(),
+        };
+        self.plugin_types.insert(plugin.to_string(), plugin_types);
+    }
+
+    fn before_unload(&mut self, plugin: &str, world: &mut World, resources: &mut Resources) {
+        let plugin_types = match self.plugin_types.remove(plugin) {
+            Some(plugin_types) => plugin_types,
+            None => return,
+        };
+        let type_registry = resources.get_cloned::<TypeRegistry>().unwrap();
+        let mut component_registry = type_registry.component.write();
+        let mut property_registry = type_registry.property.write();
+
+        let mut preserved = Vec::new();
+        for type_id in plugin_types.components.iter() {
+            let registration = match component_registry.unregister(type_id) {
+                Some(registration) => registration,
+                None => continue,
+            };
+
+            let mut entities = Vec::new();
+            for archetype in world.archetypes() {
+                if !archetype.has_type(*type_id) {
+                    continue;
+                }
+
+                for (index, entity) in archetype.iter_entities().enumerate() {
+                    let properties = registration.get_component_properties(archetype, index);
+                    match serialize_properties(properties, &property_registry) {
+                        Ok(ron) => preserved.push(PreservedComponent {
+                            entity: *entity,
+                            type_name: registration.long_name.to_string(),
+                            ron,
+                        }),
+                        Err(err) => log::warn!(
+                            "failed to preserve component {}: {}",
+                            registration.long_name,
+                            err
+                        ),
+                    }
+                    entities.push(*entity);
+                }
+            }
+
+            for entity in entities {
+                registration.remove_component_from_entity(world, entity);
+            }
+        }
+
+        for name in plugin_types.properties.iter() {
+            property_registry.unregister(name);
+        }
+
+        self.preserved.insert(plugin.to_string(), preserved);
+    }
+
+    fn after_reload(&mut self, plugin: &str, world: &mut World, resources: &mut Resources) {
+        let preserved = match self.preserved.remove(plugin) {
+            Some(preserved) => preserved,
+            None => return,
+        };
+        let type_registry = resources.get_cloned::<TypeRegistry>().unwrap();
+        let component_registry = type_registry.component.read();
+        let property_registry = type_registry.property.read();
+
+        for component in preserved {
+            let registration = match component_registry.get_with_full_name(&component.type_name) {
+                Some(registration) => registration,
+                None => {
+                    log::warn!(
+                        "component {} is no longer registered and was not restored",
+                        component.type_name
+                    );
+                    continue;
+                }
+            };
+
+            if !world.contains(component.entity) {
+                continue;
+            }
+
+            match deserialize_dynamic_properties(&component.ron, &property_registry) {
+                Ok(properties) => registration.add_component_to_entity(
+                    world,
+                    resources,
+                    component.entity,
+                    &properties,
+                ),
+                Err(err) => log::warn!(
+                    "failed to restore component {}: {}",
+                    component.type_name,
+                    err
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_property::Properties;
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct Health {
+        value: f32,
+    }
+
+    #[test]
+    fn preserve_components() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(TypeRegistry::default());
+        let mut hook = ComponentReloadHook::default();
+
+        let register = |resources: &Resources| {
+            let type_registry = resources.get::<TypeRegistry>().unwrap();
+            type_registry.component.write().register::<Health>();
+            type_registry.property.write().register::<Health>();
+        };
+
+        hook.build_started(&mut world, &mut resources);
+        register(&resources);
+        hook.build_finished("plugin", &mut world, &mut resources);
+
+        let entity = world.spawn((Health { value: 5.0 },));
+
+        hook.before_unload("plugin", &mut world, &mut resources);
+        assert!(world.get::<Health>(entity).is_err());
+        {
+            let type_registry = resources.get::<TypeRegistry>().unwrap();
+            assert!(type_registry
+                .component
+                .read()
+                .get(&TypeId::of::<Health>())
+                .is_none());
+            assert!(type_registry
+                .property
+                .read()
+                .get(std::any::type_name::<Health>())
+                .is_none());
+        }
+
+        hook.build_started(&mut world, &mut resources);
+        register(&resources);
+        hook.build_finished("plugin", &mut world, &mut resources);
+        hook.after_reload("plugin", &mut world, &mut resources);
+
+        assert_eq!(*world.get::<Health>(entity).unwrap(), Health { value: 5.0 });
+    }
+}
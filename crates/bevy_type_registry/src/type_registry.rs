@@ -1,5 +1,11 @@
-use bevy_ecs::{Archetype, Component, Entity, FromResources, Resources, World};
-use bevy_property::{Properties, Property, PropertyTypeRegistration, PropertyTypeRegistry};
+use bevy_ecs::{
+    Archetype, Component, Entity, EntityMap, FromResources, MapEntities, MapEntitiesError,
+    Resource, Resources, World,
+};
+use bevy_property::{
+    DynamicProperties, MethodCallError, MethodResult, Properties, Property, PropertyPatch,
+    PropertyPathError, PropertyTypeRegistration, PropertyTypeRegistry,
+};
 use bevy_utils::{HashMap, HashSet};
 use parking_lot::RwLock;
 use std::{any::TypeId, sync::Arc};
@@ -8,14 +14,62 @@ use std::{any::TypeId, sync::Arc};
 pub struct TypeRegistry {
     pub property: Arc<RwLock<PropertyTypeRegistry>>,
     pub component: Arc<RwLock<ComponentRegistry>>,
+    pub resource: Arc<RwLock<ResourceRegistry>>,
+}
+
+/// Looks up registered types by name. Types are found by their short name, unless several types share it. Ambiguous
+/// short names don't refer to any type, so those types can only be found by their full name.
+#[derive(Default)]
+pub struct TypeNames {
+    short_names: HashMap<String, TypeId>,
+    full_names: HashMap<String, TypeId>,
+    ambiguous_names: HashSet<String>,
+}
+
+impl TypeNames {
+    pub fn add(&mut self, short_name: &str, full_name: &str, ty: TypeId) {
+        self.full_names.insert(full_name.to_string(), ty);
+        if self.short_names.contains_key(short_name) || self.ambiguous_names.contains(short_name) {
+            // name is ambiguous. fall back to long names for all ambiguous types
+            self.short_names.remove(short_name);
+            self.ambiguous_names.insert(short_name.to_string());
+        } else {
+            self.short_names.insert(short_name.to_string(), ty);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.short_names.clear();
+        self.full_names.clear();
+        self.ambiguous_names.clear();
+    }
+
+    pub fn get_with_full_name(&self, full_name: &str) -> Option<TypeId> {
+        self.full_names.get(full_name).copied()
+    }
+
+    pub fn get_with_short_name(&self, short_name: &str) -> Option<TypeId> {
+        self.short_names.get(short_name).copied()
+    }
+
+    /// Looks up `type_name` as a short name and then as a full name
+    pub fn get_with_name(&self, type_name: &str) -> Option<TypeId> {
+        self.get_with_short_name(type_name)
+            .or_else(|| self.get_with_full_name(type_name))
+    }
+
+    /// Returns true if `short_name` is shared by several types
+    pub fn is_ambiguous(&self, short_name: &str) -> bool {
+        self.ambiguous_names.contains(short_name)
+    }
 }
 
 #[derive(Default)]
 pub struct ComponentRegistry {
     pub registrations: HashMap<TypeId, ComponentRegistration>,
-    pub short_names: HashMap<String, TypeId>,
-    pub full_names: HashMap<String, TypeId>,
-    pub ambigous_names: HashSet<String>,
+    pub names: TypeNames,
+    /// The components that are written to save games that only save persistent components
+    pub persistent: HashSet<TypeId>,
 }
 
 impl ComponentRegistry {
@@ -23,17 +77,46 @@ impl ComponentRegistry {
     where
         T: Properties + Component + FromResources,
     {
-        let registration = ComponentRegistration::of::<T>();
-        let short_name = registration.short_name.to_string();
-        self.full_names
-            .insert(registration.long_name.to_string(), registration.ty);
-        if self.short_names.contains_key(&short_name) || self.ambigous_names.contains(&short_name) {
-            // name is ambiguous. fall back to long names for all ambiguous types
-            self.short_names.remove(&short_name);
-            self.ambigous_names.insert(short_name);
-        } else {
-            self.short_names.insert(short_name, registration.ty);
+        self.add_registration(ComponentRegistration::of::<T>());
+    }
+
+    /// Registers a component with [Entity] fields, which are mapped to new entities when the component is copied to
+    /// other entities. See [ComponentRegistration::map_entities].
+    pub fn register_with_mapped_entities<T>(&mut self)
+    where
+        T: Properties + Component + FromResources + MapEntities,
+    {
+        self.add_registration(ComponentRegistration::of_with_mapped_entities::<T>());
+    }
+
+    /// Removes the registration for the component with the given `type_id`. Short names that were ambiguous because
+    /// of the removed type become available again.
+    pub fn unregister(&mut self, type_id: &TypeId) -> Option<ComponentRegistration> {
+        let registration = self.registrations.remove(type_id)?;
+        let registrations = std::mem::take(&mut self.registrations);
+        self.names.clear();
+        for (_, registration) in registrations {
+            self.add_registration(registration);
         }
+
+        Some(registration)
+    }
+
+    /// Adds a registered component to the allow-list of persistent components
+    pub fn set_persistent(&mut self, type_id: TypeId) {
+        self.persistent.insert(type_id);
+    }
+
+    pub fn is_persistent(&self, type_id: &TypeId) -> bool {
+        self.persistent.contains(type_id)
+    }
+
+    fn add_registration(&mut self, registration: ComponentRegistration) {
+        self.names.add(
+            &registration.short_name,
+            registration.long_name,
+            registration.ty,
+        );
         self.registrations.insert(registration.ty, registration);
     }
 
@@ -42,35 +125,39 @@ impl ComponentRegistry {
     }
 
     pub fn get_with_full_name(&self, full_name: &str) -> Option<&ComponentRegistration> {
-        self.full_names
-            .get(full_name)
-            .and_then(|id| self.registrations.get(id))
+        self.names
+            .get_with_full_name(full_name)
+            .and_then(|id| self.registrations.get(&id))
     }
 
     pub fn get_with_short_name(&self, short_name: &str) -> Option<&ComponentRegistration> {
-        self.short_names
-            .get(short_name)
-            .and_then(|id| self.registrations.get(id))
+        self.names
+            .get_with_short_name(short_name)
+            .and_then(|id| self.registrations.get(&id))
     }
 
+    /// Looks up a component by its short or full name. Returns `None` for ambiguous short names, see [TypeNames].
     pub fn get_with_name(&self, type_name: &str) -> Option<&ComponentRegistration> {
-        let mut registration = self.get_with_short_name(type_name);
-        if registration.is_none() {
-            registration = self.get_with_full_name(type_name);
-            if registration.is_none() && self.ambigous_names.contains(type_name) {
-                panic!("Type name is ambiguous: {}", type_name);
-            }
-        }
-        registration
+        self.names
+            .get_with_name(type_name)
+            .and_then(|id| self.registrations.get(&id))
     }
 }
 
+type MapEntitiesFn = fn(&mut World, &EntityMap, &[Entity]) -> Result<(), MapEntitiesError>;
+type ComponentCallFn =
+    fn(&mut World, Entity, &PropertyTypeRegistry, &str, &[&dyn Property]) -> MethodResult;
+
 #[derive(Clone)]
 pub struct ComponentRegistration {
     pub ty: TypeId,
     component_add_fn: fn(&mut World, resources: &Resources, Entity, &dyn Property),
     component_apply_fn: fn(&mut World, Entity, &dyn Property),
+    component_patch_fn: fn(&mut World, Entity, &PropertyPatch) -> Result<(), PropertyPathError>,
+    component_call_fn: ComponentCallFn,
+    component_remove_fn: fn(&mut World, Entity),
     component_properties_fn: fn(&Archetype, usize) -> &dyn Properties,
+    map_entities_fn: Option<MapEntitiesFn>,
     pub short_name: String,
     pub long_name: &'static str,
 }
@@ -92,6 +179,31 @@ impl ComponentRegistration {
                 let mut component = world.get_mut::<T>(entity).unwrap();
                 component.apply(property);
             },
+            component_patch_fn: |world: &mut World, entity: Entity, patch: &PropertyPatch| {
+                let mut component =
+                    world
+                        .get_mut::<T>(entity)
+                        .map_err(|_| PropertyPathError::MissingReceiver {
+                            type_name: std::any::type_name::<T>(),
+                        })?;
+                patch.apply(&mut *component)
+            },
+            component_call_fn: |world: &mut World,
+                                entity: Entity,
+                                registry: &PropertyTypeRegistry,
+                                method: &str,
+                                args: &[&dyn Property]| {
+                let mut component =
+                    world
+                        .get_mut::<T>(entity)
+                        .map_err(|_| MethodCallError::MissingReceiver {
+                            type_name: std::any::type_name::<T>(),
+                        })?;
+                registry.call_method(&mut *component, method, args)
+            },
+            component_remove_fn: |world: &mut World, entity: Entity| {
+                world.remove_one::<T>(entity).unwrap();
+            },
             component_properties_fn: |archetype: &Archetype, index: usize| {
                 // the type has been looked up by the caller, so this is safe
                 unsafe {
@@ -99,11 +211,29 @@ impl ComponentRegistration {
                     ptr.as_ref().unwrap()
                 }
             },
+            map_entities_fn: None,
             short_name: PropertyTypeRegistration::get_short_name(std::any::type_name::<T>()),
             long_name: std::any::type_name::<T>(),
         }
     }
 
+    pub fn of_with_mapped_entities<T: Properties + Component + FromResources + MapEntities>() -> Self
+    {
+        Self {
+            map_entities_fn: Some(
+                |world: &mut World, entity_map: &EntityMap, entities: &[Entity]| {
+                    for entity in entities.iter().copied() {
+                        if let Ok(mut component) = world.get_mut::<T>(entity) {
+                            component.map_entities(entity_map)?;
+                        }
+                    }
+                    Ok(())
+                },
+            ),
+            ..Self::of::<T>()
+        }
+    }
+
     pub fn add_component_to_entity(
         &self,
         world: &mut World,
@@ -123,6 +253,55 @@ impl ComponentRegistration {
         (self.component_apply_fn)(world, entity, property);
     }
 
+    /// Applies the patch to the entity's component. Nothing is changed if one of the patched paths doesn't exist.
+    /// Fails with [PropertyPathError::MissingReceiver] if the entity doesn't exist or doesn't have the component.
+    pub fn apply_patch_to_entity(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        patch: &PropertyPatch,
+    ) -> Result<(), PropertyPathError> {
+        (self.component_patch_fn)(world, entity, patch)
+    }
+
+    /// Calls a method that was registered with [PropertyTypeRegistry::register_method] on the entity's component.
+    /// Fails with [MethodCallError::MissingReceiver] if the entity doesn't exist or doesn't have the component.
+    pub fn call_method_on_entity(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        registry: &PropertyTypeRegistry,
+        method: &str,
+        args: &[&dyn Property],
+    ) -> MethodResult {
+        (self.component_call_fn)(world, entity, registry, method, args)
+    }
+
+    pub fn remove_component_from_entity(&self, world: &mut World, entity: Entity) {
+        (self.component_remove_fn)(world, entity);
+    }
+
+    /// Returns true if the component was registered with
+    /// [ComponentRegistry::register_with_mapped_entities]
+    pub fn maps_entities(&self) -> bool {
+        self.map_entities_fn.is_some()
+    }
+
+    /// Maps the entities that the component refers to on each of `entities`. This is called after the components
+    /// were copied from the keys of `entity_map` to its values; only the entities whose component was copied should
+    /// be passed. Components that were registered without mapped entities are left unchanged.
+    pub fn map_entities(
+        &self,
+        world: &mut World,
+        entity_map: &EntityMap,
+        entities: &[Entity],
+    ) -> Result<(), MapEntitiesError> {
+        match self.map_entities_fn {
+            Some(map_entities_fn) => map_entities_fn(world, entity_map, entities),
+            None => Ok(()),
+        }
+    }
+
     pub fn get_component_properties<'a>(
         &self,
         archetype: &'a Archetype,
@@ -131,3 +310,303 @@ impl ComponentRegistration {
         (self.component_properties_fn)(archetype, entity_index)
     }
 }
+
+/// Registers resources that can be stored in scenes. Resources are looked up by name like components in the
+/// [ComponentRegistry].
+#[derive(Default)]
+pub struct ResourceRegistry {
+    pub registrations: HashMap<TypeId, ResourceRegistration>,
+    pub names: TypeNames,
+}
+
+impl ResourceRegistry {
+    pub fn register<T>(&mut self)
+    where
+        T: Properties + Resource + FromResources,
+    {
+        let registration = ResourceRegistration::of::<T>();
+        self.names.add(
+            &registration.short_name,
+            registration.long_name,
+            registration.ty,
+        );
+        self.registrations.insert(registration.ty, registration);
+    }
+
+    pub fn get(&self, type_id: &TypeId) -> Option<&ResourceRegistration> {
+        self.registrations.get(type_id)
+    }
+
+    pub fn get_with_full_name(&self, full_name: &str) -> Option<&ResourceRegistration> {
+        self.names
+            .get_with_full_name(full_name)
+            .and_then(|id| self.registrations.get(&id))
+    }
+
+    pub fn get_with_short_name(&self, short_name: &str) -> Option<&ResourceRegistration> {
+        self.names
+            .get_with_short_name(short_name)
+            .and_then(|id| self.registrations.get(&id))
+    }
+
+    /// Looks up a resource by its short or full name. Returns `None` for ambiguous short names, see [TypeNames].
+    pub fn get_with_name(&self, type_name: &str) -> Option<&ResourceRegistration> {
+        self.names
+            .get_with_name(type_name)
+            .and_then(|id| self.registrations.get(&id))
+    }
+}
+
+#[derive(Clone)]
+pub struct ResourceRegistration {
+    pub ty: TypeId,
+    resource_add_fn: fn(&mut Resources, &dyn Property),
+    resource_apply_fn: fn(&Resources, &dyn Property) -> bool,
+    resource_properties_fn: fn(&Resources) -> Option<DynamicProperties>,
+    resource_call_fn: fn(&Resources, &PropertyTypeRegistry, &str, &[&dyn Property]) -> MethodResult,
+    pub short_name: String,
+    pub long_name: &'static str,
+}
+
+impl ResourceRegistration {
+    pub fn of<T: Properties + Resource + FromResources>() -> Self {
+        Self {
+            ty: TypeId::of::<T>(),
+            resource_add_fn: |resources: &mut Resources, property: &dyn Property| {
+                let mut resource = T::from_resources(resources);
+                resource.apply(property);
+                resources.insert(resource);
+            },
+            resource_apply_fn: |resources: &Resources, property: &dyn Property| match resources
+                .get_mut::<T>()
+            {
+                Some(mut resource) => {
+                    resource.apply(property);
+                    true
+                }
+                None => false,
+            },
+            resource_properties_fn: |resources: &Resources| {
+                resources.get::<T>().map(|resource| resource.to_dynamic())
+            },
+            resource_call_fn: |resources: &Resources,
+                               registry: &PropertyTypeRegistry,
+                               method: &str,
+                               args: &[&dyn Property]| {
+                let mut resource =
+                    resources
+                        .get_mut::<T>()
+                        .ok_or(MethodCallError::MissingReceiver {
+                            type_name: std::any::type_name::<T>(),
+                        })?;
+                registry.call_method(&mut *resource, method, args)
+            },
+            short_name: PropertyTypeRegistration::get_short_name(std::any::type_name::<T>()),
+            long_name: std::any::type_name::<T>(),
+        }
+    }
+
+    /// Inserts the resource, replacing the existing value. Properties missing from `property` are initialized with
+    /// [FromResources].
+    pub fn add_resource(&self, resources: &mut Resources, property: &dyn Property) {
+        (self.resource_add_fn)(resources, property);
+    }
+
+    /// Applies `property` to the existing resource. Returns false if the resource doesn't exist.
+    pub fn apply_resource(&self, resources: &Resources, property: &dyn Property) -> bool {
+        (self.resource_apply_fn)(resources, property)
+    }
+
+    /// Returns a copy of the resource's properties, or `None` if the resource doesn't exist
+    pub fn get_resource_properties(&self, resources: &Resources) -> Option<DynamicProperties> {
+        (self.resource_properties_fn)(resources)
+    }
+
+    /// Calls a method that was registered with [PropertyTypeRegistry::register_method] on the resource. Fails with
+    /// [MethodCallError::MissingReceiver] if the resource doesn't exist.
+    pub fn call_method_on_resource(
+        &self,
+        resources: &Resources,
+        registry: &PropertyTypeRegistry,
+        method: &str,
+        args: &[&dyn Property],
+    ) -> MethodResult {
+        (self.resource_call_fn)(resources, registry, method, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_property::{PropertiesVal, PropertyPath};
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct Inventory {
+        items: Vec<u32>,
+        gold: u64,
+    }
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct Character {
+        inventory: Inventory,
+        velocity: Velocity,
+    }
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct Velocity(f32, f32);
+
+    mod other {
+        use bevy_property::Properties;
+
+        #[derive(Properties, Default)]
+        pub struct Velocity(pub f32);
+    }
+
+    fn path(path: &str) -> PropertyPath {
+        path.parse().unwrap()
+    }
+
+    #[test]
+    fn ambiguous_names() {
+        let velocity = std::any::type_name::<Velocity>();
+        let other_velocity = std::any::type_name::<other::Velocity>();
+
+        let mut components = ComponentRegistry::default();
+        components.register::<Velocity>();
+        assert_eq!(
+            components.get_with_name("Velocity").unwrap().long_name,
+            velocity
+        );
+        components.register::<other::Velocity>();
+        assert!(components.names.is_ambiguous("Velocity"));
+        assert!(components.get_with_name("Velocity").is_none());
+        assert_eq!(
+            components.get_with_name(other_velocity).unwrap().long_name,
+            other_velocity
+        );
+        components.unregister(&TypeId::of::<other::Velocity>());
+        assert!(components.get_with_name("Velocity").is_some());
+
+        let mut resources = ResourceRegistry::default();
+        resources.register::<Velocity>();
+        resources.register::<other::Velocity>();
+        assert!(resources.get_with_name("Velocity").is_none());
+        assert!(resources.get_with_name(velocity).is_some());
+    }
+
+    #[test]
+    fn apply_patch_to_entity() {
+        let mut world = World::default();
+        let entity = world.spawn((Character {
+            inventory: Inventory {
+                items: vec![1, 2, 3],
+                gold: 10,
+            },
+            velocity: Velocity(1.0, 2.0),
+        },));
+        let registration = ComponentRegistration::of::<Character>();
+
+        let patch = PropertyPatch::new()
+            .with(path("inventory.items[1]"), 5u32)
+            .with(path("velocity.1"), 4.0f32);
+        registration
+            .apply_patch_to_entity(&mut world, entity, &patch)
+            .unwrap();
+        let character = world.get::<Character>(entity).unwrap();
+        assert_eq!(
+            *character,
+            Character {
+                inventory: Inventory {
+                    items: vec![1, 5, 3],
+                    gold: 10,
+                },
+                velocity: Velocity(1.0, 4.0),
+            }
+        );
+        assert_eq!(
+            character.path_val::<u64>(&path("inventory.gold")),
+            Some(&10)
+        );
+
+        let patch = PropertyPatch::new().with(path("inventory.items[3]"), 5u32);
+        assert_eq!(
+            registration.apply_patch_to_entity(&mut world, entity, &patch),
+            Err(PropertyPathError::MissingProperty {
+                path: "inventory.items[3]".to_string()
+            })
+        );
+
+        // entities without the component and despawned entities can't be patched
+        let missing = Err(PropertyPathError::MissingReceiver {
+            type_name: std::any::type_name::<Character>(),
+        });
+        let other_entity = world.spawn((Velocity(1.0, 2.0),));
+        assert_eq!(
+            registration.apply_patch_to_entity(&mut world, other_entity, &patch),
+            missing
+        );
+        world.despawn(entity).unwrap();
+        assert_eq!(
+            registration.apply_patch_to_entity(&mut world, entity, &patch),
+            missing
+        );
+    }
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct Score {
+        points: u32,
+    }
+
+    impl Score {
+        fn add(&mut self, points: u32) -> u32 {
+            self.points += points;
+            self.points
+        }
+    }
+
+    #[test]
+    fn call_methods_on_components_and_resources() {
+        let mut registry = PropertyTypeRegistry::default();
+        registry.register_method("add", Score::add);
+
+        let mut world = World::default();
+        let entity = world.spawn((Score { points: 1 },));
+        let result = ComponentRegistration::of::<Score>()
+            .call_method_on_entity(&mut world, entity, &registry, "add", &[&2u32])
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.any().downcast_ref::<u32>(), Some(&3));
+        assert_eq!(*world.get::<Score>(entity).unwrap(), Score { points: 3 });
+
+        // entities without the component and despawned entities have no receiver
+        let missing = Err(MethodCallError::MissingReceiver {
+            type_name: std::any::type_name::<Score>(),
+        });
+        let other_entity = world.spawn((Velocity(1.0, 2.0),));
+        world.despawn(entity).unwrap();
+        for entity in [other_entity, entity].iter() {
+            let result = ComponentRegistration::of::<Score>().call_method_on_entity(
+                &mut world,
+                *entity,
+                &registry,
+                "add",
+                &[&2u32],
+            );
+            assert_eq!(result.map(|_| ()), missing);
+        }
+
+        let mut resources = Resources::default();
+        let registration = ResourceRegistration::of::<Score>();
+        assert_eq!(
+            registration
+                .call_method_on_resource(&resources, &registry, "add", &[&2u32])
+                .map(|_| ()),
+            missing
+        );
+        resources.insert(Score { points: 5 });
+        registration
+            .call_method_on_resource(&resources, &registry, "add", &[&2u32])
+            .unwrap();
+        assert_eq!(*resources.get::<Score>().unwrap(), Score { points: 7 });
+    }
+}
@@ -39,10 +39,9 @@ pub fn text_system(
         let mut glyphs_to_queue = Vec::new();
         println!("queue {}", queued_text_glyphs.glyphs.len());
         for (font_handle, FloatOrd(font_size), character) in queued_text_glyphs.glyphs.drain() {
-            let font_atlases = font_atlas_sets
-                .get_or_insert_with(Handle::from_id(font_handle.id), || {
-                    FontAtlasSet::new(font_handle)
-                });
+            let font_atlases = font_atlas_sets.get_or_insert_with(font_handle.id, || {
+                FontAtlasSet::new(font_handle.clone_weak())
+            });
 
             // try adding the glyph to an atlas. if it fails, re-queue
             if let Ok(char_str) = std::str::from_utf8(&[character as u8]) {
@@ -66,9 +65,7 @@ pub fn text_system(
 
     for (text, mut calculated_size) in &mut query.iter() {
         let font_atlases = font_atlas_sets
-            .get_or_insert_with(Handle::from_id(text.font.id), || {
-                FontAtlasSet::new(text.font)
-            });
+            .get_or_insert_with(text.font.id, || FontAtlasSet::new(text.font.clone_weak()));
         // TODO: this call results in one or more TextureAtlases, whose render resources are created in the RENDER_GRAPH_SYSTEMS
         // stage. That logic runs _before_ the DRAW stage, which means we cant call add_glyphs_to_atlas in the draw stage
         // without our render resources being a frame behind. Therefore glyph atlasing either needs its own system or the TextureAtlas
@@ -86,7 +83,7 @@ pub fn text_system(
         } else {
             for character in text.value.chars() {
                 queued_text_glyphs.glyphs.insert((
-                    text.font,
+                    text.font.clone_weak(),
                     FloatOrd(text.style.font_size),
                     character,
                 ));
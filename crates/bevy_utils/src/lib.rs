@@ -1,5 +1,9 @@
 pub use ahash::AHasher;
 use ahash::RandomState;
+use std::{future::Future, pin::Pin};
+
+/// A boxed future that can be sent to other threads. This is used to return futures from trait methods.
+pub type BoxedFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 pub type HashMap<K, V> = std::collections::HashMap<K, V, RandomState>;
 pub type HashSet<K> = std::collections::HashSet<K, RandomState>;
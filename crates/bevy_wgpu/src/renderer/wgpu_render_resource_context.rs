@@ -377,10 +377,13 @@ impl RenderResourceContext for WgpuRenderResourceContext {
             .map(|c| c.wgpu_into())
             .collect::<Vec<wgpu::ColorStateDescriptor>>();
 
-        self.create_shader_module(pipeline_descriptor.shader_stages.vertex, shaders);
+        self.create_shader_module(
+            pipeline_descriptor.shader_stages.vertex.clone_weak(),
+            shaders,
+        );
 
-        if let Some(fragment_handle) = pipeline_descriptor.shader_stages.fragment {
-            self.create_shader_module(fragment_handle, shaders);
+        if let Some(fragment_handle) = &pipeline_descriptor.shader_stages.fragment {
+            self.create_shader_module(fragment_handle.clone_weak(), shaders);
         }
 
         let shader_modules = self.resources.shader_modules.read();
@@ -388,8 +391,8 @@ impl RenderResourceContext for WgpuRenderResourceContext {
             .get(&pipeline_descriptor.shader_stages.vertex)
             .unwrap();
 
-        let fragment_shader_module = match pipeline_descriptor.shader_stages.fragment {
-            Some(fragment_handle) => Some(shader_modules.get(&fragment_handle).unwrap()),
+        let fragment_shader_module = match &pipeline_descriptor.shader_stages.fragment {
+            Some(fragment_handle) => Some(shader_modules.get(fragment_handle).unwrap()),
             None => None,
         };
 
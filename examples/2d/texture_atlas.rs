@@ -49,11 +49,11 @@ fn load_atlas(
         }
 
         let texture_atlas = texture_atlas_builder.finish(&mut textures).unwrap();
-        let texture_atlas_texture = texture_atlas.texture;
+        let texture_atlas_texture = texture_atlas.texture.clone();
         let vendor_handle = asset_server
             .get_handle("assets/textures/rpg/chars/vendor/generic-rpg-vendor.png")
             .unwrap();
-        let vendor_index = texture_atlas.get_texture_index(vendor_handle).unwrap();
+        let vendor_index = texture_atlas.get_texture_index(&vendor_handle).unwrap();
         let atlas_handle = texture_atlases.add(texture_atlas);
 
         // set up a scene to display our texture atlas
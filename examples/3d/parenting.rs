@@ -36,8 +36,8 @@ fn setup(
     commands
         // parent cube
         .spawn(PbrComponents {
-            mesh: cube_handle,
-            material: cube_material_handle,
+            mesh: cube_handle.clone(),
+            material: cube_material_handle.clone(),
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
             ..Default::default()
         })
@@ -45,8 +45,8 @@ fn setup(
         .with_children(|parent| {
             // child cube
             parent.spawn(PbrComponents {
-                mesh: cube_handle,
-                material: cube_material_handle,
+                mesh: cube_handle.clone(),
+                material: cube_material_handle.clone(),
                 transform: Transform::from_translation(Vec3::new(0.0, 0.0, 3.0)),
                 ..Default::default()
             });
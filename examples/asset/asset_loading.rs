@@ -28,6 +28,11 @@ fn setup(
     // You can load individual assets like this:
     let cube_handle = asset_server.load("assets/models/cube/cube.gltf").unwrap();
 
+    // Files can contain more than one asset. Each of them has a label, which can be used to load it directly:
+    let _cube_mesh_handle: Handle<Mesh> = asset_server
+        .load("assets/models/cube/cube.gltf#Mesh0/Primitive0")
+        .unwrap();
+
     // Assets are loaded in the background by default, which means they might not be available immediately after calling load().
     // If you need immediate access you can load assets synchronously like this:
     let sphere_handle = asset_server
@@ -48,14 +53,14 @@ fn setup(
         // monkey
         .spawn(PbrComponents {
             mesh: monkey_handle,
-            material: material_handle,
+            material: material_handle.clone(),
             transform: Transform::from_translation(Vec3::new(-3.0, 0.0, 0.0)),
             ..Default::default()
         })
         // cube
         .spawn(PbrComponents {
             mesh: cube_handle,
-            material: material_handle,
+            material: material_handle.clone(),
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
             ..Default::default()
         })
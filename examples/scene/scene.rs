@@ -109,7 +109,11 @@ fn save_scene_system(_world: &mut World, resources: &mut Resources) {
 
     // The component registry resource contains information about all registered components. This is used to construct scenes.
     let type_registry = resources.get::<TypeRegistry>().unwrap();
-    let scene = Scene::from_world(&world, &type_registry.component.read());
+    let mut scene = Scene::from_world(&world, &type_registry.component.read());
+
+    // Handles refer to assets by an id that is only valid while the app runs. Before a scene is saved, its handles
+    // should refer to the paths of their assets instead. The assets are loaded again when the scene is spawned.
+    scene.store_asset_paths(&resources.get::<AssetServer>().unwrap());
 
     // Scenes can be serialized like this:
     println!(
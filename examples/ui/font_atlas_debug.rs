@@ -45,7 +45,7 @@ fn atlas_render_system(
                 .unwrap();
             state.atlas_count += 1;
             commands.spawn(ImageComponents {
-                material: materials.add(texture_atlas.texture.into()),
+                material: materials.add(texture_atlas.texture.clone().into()),
                 style: Style {
                     position_type: PositionType::Absolute,
                     position: Rect {
@@ -74,7 +74,7 @@ fn text_update_system(mut state: ResMut<State>, time: Res<Time>, mut query: Quer
 
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>, mut state: ResMut<State>) {
     let font_handle = asset_server.load("assets/fonts/FiraSans-Bold.ttf").unwrap();
-    state.handle = font_handle;
+    state.handle = font_handle.clone();
     commands
         .spawn(UiCameraComponents::default())
         .spawn(TextComponents {
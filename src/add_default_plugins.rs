@@ -1,50 +1,58 @@
-use crate::app::AppBuilder;
-
-pub trait AddDefaultPlugins {
-    fn add_default_plugins(&mut self) -> &mut Self;
-}
-
-impl AddDefaultPlugins for AppBuilder {
-    fn add_default_plugins(&mut self) -> &mut Self {
-        self.add_plugin(bevy_type_registry::TypeRegistryPlugin::default());
-        self.add_plugin(bevy_core::CorePlugin::default());
-        self.add_plugin(bevy_transform::TransformPlugin::default());
-        self.add_plugin(bevy_diagnostic::DiagnosticsPlugin::default());
-        self.add_plugin(bevy_input::InputPlugin::default());
-        self.add_plugin(bevy_window::WindowPlugin::default());
-        self.add_plugin(bevy_asset::AssetPlugin::default());
-        self.add_plugin(bevy_scene::ScenePlugin::default());
+use crate::app::{AppBuilder, PluginGroup, PluginGroupBuilder};
+
+/// The plugins that make up a "full" Bevy app, subject to the enabled cargo features.
+/// Individual plugins can be disabled, replaced or reordered using [AppBuilder::add_plugins_with].
+pub struct DefaultPlugins;
+
+impl PluginGroup for DefaultPlugins {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        group.add(bevy_type_registry::TypeRegistryPlugin::default());
+        group.add(bevy_core::CorePlugin::default());
+        group.add(bevy_transform::TransformPlugin::default());
+        group.add(bevy_diagnostic::DiagnosticsPlugin::default());
+        group.add(bevy_input::InputPlugin::default());
+        group.add(bevy_window::WindowPlugin::default());
+        group.add(bevy_asset::AssetPlugin::default());
+        group.add(bevy_scene::ScenePlugin::default());
 
         #[cfg(feature = "bevy_render")]
-        self.add_plugin(bevy_render::RenderPlugin::default());
+        group.add(bevy_render::RenderPlugin::default());
 
         #[cfg(feature = "bevy_sprite")]
-        self.add_plugin(bevy_sprite::SpritePlugin::default());
+        group.add(bevy_sprite::SpritePlugin::default());
 
         #[cfg(feature = "bevy_pbr")]
-        self.add_plugin(bevy_pbr::PbrPlugin::default());
+        group.add(bevy_pbr::PbrPlugin::default());
 
         #[cfg(feature = "bevy_ui")]
-        self.add_plugin(bevy_ui::UiPlugin::default());
+        group.add(bevy_ui::UiPlugin::default());
 
         #[cfg(feature = "bevy_text")]
-        self.add_plugin(bevy_text::TextPlugin::default());
+        group.add(bevy_text::TextPlugin::default());
 
         #[cfg(feature = "bevy_audio")]
-        self.add_plugin(bevy_audio::AudioPlugin::default());
+        group.add(bevy_audio::AudioPlugin::default());
 
         #[cfg(feature = "bevy_gilrs")]
-        self.add_plugin(bevy_gilrs::GilrsPlugin::default());
+        group.add(bevy_gilrs::GilrsPlugin::default());
 
         #[cfg(feature = "bevy_gltf")]
-        self.add_plugin(bevy_gltf::GltfPlugin::default());
+        group.add(bevy_gltf::GltfPlugin::default());
 
         #[cfg(feature = "bevy_winit")]
-        self.add_plugin(bevy_winit::WinitPlugin::default());
+        group.add(bevy_winit::WinitPlugin::default());
 
         #[cfg(feature = "bevy_wgpu")]
-        self.add_plugin(bevy_wgpu::WgpuPlugin::default());
+        group.add(bevy_wgpu::WgpuPlugin::default());
+    }
+}
 
-        self
+pub trait AddDefaultPlugins {
+    fn add_default_plugins(&mut self) -> &mut Self;
+}
+
+impl AddDefaultPlugins for AppBuilder {
+    fn add_default_plugins(&mut self) -> &mut Self {
+        self.add_plugins(DefaultPlugins)
     }
 }